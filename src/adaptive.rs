@@ -0,0 +1,102 @@
+//! Adaptive mode. Answers given since the last change of difficulty are assessed after every
+//! question: a streak of fast, correct answers makes questions harder, while repeated misses
+//! or slow answers make them easier again.
+
+use std::time::Duration;
+
+use crate::skill::Adjustment;
+use crate::stats::Record;
+
+/// Number of recent answers that need to be correct (and fast) to make questions harder
+const FLUENT_STREAK: usize = 5;
+/// Maximum average time of the streak to consider the answers fluent
+const FLUENT_TIME: Duration = Duration::from_secs(6);
+/// Number of recent answers checked for misses and slow answers
+const STRUGGLE_WINDOW: usize = 3;
+/// Number of misses within the window that makes questions easier
+const STRUGGLE_MISSES: usize = 2;
+/// Answers slower than that are considered a struggle
+const SLOW_TIME: Duration = Duration::from_secs(15);
+
+/// Decides whether the difficulty should change, based on records of the answers given
+/// since the last change.
+pub fn assess(records: &[Record]) -> Option<Adjustment> {
+    if records.len() >= STRUGGLE_WINDOW {
+        let recent = &records[records.len() - STRUGGLE_WINDOW..];
+        let misses = recent.iter().filter(|record| !record.correct).count();
+        let all_slow = recent.iter().all(|record| record.duration > SLOW_TIME);
+        if misses >= STRUGGLE_MISSES || all_slow {
+            return Some(Adjustment::Easier);
+        }
+    }
+
+    if records.len() >= FLUENT_STREAK {
+        let recent = &records[records.len() - FLUENT_STREAK..];
+        let all_correct = recent.iter().all(|record| record.correct);
+        let total_time: Duration = recent.iter().map(|record| record.duration).sum();
+        let streak = u32::try_from(FLUENT_STREAK).expect("FLUENT_STREAK fits in u32");
+        if all_correct && total_time / streak <= FLUENT_TIME {
+            return Some(Adjustment::Harder);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(correct: bool, secs: u64) -> Record {
+        Record {
+            prompt: "Question".to_string(),
+            correct_answer: "Answer".to_string(),
+            given_answers: vec!["Answer".to_string()],
+            correct,
+            duration: Duration::from_secs(secs),
+            timed_out: false,
+            categories: vec![],
+        }
+    }
+
+    #[test]
+    fn not_enough_answers() {
+        assert_eq!(assess(&[]), None);
+        assert_eq!(assess(&[record(false, 1), record(false, 1)]), None);
+        assert_eq!(assess(&vec![record(true, 1); 4]), None);
+    }
+
+    #[test]
+    fn fluent_answers() {
+        assert_eq!(assess(&vec![record(true, 2); 5]), Some(Adjustment::Harder));
+        assert_eq!(
+            assess(&[
+                record(false, 2),
+                record(true, 10),
+                record(true, 1),
+                record(true, 1),
+                record(true, 1),
+                record(true, 1),
+            ]),
+            Some(Adjustment::Harder)
+        );
+        // Correct, but too slow on average
+        assert_eq!(assess(&vec![record(true, 7); 5]), None);
+    }
+
+    #[test]
+    fn struggling() {
+        assert_eq!(
+            assess(&[record(false, 1), record(true, 1), record(false, 1)]),
+            Some(Adjustment::Easier)
+        );
+        assert_eq!(
+            assess(&[record(true, 16), record(true, 20), record(true, 30)]),
+            Some(Adjustment::Easier)
+        );
+        assert_eq!(
+            assess(&[record(false, 1), record(true, 1), record(true, 1)]),
+            None
+        );
+    }
+}
@@ -1,28 +1,57 @@
 use std::borrow::Borrow;
 use std::io::{self, Write};
+use std::path::Path;
 use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
+use chrono::Local;
 use crossterm::style::Color;
 use rand::Rng;
 
+use crate::adaptive;
 use crate::args::prelude::*;
 use crate::colour;
 use crate::config::{BehaviourOnError, Config, NumberOfQuestions};
+use crate::export;
+use crate::history::{self, command as history_command};
+use crate::input::Input;
 use crate::question::{Generator, Question};
+use crate::repetition;
+use crate::skill::arithmetic;
+use crate::skill::bases;
+use crate::skill::calendar;
+use crate::skill::computus;
+use crate::skill::division;
 use crate::skill::doomsday_algorithm;
+use crate::skill::gcd;
 use crate::skill::powers;
+use crate::skill::primes;
+use crate::skill::roman;
+use crate::skill::squares;
 use crate::skill::times_table;
-use crate::skill::Skill;
+use crate::skill::{Adjustment, Skill};
 use crate::stats;
+use crate::worksheet;
 
 pub const APP_NAME: &str = env!("CARGO_PKG_NAME");
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-const COMMANDS: [help::Command; 3] = [
+const COMMANDS: [help::Command; 13] = [
     help::Command::new(powers::CMD, "Practise powers (configurable base)."),
     help::Command::new(times_table::CMD, "Practise multiplication table."),
     help::Command::new(doomsday_algorithm::CMD, "Practise the Doomsday algorithm."),
+    help::Command::new(arithmetic::CMD, "Practise addition and subtraction."),
+    help::Command::new(division::CMD, "Practise division (also with remainders)."),
+    help::Command::new(squares::CMD, "Practise squares, cubes and roots."),
+    help::Command::new(calendar::CMD, "Practise day of the year and day counting."),
+    help::Command::new(computus::CMD, "Practise calculating the date of Easter."),
+    help::Command::new(bases::CMD, "Practise binary, octal and hex conversions."),
+    help::Command::new(roman::CMD, "Practise Roman numerals."),
+    help::Command::new(primes::CMD, "Practise primality and prime factorisation."),
+    help::Command::new(gcd::CMD, "Practise greatest common divisors and LCMs."),
+    help::Command::new(history_command::CMD, "Browse and summarise past sessions."),
 ];
 
 pub struct Application;
@@ -47,9 +76,19 @@ impl Application {
     /// Panics if ctrlc crate fails to set the ctrl-c handler.
     /// Panics if any internal error happens. It shouldn't, but it might.
     pub fn run(config: Config) {
+        let repetition = config.options.repetition_file.as_ref().map(|path| {
+            repetition::Scheduler::load(path).unwrap_or_else(|err| {
+                eprintln!("{APP_NAME}: {err}");
+                process::exit(1);
+            })
+        });
+
         let app = Arc::new(AppImpl {
             config,
             stats: stats::Lock::new(),
+            repetition,
+            adapted_at: AtomicUsize::new(0),
+            input: Input::new(),
         });
 
         let app_ref = app.clone();
@@ -83,6 +122,10 @@ impl Application {
 struct AppImpl {
     config: Config,
     stats: stats::Lock,
+    repetition: Option<repetition::Scheduler>,
+    /// Number of questions answered when the difficulty was last assessed (adaptive mode)
+    adapted_at: AtomicUsize,
+    input: Input,
 }
 
 impl AppImpl {
@@ -93,16 +136,74 @@ impl AppImpl {
         } else if self.config.options.show_version {
             Application::print_version();
             return;
+        } else if let Some(history) = &self.config.history {
+            if history.wants_to_print_help() {
+                println!("{}", history.get_help_text());
+            } else {
+                self.print_history(history);
+            }
+            return;
         } else if let Some(skill) = &self.config.skill {
             if skill.wants_to_print_help() {
                 println!("{}", skill.get_help_text());
                 return;
             }
+            if let Some(path) = &self.config.options.worksheet_file {
+                self.write_worksheet(path);
+                return;
+            }
         }
 
         self.play();
     }
 
+    fn print_history(&self, history: &history_command::History) {
+        let Some(path) = &self.config.options.history_file else {
+            eprintln!("{APP_NAME}: history is disabled");
+            process::exit(1);
+        };
+        match history::load(path) {
+            Ok(sessions) => println!("{}", history.get_report(&sessions)),
+            Err(err) => {
+                eprintln!("{APP_NAME}: {err}");
+                process::exit(1);
+            }
+        }
+    }
+
+    fn write_worksheet(&self, path: &Path) {
+        let generator = Generator::new(
+            self.number_of_questions(),
+            self.get_skill(),
+            None,
+            self.config.options.seed,
+        );
+        let mut questions = vec![];
+        while generator.has_next_question() {
+            questions.push(
+                generator
+                    .next_question()
+                    .expect("next_question called even though there were no questions left"),
+            );
+        }
+
+        let command = self.config.command.as_deref().unwrap_or_default();
+        match worksheet::write(path, command, &questions) {
+            Ok(answer_key_path) => println!(
+                "Worksheet written to '{}', answer key to '{}'.",
+                path.display(),
+                answer_key_path.display()
+            ),
+            Err(err) => {
+                eprintln!(
+                    "{APP_NAME}: could not write worksheet to '{}': {err}",
+                    path.display()
+                );
+                process::exit(1);
+            }
+        }
+    }
+
     fn print_help(&self) {
         let definitions = &self.config.options.arg_definitions;
         let options = help::Options::new("General options", definitions);
@@ -118,11 +219,19 @@ impl AppImpl {
     fn handle_interrupt(&self) {
         println!();
         self.print_stats_post_game();
+        self.save_export(false);
+        self.save_history(false);
+        self.save_repetition();
         process::exit(1);
     }
 
     fn play(&self) {
-        let generator = Generator::new(self.number_of_questions(), self.get_skill());
+        let generator = Generator::new(
+            self.number_of_questions(),
+            self.get_skill(),
+            self.repetition.as_ref(),
+            self.config.options.seed,
+        );
 
         self.before_game();
 
@@ -131,19 +240,32 @@ impl AppImpl {
                 &generator
                     .next_question()
                     .expect("next_question called even though there were no questions left"),
+                generator.deadline(),
             );
+            if self.adapt_difficulty() {
+                generator.refresh();
+            }
         }
 
         self.print_stats_post_game();
+        self.save_export(true);
+        self.save_history(true);
+        self.save_repetition();
     }
 
     fn before_game(&self) {
         self.print_intro();
 
+        if self.config.options.adaptive {
+            self.get_skill().adapt(Adjustment::Start);
+        }
+
         self.stats.start(self.number_of_questions());
     }
 
-    fn handle_question(&self, question: &Question) {
+    /// Asks `question`. If `deadline` (of the whole game) passes before the question is
+    /// answered, it is abandoned.
+    fn handle_question(&self, question: &Question, deadline: Option<Instant>) {
         println!(
             "\n{}{}",
             colour::format_text("Q: ", self.use_colour(), Color::DarkYellow),
@@ -155,41 +277,100 @@ impl AppImpl {
         );
         io::stdout().flush().expect("IO operation failed (flush)");
 
-        self.stats.start_new_question();
-
-        let mut answer = Self::get_input();
+        if self.config.options.adaptive {
+            if let Some(range) = self.get_skill().current_range() {
+                self.stats.set_current_range(&range);
+            }
+        }
+        self.stats.start_new_question(question);
+        let answer_deadline = self
+            .config
+            .options
+            .question_timeout
+            .map(|timeout| Instant::now() + timeout)
+            .into_iter()
+            .chain(deadline)
+            .min();
+
+        let Some(mut answer) = self.input.read_line(answer_deadline) else {
+            self.handle_timeout(question, deadline, true);
+            return;
+        };
         let mut correct = question.is_answer_correct(&answer);
 
-        self.stats.answer_question(correct);
-        self.print_answer_feedback(correct, question.correct_answer());
+        self.stats.answer_question(&answer, correct);
+        self.review(question, correct);
+        self.print_answer_feedback(correct, question);
 
         if let BehaviourOnError::Repeat = self.config.options.behaviour_on_error {
             while !correct {
                 print!("A: ");
                 io::stdout().flush().expect("IO operation failed (flush)");
-                answer = Self::get_input();
+                let Some(next_answer) = self.input.read_line(answer_deadline) else {
+                    self.handle_timeout(question, deadline, false);
+                    return;
+                };
+                answer = next_answer;
                 correct = question.is_answer_correct(&answer);
-                self.stats.answer_question(correct);
-                self.print_answer_feedback(correct, question.correct_answer());
+                self.stats.answer_question(&answer, correct);
+                self.print_answer_feedback(correct, question);
             }
         }
 
         if !self.config.options.disable_live_statistics {
-            self.print_stats_in_between();
+            self.print_stats_in_between(deadline);
+        }
+    }
+
+    /// Changes the difficulty if answers given since the last change call for it.
+    /// Returns `true` if the skill changed its range.
+    fn adapt_difficulty(&self) -> bool {
+        if !self.config.options.adaptive {
+            return false;
+        }
+        let records = self.stats.get_records();
+        let since = self.adapted_at.load(Ordering::Relaxed).min(records.len());
+        let Some(adjustment) = adaptive::assess(&records[since..]) else {
+            return false;
+        };
+        self.adapted_at.store(records.len(), Ordering::Relaxed);
+        self.get_skill().adapt(adjustment)
+    }
+
+    /// Handles `question` not answered in time. Either the game is over (`deadline` passed)
+    /// or the question timed out and counts as wrong.
+    fn handle_timeout(&self, question: &Question, deadline: Option<Instant>, first_attempt: bool) {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.print_time_up();
+            return;
+        }
+
+        self.stats.time_out_question();
+        self.input.discard_pending();
+        if first_attempt {
+            self.review(question, false);
+        }
+        self.print_timeout_feedback(question);
+
+        if !self.config.options.disable_live_statistics {
+            self.print_stats_in_between(deadline);
         }
     }
 
-    fn get_input() -> String {
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .expect("IO operation failed (stdin)");
-        input.trim().to_string()
+    fn print_time_up(&self) {
+        println!();
+        println!(
+            "{}",
+            colour::format_text("Time is up!", self.use_colour(), Color::Yellow)
+        );
     }
 
     fn print_intro(&self) {
         let number_of_questions = match self.number_of_questions() {
             NumberOfQuestions::Infinite => "Infinite questions",
+            NumberOfQuestions::TimeLimited(limit) => {
+                &format!("Time limit: {}", stats::format_duration(&limit))
+            }
             NumberOfQuestions::Limited(num) => {
                 if num > 1 {
                     &format!("{num} questions")
@@ -206,7 +387,7 @@ impl AppImpl {
         println!("{number_of_questions}. Use {ctrl_c} to exit.");
     }
 
-    fn print_stats_in_between(&self) {
+    fn print_stats_in_between(&self, deadline: Option<Instant>) {
         let text = match self.number_of_questions() {
             NumberOfQuestions::Infinite => colour::format_text(
                 &format!(
@@ -229,6 +410,19 @@ impl AppImpl {
                 self.use_colour(),
                 Color::Grey,
             ),
+            NumberOfQuestions::TimeLimited(_) => colour::format_text(
+                &format!(
+                    "Time taken: {}, current accuracy: {} ({}), time left: {}",
+                    self.stats.get_last_question_time(),
+                    self.stats.get_current_accuracy(),
+                    self.stats.get_number_of_correct_answers(),
+                    stats::format_duration(&deadline.map_or_else(Default::default, |deadline| {
+                        deadline.saturating_duration_since(Instant::now())
+                    })),
+                ),
+                self.use_colour(),
+                Color::Grey,
+            ),
         };
         println!("{text}");
     }
@@ -236,6 +430,8 @@ impl AppImpl {
     fn print_stats_post_game(&self) {
         self.print_summary();
         self.print_time_stats();
+        self.print_category_breakdown();
+        self.print_ranges();
     }
 
     fn print_summary(&self) {
@@ -244,10 +440,23 @@ impl AppImpl {
             "Correct answers: {}",
             self.stats.get_number_of_correct_answers()
         );
+        if self.config.options.question_timeout.is_some() {
+            println!(
+                "Timed out: {}",
+                self.stats.get_number_of_timed_out_questions()
+            );
+        }
         match self.number_of_questions() {
             NumberOfQuestions::Infinite => {
                 println!("Accuracy: {}", self.stats.get_current_accuracy());
             }
+            NumberOfQuestions::TimeLimited(_) => {
+                println!("Accuracy: {}", self.stats.get_current_accuracy());
+                println!(
+                    "Questions per minute: {}",
+                    self.stats.get_questions_per_minute()
+                );
+            }
             NumberOfQuestions::Limited(_) => {
                 if self.stats.get_number_of_remaining_questions() == 0 {
                     println!("Accuracy: {}", self.stats.get_total_accuracy());
@@ -270,7 +479,96 @@ impl AppImpl {
         println!("  avg: {}", self.stats.get_avg_question_time());
     }
 
-    fn print_answer_feedback(&self, correct: bool, correct_answer: &str) {
+    fn print_category_breakdown(&self) {
+        let breakdown = self.stats.get_category_breakdown();
+        if !breakdown.is_empty() {
+            println!("{breakdown}");
+        }
+    }
+
+    fn print_ranges(&self) {
+        let ranges = self.stats.get_ranges();
+        if !self.config.options.adaptive || ranges.is_empty() {
+            return;
+        }
+        println!("Ranges used:");
+        for range in ranges {
+            println!("  {range}");
+        }
+    }
+
+    fn session(&self, completed: bool) -> Option<history::Session> {
+        Some(history::Session {
+            timestamp: Local::now().fixed_offset(),
+            command: self.config.command.clone()?,
+            general_options: self.config.options.args.clone(),
+            command_options: self.config.command_options.clone(),
+            completed,
+            records: self.stats.get_records(),
+        })
+    }
+
+    fn save_export(&self, completed: bool) {
+        let Some(path) = &self.config.options.export_file else {
+            return;
+        };
+        let totals = self.stats.get_totals();
+        let Some(session) = self.session(completed) else {
+            return;
+        };
+        if let Err(err) = export::write(path, self.config.options.export_format, &session, &totals)
+        {
+            eprintln!(
+                "{APP_NAME}: could not export session to '{}': {err}",
+                path.display()
+            );
+        }
+    }
+
+    fn save_history(&self, completed: bool) {
+        let Some(path) = &self.config.options.history_file else {
+            return;
+        };
+        let Some(session) = self.session(completed) else {
+            return;
+        };
+        if session.records.is_empty() {
+            return;
+        }
+
+        if let Err(err) = history::append(path, &session) {
+            eprintln!(
+                "{APP_NAME}: could not save session to '{}': {err}",
+                path.display()
+            );
+        }
+    }
+
+    fn review(&self, question: &Question, correct: bool) {
+        if let (Some(scheduler), Some(key)) = (&self.repetition, question.key()) {
+            scheduler.review(
+                key,
+                correct,
+                self.stats.get_last_question_duration(),
+                Local::now().date_naive(),
+            );
+        }
+    }
+
+    fn save_repetition(&self) {
+        if let (Some(scheduler), Some(path)) =
+            (&self.repetition, &self.config.options.repetition_file)
+        {
+            if let Err(err) = scheduler.save(path) {
+                eprintln!(
+                    "{APP_NAME}: could not save spaced repetition progress to '{}': {err}",
+                    path.display()
+                );
+            }
+        }
+    }
+
+    fn print_answer_feedback(&self, correct: bool, question: &Question) {
         let mut feedback = String::new();
         if correct {
             feedback.push_str(&colour::format_text(
@@ -286,13 +584,42 @@ impl AppImpl {
             ));
             match self.config.options.behaviour_on_error {
                 BehaviourOnError::ShowCorrect => {
-                    feedback.push_str(&format!(" Correct answer: {correct_answer}"));
+                    feedback.push_str(&format!(" Correct answer: {}", question.correct_answer()));
                 }
                 BehaviourOnError::Repeat => feedback.push_str(" Try again:"),
                 BehaviourOnError::NextQuestion => (),
             }
         }
         println!("{feedback}");
+        if !correct {
+            self.print_explanation(question);
+        }
+    }
+
+    fn print_timeout_feedback(&self, question: &Question) {
+        let mut feedback = colour::format_text("Out of time!", self.use_colour(), Color::Red);
+        match self.config.options.behaviour_on_error {
+            BehaviourOnError::ShowCorrect | BehaviourOnError::Repeat => {
+                feedback.push_str(&format!(" Correct answer: {}", question.correct_answer()));
+            }
+            BehaviourOnError::NextQuestion => (),
+        }
+        println!();
+        println!("{feedback}");
+        self.print_explanation(question);
+    }
+
+    /// Prints the worked solution of `question` after a wrong answer or a timeout, if
+    /// requested and provided by the skill. The solution shows the correct answer too.
+    fn print_explanation(&self, question: &Question) {
+        if !self.config.options.explain {
+            return;
+        }
+        if let Some(explanation) = question.explanation() {
+            for line in explanation.lines() {
+                println!("  {line}");
+            }
+        }
     }
 
     fn random_feedback_correct() -> String {
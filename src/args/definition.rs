@@ -4,6 +4,7 @@ use super::ArgValue;
 pub enum ValueKind {
     Int,
     UnsignedInt,
+    Str,
     OneOfStr(Vec<String>),
 }
 
@@ -147,7 +148,7 @@ impl ArgDefinitionBuilder {
                         "default value must be of type u32"
                     );
                 }
-                ValueKind::OneOfStr(_) => {
+                ValueKind::Str | ValueKind::OneOfStr(_) => {
                     assert!(
                         self.default_value.is_none()
                             || matches!(self.default_value, Some(ArgValue::Str(_))),
@@ -312,6 +313,17 @@ mod tests {
             .build();
     }
 
+    #[test]
+    #[should_panic(expected = "default value must be of type String")]
+    fn free_string_arg_definition_requires_string_default_value() {
+        Arg::builder()
+            .id("some_arg")
+            .short_name('s')
+            .kind(ArgKind::Value(ValueKind::Str))
+            .default_value(ArgValue::UnsignedInt(42))
+            .build();
+    }
+
     #[test]
     fn arg_not_matching_any_definition() {
         let arg = "my_arg";
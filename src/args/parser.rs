@@ -30,6 +30,12 @@ pub fn parse_and_validate_arg_list(
     Ok(parsed_args)
 }
 
+/// Returns whether the option `arg_id` was given, as opposed to falling back to its
+/// default value.
+pub fn is_arg_given(arg_id: &str, parsed_args: &[ArgValuePair]) -> bool {
+    parsed_args.iter().any(|elem| elem.id == arg_id)
+}
+
 fn parse_and_validate_arg(
     arg_list: &[String],
     arg_definition_list: &[Arg],
@@ -115,6 +121,7 @@ fn validate_and_create_arg(
             match &value_kind {
                 ValueKind::Int => ArgValue::Int(parse_i32(arg_value)?),
                 ValueKind::UnsignedInt => ArgValue::UnsignedInt(parse_u32(arg_value)?),
+                ValueKind::Str => ArgValue::Str(arg_value.to_string()),
                 ValueKind::OneOfStr(possible_values) => {
                     ArgValue::Str(validate_one_of_str(arg_value, possible_values)?.to_string())
                 }
@@ -154,6 +161,13 @@ fn validate_one_of_str<'a>(value: &'a str, possible_values: &[String]) -> Result
 mod tests {
     use super::*;
 
+    #[test]
+    fn given_args() {
+        let parsed_args = vec![ArgValuePair::new("given", ArgValue::UnsignedInt(0))];
+        assert!(is_arg_given("given", &parsed_args));
+        assert!(!is_arg_given("not_given", &parsed_args));
+    }
+
     #[test]
     fn i32_parsing() {
         let i32_max = i32::MAX.to_string();
@@ -320,6 +334,23 @@ mod tests {
         assert_eq!(arg_value_pair, expected);
     }
 
+    #[test]
+    fn validate_and_create_free_string_arg() {
+        let arg = "--some_arg";
+        let value = "any value/at all.txt";
+        let arg_value = Some(value);
+        let arg_definition = Arg::builder()
+            .id(arg)
+            .short_name('s')
+            .kind(ArgKind::Value(ValueKind::Str))
+            .default_value(ArgValue::Str(String::new()))
+            .build();
+
+        let expected = ArgValuePair::new(arg, ArgValue::Str(value.to_string()));
+        let arg_value_pair =
+            validate_and_create_arg(arg, arg_value, &arg_definition).expect("test failed");
+        assert_eq!(arg_value_pair, expected);
+    }
 
     #[test]
     #[should_panic(expected = "invalid option")]
@@ -1,20 +1,62 @@
 use std::cmp;
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::application::{self, Application};
 use crate::args::prelude::*;
+use crate::export::{self, Format};
+use crate::history::command::{self as history, History};
+use crate::skill::arithmetic;
+use crate::skill::bases;
+use crate::skill::calendar;
+use crate::skill::computus;
+use crate::skill::division;
 use crate::skill::doomsday_algorithm;
+use crate::skill::gcd;
 use crate::skill::powers;
+use crate::skill::primes;
+use crate::skill::roman;
+use crate::skill::squares;
 use crate::skill::times_table;
 use crate::skill::{self, Skill};
 
-const COMMANDS: [&str; 3] = [powers::CMD, times_table::CMD, doomsday_algorithm::CMD];
+const COMMANDS: [&str; 13] = [
+    powers::CMD,
+    times_table::CMD,
+    doomsday_algorithm::CMD,
+    arithmetic::CMD,
+    division::CMD,
+    squares::CMD,
+    calendar::CMD,
+    computus::CMD,
+    bases::CMD,
+    roman::CMD,
+    primes::CMD,
+    gcd::CMD,
+    history::CMD,
+];
 
 const ARG_ID_HELP: &str = "help";
 const ARG_ID_VERSION: &str = "version";
 const ARG_ID_NUMBER_OF_QUESTIONS: &str = "num_of_questions";
+const ARG_ID_TIME_LIMIT: &str = "time_limit";
+const ARG_ID_QUESTION_TIMEOUT: &str = "question_timeout";
+const ARG_ID_SEED: &str = "seed";
+const ARG_ID_WORKSHEET: &str = "worksheet";
 const ARG_ID_DISABLE_LIVE_STATISTICS: &str = "disable_live_stats";
 const ARG_ID_BEHAVIOUR_ON_ERROR: &str = "behaviour_on_err";
+const ARG_ID_EXPLAIN: &str = "explain";
 const ARG_ID_NO_COLOUR: &str = "no_colour";
+const ARG_ID_HISTORY_FILE: &str = "history_file";
+const ARG_ID_NO_HISTORY: &str = "no_history";
+const ARG_ID_SPACED_REPETITION: &str = "spaced_repetition";
+const ARG_ID_ADAPTIVE: &str = "adaptive";
+const ARG_ID_EXPORT: &str = "export";
+const ARG_ID_EXPORT_FORMAT: &str = "export_format";
+
+const HISTORY_FILE_NAME: &str = "history";
+const REPETITION_FILE_NAME: &str = "repetition";
 
 const BEHAVIOUR_ON_ERROR_CONTINUE: &str = "continue";
 const BEHAVIOUR_ON_ERROR_SHOW_CORRECT: &str = "showcorrect";
@@ -24,12 +66,17 @@ const BEHAVIOUR_ON_ERROR_REPEAT: &str = "repeat";
 pub enum NumberOfQuestions {
     Limited(u32),
     Infinite,
+    /// As many questions as can be answered within the time limit
+    TimeLimited(Duration),
 }
 
 #[derive(Debug)]
 pub struct Config {
     pub options: GeneralOptions,
     pub skill: Option<Box<dyn Skill>>,
+    pub history: Option<History>,
+    pub command: Option<String>,
+    pub command_options: Vec<String>,
 }
 
 impl Config {
@@ -65,6 +112,9 @@ impl Config {
                 return Ok(Self {
                     options: options.clone(),
                     skill: None,
+                    history: None,
+                    command: None,
+                    command_options: vec![],
                 });
             }
         }
@@ -73,11 +123,24 @@ impl Config {
         let Some(command) = command else {
             return Err(Self::build_err_message(Some("missing command".to_string())));
         };
+        if command == history::CMD {
+            return Ok(Self {
+                options,
+                skill: None,
+                history: Some(History::build(command_options)?),
+                command: Some(command),
+                command_options: command_options.to_vec(),
+            });
+        }
+
         let skill = skill::build(&command, command_options)?;
 
         Ok(Self {
             options,
             skill: Some(skill),
+            history: None,
+            command: Some(command),
+            command_options: command_options.to_vec(),
         })
     }
 
@@ -110,6 +173,23 @@ impl Config {
     }
 }
 
+/// Directory where the application keeps its data between runs.
+/// Returns `None` if no suitable location could be determined.
+pub fn data_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join(application::APP_NAME))
+    } else if let Some(dir) = env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        Some(PathBuf::from(dir).join(application::APP_NAME))
+    } else {
+        env::var_os("HOME").map(|dir| {
+            PathBuf::from(dir)
+                .join(".local")
+                .join("share")
+                .join(application::APP_NAME)
+        })
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum BehaviourOnError {
     NextQuestion,
@@ -132,15 +212,29 @@ impl BehaviourOnError {
 #[derive(Debug, Clone)]
 pub struct GeneralOptions {
     pub arg_definitions: Vec<Arg>,
+    pub args: Vec<String>,
 
     pub show_help: bool,
     pub show_version: bool,
 
     pub number_of_questions: NumberOfQuestions,
+    pub question_timeout: Option<Duration>,
+    pub seed: Option<u64>,
     pub disable_live_statistics: bool,
     pub behaviour_on_error: BehaviourOnError,
+    pub explain: bool,
 
     pub use_colour: bool,
+
+    pub history_file: Option<PathBuf>,
+    pub repetition_file: Option<PathBuf>,
+
+    pub adaptive: bool,
+
+    pub export_file: Option<PathBuf>,
+    pub export_format: Format,
+
+    pub worksheet_file: Option<PathBuf>,
 }
 
 impl GeneralOptions {
@@ -157,11 +251,42 @@ impl GeneralOptions {
             &parsed_args,
             &arg_definitions,
         );
-        let number_of_questions = if number_of_questions == 0 {
+        let time_limit = String::set_value_from_arg_or_default(
+            ARG_ID_TIME_LIMIT,
+            &parsed_args,
+            &arg_definitions,
+        );
+        if !time_limit.is_empty() && parser::is_arg_given(ARG_ID_NUMBER_OF_QUESTIONS, &parsed_args)
+        {
+            return Err(
+                "options '--number-of-questions' and '--time-limit' cannot be used together"
+                    .to_string(),
+            );
+        }
+        let number_of_questions = if !time_limit.is_empty() {
+            NumberOfQuestions::TimeLimited(Self::parse_time_limit(&time_limit)?)
+        } else if number_of_questions == 0 {
             NumberOfQuestions::Infinite
         } else {
             NumberOfQuestions::Limited(number_of_questions)
         };
+        let question_timeout = u32::set_value_from_arg_or_default(
+            ARG_ID_QUESTION_TIMEOUT,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let question_timeout =
+            (question_timeout > 0).then(|| Duration::from_secs(u64::from(question_timeout)));
+        let seed =
+            String::set_value_from_arg_or_default(ARG_ID_SEED, &parsed_args, &arg_definitions);
+        let seed = if seed.is_empty() {
+            None
+        } else {
+            Some(
+                seed.parse::<u64>()
+                    .map_err(|_| format!("invalid seed: '{seed}'"))?,
+            )
+        };
         let disable_live_statistics = bool::set_value_from_arg_or_default(
             ARG_ID_DISABLE_LIVE_STATISTICS,
             &parsed_args,
@@ -173,21 +298,145 @@ impl GeneralOptions {
             &arg_definitions,
         );
         let behaviour_on_error = BehaviourOnError::from_string(&behaviour_on_error);
+        let explain =
+            bool::set_value_from_arg_or_default(ARG_ID_EXPLAIN, &parsed_args, &arg_definitions);
 
         let use_colour =
             !bool::set_value_from_arg_or_default(ARG_ID_NO_COLOUR, &parsed_args, &arg_definitions);
 
+        let history_file = String::set_value_from_arg_or_default(
+            ARG_ID_HISTORY_FILE,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let no_history =
+            bool::set_value_from_arg_or_default(ARG_ID_NO_HISTORY, &parsed_args, &arg_definitions);
+        let history_file = Self::history_file(&history_file, no_history)?;
+
+        let spaced_repetition = bool::set_value_from_arg_or_default(
+            ARG_ID_SPACED_REPETITION,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let repetition_file = if spaced_repetition {
+            let dir =
+                data_dir().ok_or("could not determine data directory for spaced repetition")?;
+            Some(dir.join(REPETITION_FILE_NAME))
+        } else {
+            None
+        };
+
+        let adaptive =
+            bool::set_value_from_arg_or_default(ARG_ID_ADAPTIVE, &parsed_args, &arg_definitions);
+
+        let export_file =
+            String::set_value_from_arg_or_default(ARG_ID_EXPORT, &parsed_args, &arg_definitions);
+        let export_file = if export_file.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(export_file))
+        };
+        let export_format = String::set_value_from_arg_or_default(
+            ARG_ID_EXPORT_FORMAT,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let export_format = Format::from_string(&export_format);
+
+        let worksheet_file =
+            String::set_value_from_arg_or_default(ARG_ID_WORKSHEET, &parsed_args, &arg_definitions);
+        // No game is played with a worksheet, so options of the game would be ignored
+        if let Some((_, name)) = [
+            (ARG_ID_TIME_LIMIT, "--time-limit"),
+            (ARG_ID_QUESTION_TIMEOUT, "--question-timeout"),
+            (ARG_ID_SPACED_REPETITION, "--spaced-repetition"),
+            (ARG_ID_ADAPTIVE, "--adaptive"),
+            (ARG_ID_EXPORT, "--export"),
+            (ARG_ID_EXPORT_FORMAT, "--format"),
+            (ARG_ID_BEHAVIOUR_ON_ERROR, "--behavior-on-error"),
+            (ARG_ID_DISABLE_LIVE_STATISTICS, "--disable-live-statistics"),
+            (ARG_ID_EXPLAIN, "--explain"),
+        ]
+        .into_iter()
+        .find(|(id, _)| !worksheet_file.is_empty() && parser::is_arg_given(id, &parsed_args))
+        {
+            return Err(format!(
+                "options '--worksheet' and '{name}' cannot be used together"
+            ));
+        }
+        let worksheet_file = if worksheet_file.is_empty() {
+            None
+        } else if let NumberOfQuestions::Limited(_) = number_of_questions {
+            Some(PathBuf::from(worksheet_file))
+        } else {
+            return Err("worksheet needs a limited number of questions".to_string());
+        };
+
         Ok(Self {
             arg_definitions,
+            args: args.to_vec(),
             show_help,
             show_version,
             number_of_questions,
+            question_timeout,
+            seed,
             disable_live_statistics,
             behaviour_on_error,
-            use_colour
+            explain,
+            use_colour,
+            history_file,
+            repetition_file,
+            adaptive,
+            export_file,
+            export_format,
+            worksheet_file,
         })
     }
 
+    /// Parses time limit such as "90", "90s", "2m" or "1m30s". Plain numbers are seconds.
+    fn parse_time_limit(value: &str) -> Result<Duration, String> {
+        let invalid = || format!("invalid time limit: '{value}'");
+        let mut seconds: u64 = 0;
+        let mut number = String::new();
+        for c in value.chars() {
+            let multiplier = match c {
+                '0'..='9' => {
+                    number.push(c);
+                    continue;
+                }
+                'h' => 60 * 60,
+                'm' => 60,
+                's' => 1,
+                _ => return Err(invalid()),
+            };
+            let amount: u64 = number.parse().map_err(|_| invalid())?;
+            seconds = amount
+                .checked_mul(multiplier)
+                .and_then(|amount| seconds.checked_add(amount))
+                .ok_or_else(invalid)?;
+            number.clear();
+        }
+        if !number.is_empty() {
+            let amount: u64 = number.parse().map_err(|_| invalid())?;
+            seconds = seconds.checked_add(amount).ok_or_else(invalid)?;
+        }
+        if seconds == 0 {
+            return Err("time limit must be greater than 0".to_string());
+        }
+        Ok(Duration::from_secs(seconds))
+    }
+
+    fn history_file(path: &str, no_history: bool) -> Result<Option<PathBuf>, String> {
+        match (path.is_empty(), no_history) {
+            (false, true) => Err(
+                "options '--history-file' and '--no-history' cannot be used together".to_string(),
+            ),
+            (false, false) => Ok(Some(PathBuf::from(path))),
+            (true, true) => Ok(None),
+            (true, false) => Ok(data_dir().map(|dir| dir.join(HISTORY_FILE_NAME))),
+        }
+    }
+
     fn build_arg_definitions() -> Vec<Arg> {
         vec![
             Arg::builder()
@@ -220,6 +469,44 @@ impl GeneralOptions {
                 .stop_parsing(false)
                 .default_value(ArgValue::UnsignedInt(20))
                 .build(),
+            Arg::builder()
+                .id(ARG_ID_TIME_LIMIT)
+                .short_name('t')
+                .long_name("time-limit")
+                .description(vec![
+                    "Answer as many questions as possible within".to_string(),
+                    "the time limit, e.g. 60s, 2m or 1m30s".to_string(),
+                    "(instead of a number of questions).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::Str))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(String::new()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_QUESTION_TIMEOUT)
+                .long_name("question-timeout")
+                .description(vec![
+                    "Mark a question as wrong if it's not answered".to_string(),
+                    "within the given number of seconds".to_string(),
+                    "(0: no timeout, default).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(0))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_SEED)
+                .short_name('s')
+                .long_name("seed")
+                .description(vec![
+                    "Generate questions from the given seed".to_string(),
+                    "(a number), so that the same questions".to_string(),
+                    "are asked every time.".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::Str))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(String::new()))
+                .build(),
             Arg::builder()
                 .id(ARG_ID_DISABLE_LIVE_STATISTICS)
                 .short_name('d')
@@ -253,6 +540,18 @@ impl GeneralOptions {
                 .stop_parsing(false)
                 .default_value(ArgValue::Str("showcorrect".to_string()))
                 .build(),
+            Arg::builder()
+                .id(ARG_ID_EXPLAIN)
+                .long_name("explain")
+                .description(vec![
+                    "Show how the correct answer is worked out".to_string(),
+                    "after a wrong answer or a timeout (supported".to_string(),
+                    "by some skills, e.g. doomsday).".to_string(),
+                ])
+                .kind(ArgKind::Flag)
+                .stop_parsing(false)
+                .default_value(ArgValue::Bool(false))
+                .build(),
             Arg::builder()
                 .id(ARG_ID_NO_COLOUR)
                 .long_name("no-color")
@@ -261,6 +560,90 @@ impl GeneralOptions {
                 .stop_parsing(false)
                 .default_value(ArgValue::Bool(false))
                 .build(),
+            Arg::builder()
+                .id(ARG_ID_HISTORY_FILE)
+                .long_name("history-file")
+                .description(vec![
+                    "Set the file finished sessions are appended to".to_string(),
+                    "(default: 'history' in the data directory).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::Str))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(String::new()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_NO_HISTORY)
+                .long_name("no-history")
+                .description(vec!["Do not save the session to history.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(false)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_SPACED_REPETITION)
+                .short_name('r')
+                .long_name("spaced-repetition")
+                .description(vec![
+                    "Ask questions due for repetition and the ones".to_string(),
+                    "you struggle with first. Progress of each".to_string(),
+                    "question is kept between runs.".to_string(),
+                ])
+                .kind(ArgKind::Flag)
+                .stop_parsing(false)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_ADAPTIVE)
+                .short_name('a')
+                .long_name("adaptive")
+                .description(vec![
+                    "Start with a narrow range of questions.".to_string(),
+                    "Widen it after fast, correct answers and".to_string(),
+                    "narrow it after misses or slow answers.".to_string(),
+                ])
+                .kind(ArgKind::Flag)
+                .stop_parsing(false)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_EXPORT)
+                .long_name("export")
+                .description(vec![
+                    "Write the session result (questions, answers".to_string(),
+                    "and summary) to the given file when the game".to_string(),
+                    "ends.".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::Str))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(String::new()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_EXPORT_FORMAT)
+                .long_name("format")
+                .description(vec![
+                    "Set the format of the exported file".to_string(),
+                    "(json or csv, default: json).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    export::FORMAT_JSON.to_string(),
+                    export::FORMAT_CSV.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(export::FORMAT_JSON.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_WORKSHEET)
+                .long_name("worksheet")
+                .description(vec![
+                    "Write the questions to a printable worksheet".to_string(),
+                    "(Markdown, HTML or plain text, depending on".to_string(),
+                    "the extension) and the answers to an answer".to_string(),
+                    "key next to it, instead of playing.".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::Str))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(String::new()))
+                .build(),
         ]
     }
 }
@@ -333,8 +716,15 @@ mod tests {
             config.options.behaviour_on_error,
             BehaviourOnError::ShowCorrect
         );
+        assert!(!config.options.explain);
         assert!(config.skill.is_some());
         assert!(config.options.use_colour);
+        assert_eq!(config.options.repetition_file, None);
+        assert!(!config.options.adaptive);
+        assert_eq!(config.options.export_file, None);
+        assert_eq!(config.options.export_format, Format::Json);
+        assert_eq!(config.command, Some("powers".to_string()));
+        assert!(config.command_options.is_empty());
     }
 
     #[test]
@@ -344,7 +734,13 @@ mod tests {
             "--number-of-questions=10".to_string(),
             "--disable-live-statistics".to_string(),
             "--behavior-on-error=repeat".to_string(),
+            "--explain".to_string(),
             "--no-color".to_string(),
+            "--history-file=history.txt".to_string(),
+            "--spaced-repetition".to_string(),
+            "--adaptive".to_string(),
+            "--export=session.csv".to_string(),
+            "--format=csv".to_string(),
             "powers".to_string(),
         ];
         let config = Config::build(&args).expect("should build successfully");
@@ -356,8 +752,22 @@ mod tests {
         );
         assert!(config.options.disable_live_statistics);
         assert_eq!(config.options.behaviour_on_error, BehaviourOnError::Repeat);
+        assert!(config.options.explain);
         assert!(config.skill.is_some());
         assert!(!config.options.use_colour);
+        assert_eq!(
+            config.options.history_file,
+            Some(PathBuf::from("history.txt"))
+        );
+        if data_dir().is_some() {
+            assert!(config.options.repetition_file.is_some());
+        }
+        assert!(config.options.adaptive);
+        assert_eq!(
+            config.options.export_file,
+            Some(PathBuf::from("session.csv"))
+        );
+        assert_eq!(config.options.export_format, Format::Csv);
 
         // Different set of args
         let args = [
@@ -368,7 +778,10 @@ mod tests {
             "-d".to_string(),
             "-b".to_string(),
             "continue".to_string(),
+            "--no-history".to_string(),
             "powers".to_string(),
+            "-b".to_string(),
+            "3".to_string(),
         ];
         let config = Config::build(&args).expect("should build successfully");
         assert!(!config.options.show_help);
@@ -384,6 +797,38 @@ mod tests {
         );
         assert!(config.skill.is_some());
         assert!(!config.options.use_colour);
+        assert_eq!(config.options.history_file, None);
+        assert_eq!(config.command, Some("powers".to_string()));
+        assert_eq!(
+            config.command_options,
+            vec!["-b".to_string(), "3".to_string()]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be used together")]
+    fn build_conflicting_history_options() {
+        let args = [
+            "command".to_string(),
+            "--history-file=history.txt".to_string(),
+            "--no-history".to_string(),
+            "powers".to_string(),
+        ];
+        Config::build(&args).expect("should fail due to conflicting options");
+    }
+
+    #[test]
+    fn build_history_command() {
+        let args = [
+            "command".to_string(),
+            "--no-history".to_string(),
+            "history".to_string(),
+            "--skill=doomsday".to_string(),
+        ];
+        let config = Config::build(&args).expect("should build successfully");
+        assert!(config.skill.is_none());
+        assert!(config.history.is_some());
+        assert_eq!(config.command, Some("history".to_string()));
     }
 
     #[test]
@@ -480,4 +925,153 @@ mod tests {
             BehaviourOnError::Repeat
         );
     }
+
+    #[test]
+    fn parse_time_limit() {
+        assert_eq!(
+            GeneralOptions::parse_time_limit("90"),
+            Ok(Duration::from_secs(90))
+        );
+        assert_eq!(
+            GeneralOptions::parse_time_limit("60s"),
+            Ok(Duration::from_secs(60))
+        );
+        assert_eq!(
+            GeneralOptions::parse_time_limit("2m"),
+            Ok(Duration::from_secs(120))
+        );
+        assert_eq!(
+            GeneralOptions::parse_time_limit("1h1m30s"),
+            Ok(Duration::from_secs(3690))
+        );
+        assert_eq!(
+            GeneralOptions::parse_time_limit("1m30"),
+            Ok(Duration::from_secs(90))
+        );
+        assert!(GeneralOptions::parse_time_limit("0s")
+            .is_err_and(|err| err.contains("must be greater than 0")));
+        for value in ["m", "1.5m", "60 s", "-1", "1d"] {
+            assert!(GeneralOptions::parse_time_limit(value)
+                .is_err_and(|err| err.contains("invalid time limit")));
+        }
+    }
+
+    #[test]
+    fn build_time_limit() {
+        let args = [
+            "command".to_string(),
+            "--time-limit=1m".to_string(),
+            "powers".to_string(),
+        ];
+        let config = Config::build(&args).expect("should build successfully");
+        assert_eq!(
+            config.options.number_of_questions,
+            NumberOfQuestions::TimeLimited(Duration::from_secs(60))
+        );
+
+        let args = [
+            "command".to_string(),
+            "-n".to_string(),
+            "5".to_string(),
+            "--time-limit=1m".to_string(),
+            "powers".to_string(),
+        ];
+        assert!(Config::build(&args).is_err_and(|err| err.contains("cannot be used together")));
+
+        let args = [
+            "command".to_string(),
+            "-t".to_string(),
+            "soon".to_string(),
+            "powers".to_string(),
+        ];
+        assert!(Config::build(&args).is_err_and(|err| err.contains("invalid time limit")));
+    }
+
+    #[test]
+    fn build_question_timeout() {
+        let args = ["command".to_string(), "powers".to_string()];
+        let config = Config::build(&args).expect("should build successfully");
+        assert_eq!(config.options.question_timeout, None);
+
+        let args = [
+            "command".to_string(),
+            "--question-timeout=5".to_string(),
+            "powers".to_string(),
+        ];
+        let config = Config::build(&args).expect("should build successfully");
+        assert_eq!(
+            config.options.question_timeout,
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn build_seed() {
+        let args = ["command".to_string(), "powers".to_string()];
+        let config = Config::build(&args).expect("should build successfully");
+        assert_eq!(config.options.seed, None);
+
+        let args = [
+            "command".to_string(),
+            "--seed=18446744073709551615".to_string(),
+            "powers".to_string(),
+        ];
+        let config = Config::build(&args).expect("should build successfully");
+        assert_eq!(config.options.seed, Some(u64::MAX));
+
+        let args = [
+            "command".to_string(),
+            "-s".to_string(),
+            "-1".to_string(),
+            "powers".to_string(),
+        ];
+        assert!(Config::build(&args).is_err_and(|err| err.contains("invalid seed: '-1'")));
+    }
+
+    #[test]
+    fn build_worksheet() {
+        let args = [
+            "command".to_string(),
+            "--worksheet=sheet.md".to_string(),
+            "powers".to_string(),
+        ];
+        let config = Config::build(&args).expect("should build successfully");
+        assert_eq!(
+            config.options.worksheet_file,
+            Some(PathBuf::from("sheet.md"))
+        );
+
+        let args = [
+            "command".to_string(),
+            "-n".to_string(),
+            "0".to_string(),
+            "--worksheet=sheet.md".to_string(),
+            "powers".to_string(),
+        ];
+        assert!(Config::build(&args).is_err_and(|err| err.contains("limited number")));
+
+        for option in [
+            "--time-limit=1m",
+            "--question-timeout=5",
+            "--spaced-repetition",
+            "--adaptive",
+            "--export=session.json",
+            "--format=csv",
+            "--behavior-on-error=repeat",
+            "--disable-live-statistics",
+            "--explain",
+        ] {
+            let args = [
+                "command".to_string(),
+                option.to_string(),
+                "--worksheet=sheet.md".to_string(),
+                "powers".to_string(),
+            ];
+            let name = option.split('=').next().unwrap_or(option);
+            let err = Config::build(&args).expect_err("should fail due to conflicting options");
+            assert!(err.contains(&format!(
+                "'--worksheet' and '{name}' cannot be used together"
+            )));
+        }
+    }
 }
@@ -0,0 +1,374 @@
+//! Machine-readable export of a finished session, in JSON or CSV.
+//!
+//! JSON contains session details, summary and a list of questions. CSV contains a table of
+//! questions only, summary metrics are written to a second CSV file next to it (see
+//! `summary_path`), so that both can be read as plain tables.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::SecondsFormat;
+
+use crate::config::NumberOfQuestions;
+use crate::history::Session;
+use crate::stats::{self, Record, Totals};
+
+pub const FORMAT_JSON: &str = "json";
+pub const FORMAT_CSV: &str = "csv";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn from_string(value: &str) -> Format {
+        match value {
+            FORMAT_JSON => Format::Json,
+            FORMAT_CSV => Format::Csv,
+            _ => panic!("incorrect value for Format"),
+        }
+    }
+}
+
+/// Writes `session` and its `totals` to `path`, creating the directory if needed.
+/// In CSV format, the summary is written to `summary_path(path)`.
+pub fn write(path: &Path, format: Format, session: &Session, totals: &Totals) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+    match format {
+        Format::Json => fs::write(path, to_json(session, totals)),
+        Format::Csv => {
+            fs::write(path, to_csv(session))?;
+            fs::write(summary_path(path), to_csv_summary(session, totals))
+        }
+    }
+}
+
+/// Path of the CSV summary file, e.g. "session-summary.csv" for "session.csv"
+pub fn summary_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut file_name = format!("{stem}-summary");
+    if let Some(extension) = path.extension() {
+        file_name.push('.');
+        file_name.push_str(&extension.to_string_lossy());
+    }
+    path.with_file_name(file_name)
+}
+
+/// Summary metrics as (name, value) pairs. Values are already valid JSON literals.
+fn summary(records: &[Record], totals: &Totals) -> Vec<(&'static str, String)> {
+    let (questions_total, total_accuracy) = match totals.number_of_questions {
+        NumberOfQuestions::Limited(num) => (
+            num.to_string(),
+            format_accuracy(totals.total_accuracy().unwrap_or_default()),
+        ),
+        NumberOfQuestions::Infinite | NumberOfQuestions::TimeLimited(_) => {
+            ("null".to_string(), "null".to_string())
+        }
+    };
+
+    vec![
+        ("questions_total", questions_total),
+        ("answered", totals.answered.to_string()),
+        ("skipped", totals.skipped.to_string()),
+        ("correct", totals.correct.to_string()),
+        (
+            "timed_out",
+            records
+                .iter()
+                .filter(|record| record.timed_out)
+                .count()
+                .to_string(),
+        ),
+        ("accuracy", format_accuracy(totals.accuracy())),
+        ("total_accuracy", total_accuracy),
+        ("total_time_ms", totals.total_time.as_millis().to_string()),
+        (
+            "min_time_ms",
+            stats::min_question_time(records).as_millis().to_string(),
+        ),
+        (
+            "max_time_ms",
+            stats::max_question_time(records).as_millis().to_string(),
+        ),
+        (
+            "avg_time_ms",
+            stats::avg_question_time(records).as_millis().to_string(),
+        ),
+    ]
+}
+
+/// Accuracy in percent, rounded to 2 decimal places
+fn format_accuracy(accuracy: f64) -> String {
+    format!("{accuracy:.2}")
+}
+
+fn to_json(session: &Session, totals: &Totals) -> String {
+    let mut text = String::from("{\n");
+    text.push_str(&format!(
+        "  \"timestamp\": {},\n",
+        json_string(
+            &session
+                .timestamp
+                .to_rfc3339_opts(SecondsFormat::Secs, false)
+        )
+    ));
+    text.push_str(&format!(
+        "  \"command\": {},\n",
+        json_string(&session.command)
+    ));
+    text.push_str(&format!(
+        "  \"general_options\": {},\n",
+        json_list(&session.general_options)
+    ));
+    text.push_str(&format!(
+        "  \"command_options\": {},\n",
+        json_list(&session.command_options)
+    ));
+    text.push_str(&format!("  \"completed\": {},\n", session.completed));
+
+    let summary: Vec<_> = summary(&session.records, totals)
+        .into_iter()
+        .map(|(name, value)| format!("    \"{name}\": {value}"))
+        .collect();
+    text.push_str(&format!(
+        "  \"summary\": {{\n{}\n  }},\n",
+        summary.join(",\n")
+    ));
+
+    let questions: Vec<_> = session
+        .records
+        .iter()
+        .map(|record| {
+            format!(
+                "    {{\"prompt\": {}, \"expected_answer\": {}, \"given_answers\": {}, \"correct\": {}, \"timed_out\": {}, \"attempts\": {}, \"duration_ms\": {}}}",
+                json_string(&record.prompt),
+                json_string(&record.correct_answer),
+                json_list(&record.given_answers),
+                record.correct,
+                record.timed_out,
+                record.given_answers.len(),
+                record.duration.as_millis()
+            )
+        })
+        .collect();
+    if questions.is_empty() {
+        text.push_str("  \"questions\": []\n");
+    } else {
+        text.push_str(&format!(
+            "  \"questions\": [\n{}\n  ]\n",
+            questions.join(",\n")
+        ));
+    }
+
+    text.push_str("}\n");
+    text
+}
+
+fn to_csv(session: &Session) -> String {
+    let mut text = String::from(
+        "prompt,expected_answer,given_answers,correct,timed_out,attempts,duration_ms\n",
+    );
+    for record in &session.records {
+        text.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            csv_field(&record.prompt),
+            csv_field(&record.correct_answer),
+            csv_field(&csv_list(&record.given_answers)),
+            record.correct,
+            record.timed_out,
+            record.given_answers.len(),
+            record.duration.as_millis()
+        ));
+    }
+    text
+}
+
+fn to_csv_summary(session: &Session, totals: &Totals) -> String {
+    let mut text = String::from("metric,value\n");
+    for (name, value) in summary(&session.records, totals) {
+        let value = if value == "null" {
+            String::new()
+        } else {
+            value
+        };
+        text.push_str(&format!("{name},{value}\n"));
+    }
+    text
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_list(items: &[String]) -> String {
+    let items: Vec<_> = items.iter().map(|item| json_string(item)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// Joins `items` with semicolons. Backslashes and semicolons inside items are escaped
+/// with a backslash, so that the list can be split back.
+fn csv_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|item| item.replace('\\', "\\\\").replace(';', "\\;"))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::DateTime;
+
+    use super::*;
+
+    fn session() -> Session {
+        Session {
+            timestamp: DateTime::parse_from_rfc3339("2026-01-02T10:20:30+01:00")
+                .expect("Test: invalid timestamp"),
+            command: "powers".to_string(),
+            general_options: vec!["-n".to_string(), "3".to_string()],
+            command_options: vec![],
+            completed: false,
+            records: vec![
+                Record {
+                    prompt: "2^3".to_string(),
+                    correct_answer: "8".to_string(),
+                    given_answers: vec!["8".to_string()],
+                    correct: true,
+                    duration: Duration::from_millis(1500),
+                    timed_out: false,
+                    categories: vec![],
+                },
+                Record {
+                    prompt: "2^4".to_string(),
+                    correct_answer: "16".to_string(),
+                    given_answers: vec!["1,6".to_string(), "16".to_string()],
+                    correct: false,
+                    duration: Duration::from_millis(4500),
+                    timed_out: false,
+                    categories: vec![],
+                },
+            ],
+        }
+    }
+
+    fn totals() -> Totals {
+        Totals {
+            number_of_questions: NumberOfQuestions::Limited(3),
+            answered: 2,
+            skipped: 1,
+            correct: 1,
+            total_time: Duration::from_millis(7000),
+        }
+    }
+
+    #[test]
+    fn json_export() {
+        let json = to_json(&session(), &totals());
+        assert!(json.starts_with("{\n  \"timestamp\": \"2026-01-02T10:20:30+01:00\",\n"));
+        assert!(json.contains("  \"command\": \"powers\",\n"));
+        assert!(json.contains("  \"general_options\": [\"-n\", \"3\"],\n"));
+        assert!(json.contains("  \"command_options\": [],\n"));
+        assert!(json.contains("  \"completed\": false,\n"));
+        assert!(json.contains("    \"questions_total\": 3,\n"));
+        assert!(json.contains("    \"accuracy\": 50.00,\n"));
+        assert!(json.contains("    \"total_accuracy\": 33.33,\n"));
+        assert!(json.contains("    \"min_time_ms\": 1500,\n"));
+        assert!(json.contains("    \"avg_time_ms\": 3000\n  },\n"));
+        assert!(json.contains(
+            "    {\"prompt\": \"2^4\", \"expected_answer\": \"16\", \"given_answers\": [\"1,6\", \"16\"], \"correct\": false, \"timed_out\": false, \"attempts\": 2, \"duration_ms\": 4500}\n  ]\n}\n"
+        ));
+
+        let mut session = session();
+        session.records.clear();
+        let totals = Totals {
+            number_of_questions: NumberOfQuestions::Infinite,
+            answered: 0,
+            skipped: 0,
+            correct: 0,
+            total_time: Duration::ZERO,
+        };
+        let json = to_json(&session, &totals);
+        assert!(json.contains("    \"questions_total\": null,\n"));
+        assert!(json.contains("    \"accuracy\": 0.00,\n"));
+        assert!(json.contains("  \"questions\": []\n}"));
+    }
+
+    #[test]
+    fn csv_export() {
+        let csv = to_csv(&session());
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "prompt,expected_answer,given_answers,correct,timed_out,attempts,duration_ms",
+                "2^3,8,8,true,false,1,1500",
+                "2^4,16,\"1,6;16\",false,false,2,4500",
+            ]
+        );
+
+        let summary = to_csv_summary(&session(), &totals());
+        let lines: Vec<_> = summary.lines().collect();
+        assert_eq!(lines[0], "metric,value");
+        assert!(lines.contains(&"questions_total,3"));
+        assert!(lines.contains(&"total_accuracy,33.33"));
+        assert!(lines.contains(&"max_time_ms,4500"));
+        assert!(lines.iter().all(|line| line.split(',').count() == 2));
+    }
+
+    #[test]
+    fn csv_summary_path() {
+        assert_eq!(
+            summary_path(Path::new("dir/session.csv")),
+            Path::new("dir/session-summary.csv")
+        );
+        assert_eq!(
+            summary_path(Path::new("session")),
+            Path::new("session-summary")
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(
+            csv_list(&["a;b".to_string(), "c\\".to_string(), String::new()]),
+            "a\\;b;c\\\\;"
+        );
+    }
+}
@@ -0,0 +1,413 @@
+//! Session history. Every session is appended to a plain text file as:
+//! - a `session` line: timestamp, command, general options, command options and whether
+//!   the session was completed or interrupted,
+//! - a `question` line per answered question: prompt, correct answer, given answers,
+//!   correctness of the first answer, time taken in milliseconds and whether time ran out
+//!   (missing in files written before question timeouts, read as not timed out).
+//!
+//! Fields are separated with tabs and list elements with commas. Backslashes, tabs,
+//! new lines and commas inside fields are escaped with a backslash. A question without
+//! any given answers (it timed out) has a single backslash in place of the list.
+
+pub mod command;
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+
+use crate::stats::Record;
+
+const SESSION_TAG: &str = "session";
+const QUESTION_TAG: &str = "question";
+const COMPLETED: &str = "completed";
+const INTERRUPTED: &str = "interrupted";
+const FIELD_SEPARATOR: char = '\t';
+const LIST_SEPARATOR: char = ',';
+/// Never produced by `escape`, so it can't be confused with a single empty answer
+const NO_ANSWERS: &str = "\\";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub timestamp: DateTime<FixedOffset>,
+    pub command: String,
+    pub general_options: Vec<String>,
+    pub command_options: Vec<String>,
+    pub completed: bool,
+    pub records: Vec<Record>,
+}
+
+impl Session {
+    fn serialise(&self) -> String {
+        let status = if self.completed {
+            COMPLETED
+        } else {
+            INTERRUPTED
+        };
+        let mut text = join_fields(&[
+            SESSION_TAG.to_string(),
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Secs, false),
+            escape(&self.command),
+            escape_list(&self.general_options),
+            escape_list(&self.command_options),
+            status.to_string(),
+        ]);
+        text.push('\n');
+
+        for record in &self.records {
+            text.push_str(&join_fields(&[
+                QUESTION_TAG.to_string(),
+                escape(&record.prompt),
+                escape(&record.correct_answer),
+                escape_answers(&record.given_answers),
+                u8::from(record.correct).to_string(),
+                record.duration.as_millis().to_string(),
+                u8::from(record.timed_out).to_string(),
+            ]));
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+/// Loads all sessions from the history file. Missing file is treated as empty history.
+pub fn load(path: &Path) -> Result<Vec<Session>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(format!("could not read '{}': {err}", path.display())),
+    };
+    parse(&text).map_err(|line| format!("'{}' is corrupted (line {line})", path.display()))
+}
+
+/// Parses history. On error, returns the number of the offending line.
+fn parse(text: &str) -> Result<Vec<Session>, usize> {
+    let mut sessions: Vec<Session> = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line_number = i + 1;
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<_> = line.split(FIELD_SEPARATOR).collect();
+        match fields.as_slice() {
+            [SESSION_TAG, timestamp, command, general_options, command_options, status] => {
+                let timestamp = DateTime::parse_from_rfc3339(timestamp).map_err(|_| line_number)?;
+                let completed = match *status {
+                    COMPLETED => true,
+                    INTERRUPTED => false,
+                    _ => return Err(line_number),
+                };
+                sessions.push(Session {
+                    timestamp,
+                    command: unescape(command),
+                    general_options: split_options(general_options),
+                    command_options: split_options(command_options),
+                    completed,
+                    records: vec![],
+                });
+            }
+            [QUESTION_TAG, prompt, correct_answer, given_answers, correct, duration, rest @ ..]
+                if rest.len() <= 1 =>
+            {
+                let session = sessions.last_mut().ok_or(line_number)?;
+                let correct = parse_flag(correct).ok_or(line_number)?;
+                let duration = duration.parse::<u64>().map_err(|_| line_number)?;
+                let timed_out = match rest {
+                    [timed_out] => parse_flag(timed_out).ok_or(line_number)?,
+                    _ => false,
+                };
+                session.records.push(Record {
+                    prompt: unescape(prompt),
+                    correct_answer: unescape(correct_answer),
+                    given_answers: split_answers(given_answers),
+                    correct,
+                    duration: Duration::from_millis(duration),
+                    timed_out,
+                    categories: vec![],
+                });
+            }
+            _ => return Err(line_number),
+        }
+    }
+    Ok(sessions)
+}
+
+/// Appends `session` to the history file, creating the file (and its directory) if needed.
+pub fn append(path: &Path, session: &Session) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(session.serialise().as_bytes())
+}
+
+fn parse_flag(field: &str) -> Option<bool> {
+    match field {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn join_fields(fields: &[String]) -> String {
+    fields.join(&FIELD_SEPARATOR.to_string())
+}
+
+fn escape_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|item| escape(item))
+        .collect::<Vec<_>>()
+        .join(&LIST_SEPARATOR.to_string())
+}
+
+fn escape_answers(answers: &[String]) -> String {
+    if answers.is_empty() {
+        NO_ANSWERS.to_string()
+    } else {
+        escape_list(answers)
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            LIST_SEPARATOR => escaped.push_str("\\,"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Option lists may be empty, unlike given answers (an empty answer is still an answer).
+fn split_options(field: &str) -> Vec<String> {
+    if field.is_empty() {
+        vec![]
+    } else {
+        split_list(field)
+    }
+}
+
+fn split_answers(field: &str) -> Vec<String> {
+    if field == NO_ANSWERS {
+        vec![]
+    } else {
+        split_list(field)
+    }
+}
+
+fn split_list(field: &str) -> Vec<String> {
+    let mut items = vec![];
+    let mut current = String::new();
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push(c);
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            LIST_SEPARATOR => items.push(unescape(&std::mem::take(&mut current))),
+            _ => current.push(c),
+        }
+    }
+    items.push(unescape(&current));
+    items
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => unescaped.push('\t'),
+                Some('n') => unescaped.push('\n'),
+                Some('r') => unescaped.push('\r'),
+                Some(escaped) => unescaped.push(escaped),
+                None => unescaped.push(c),
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn session() -> Session {
+        Session {
+            timestamp: DateTime::parse_from_rfc3339("2026-01-02T10:20:30+01:00")
+                .expect("Test: invalid timestamp"),
+            command: "powers".to_string(),
+            general_options: vec!["-n".to_string(), "2".to_string()],
+            command_options: vec!["--base=3".to_string()],
+            completed: true,
+            records: vec![
+                Record {
+                    prompt: "3^2".to_string(),
+                    correct_answer: "9".to_string(),
+                    given_answers: vec!["9".to_string()],
+                    correct: true,
+                    duration: Duration::from_millis(1500),
+                    timed_out: false,
+                    categories: vec![],
+                },
+                Record {
+                    prompt: "3^3".to_string(),
+                    correct_answer: "27".to_string(),
+                    given_answers: vec!["2,7".to_string(), "27".to_string()],
+                    correct: false,
+                    duration: Duration::from_millis(4321),
+                    timed_out: false,
+                    categories: vec![],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(escape("plain text"), "plain text");
+        assert_eq!(escape("a\tb\nc\rd"), "a\\tb\\nc\\rd");
+        assert_eq!(escape("1,2"), "1\\,2");
+        assert_eq!(escape("back\\slash"), "back\\\\slash");
+        assert_eq!(
+            escape_list(&["a,b".to_string(), "c".to_string()]),
+            "a\\,b,c"
+        );
+        assert_eq!(escape_list(&[]), "");
+    }
+
+    #[test]
+    fn session_serialisation() {
+        let text = session().serialise();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "session\t2026-01-02T10:20:30+01:00\tpowers\t-n,2\t--base=3\tcompleted"
+        );
+        assert_eq!(lines[1], "question\t3^2\t9\t9\t1\t1500\t0");
+        assert_eq!(lines[2], "question\t3^3\t27\t2\\,7,27\t0\t4321\t0");
+
+        let mut interrupted = session();
+        interrupted.completed = false;
+        interrupted.records.clear();
+        assert!(interrupted.serialise().ends_with("\tinterrupted\n"));
+    }
+
+    #[test]
+    fn unescaping() {
+        for text in [
+            "plain text",
+            "a\tb\nc\rd",
+            "1,2",
+            "back\\slash",
+            "\\,\\t",
+            "",
+        ] {
+            assert_eq!(unescape(&escape(text)), text);
+        }
+        assert_eq!(
+            split_list("a\\,b,c"),
+            vec!["a,b".to_string(), "c".to_string()]
+        );
+        assert_eq!(split_list(""), vec![String::new()]);
+        assert_eq!(split_list(","), vec![String::new(), String::new()]);
+        assert!(split_options("").is_empty());
+        assert_eq!(split_answers(""), vec![String::new()]);
+        assert!(split_answers(&escape_answers(&[])).is_empty());
+    }
+
+    #[test]
+    fn session_deserialisation() {
+        let mut interrupted = session();
+        interrupted.completed = false;
+        interrupted.general_options.clear();
+        interrupted.records[0].given_answers = vec![String::new()];
+
+        let text = session().serialise() + &interrupted.serialise();
+        let sessions = parse(&text).expect("Test: history should be parsed");
+        assert_eq!(sessions, vec![session(), interrupted]);
+    }
+
+    #[test]
+    fn timed_out_question_without_answers() {
+        let mut timed_out = session();
+        timed_out.records[1].given_answers.clear();
+        timed_out.records[1].timed_out = true;
+
+        let text = timed_out.serialise();
+        assert!(text.ends_with("question\t3^3\t27\t\\\t0\t4321\t1\n"));
+        let sessions = parse(&text).expect("Test: history should be parsed");
+        assert_eq!(sessions, vec![timed_out]);
+    }
+
+    #[test]
+    fn history_without_timeouts() {
+        let text = "session\t2026-01-02T10:20:30+01:00\tpowers\t-n,2\t--base=3\tcompleted\n\
+                    question\t3^2\t9\t9\t1\t1500\n\
+                    question\t3^3\t27\t2\\,7,27\t0\t4321\n";
+        let sessions = parse(text).expect("Test: history should be parsed");
+        assert_eq!(sessions, vec![session()]);
+    }
+
+    #[test]
+    fn corrupted_history() {
+        assert_eq!(parse("question\t3^2\t9\t9\t1\t1500\n"), Err(1));
+
+        let text = session().serialise() + "garbage\n";
+        assert_eq!(parse(&text), Err(4));
+
+        let text = session().serialise().replace("\t1500", "\tabc");
+        assert_eq!(parse(&text), Err(2));
+
+        let text = session().serialise().replace("completed", "whatever");
+        assert_eq!(parse(&text), Err(1));
+
+        let text = session().serialise().replace("\t1500\t0", "\t1500\tyes");
+        assert_eq!(parse(&text), Err(2));
+
+        let text = session().serialise().replace("\t1500\t0", "\t1500\t0\t0");
+        assert_eq!(parse(&text), Err(2));
+    }
+
+    #[test]
+    fn missing_file_is_empty_history() {
+        let path = env::temp_dir().join("practicestuff_history_test_does_not_exist");
+        assert_eq!(load(&path), Ok(vec![]));
+    }
+
+    #[test]
+    fn append_to_file() {
+        let dir =
+            env::temp_dir().join(format!("practicestuff_history_test_{}", std::process::id()));
+        let path = dir.join("history");
+        let _ = fs::remove_dir_all(&dir);
+
+        append(&path, &session()).expect("Test: could not append session");
+        append(&path, &session()).expect("Test: could not append session");
+
+        let text = fs::read_to_string(&path).expect("Test: could not read history");
+        assert_eq!(text, session().serialise().repeat(2));
+
+        fs::remove_dir_all(&dir).expect("Test: could not clean up");
+    }
+}
@@ -0,0 +1,438 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+
+use super::Session;
+use crate::application::APP_NAME;
+use crate::args::prelude::*;
+use crate::skill;
+use crate::stats::{self, Record};
+
+pub const CMD: &str = "history";
+
+const ARG_ID_HELP: &str = "help";
+const ARG_ID_SKILL: &str = "skill";
+const ARG_ID_SINCE: &str = "since";
+const ARG_ID_UNTIL: &str = "until";
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug)]
+pub struct History {
+    arg_definitions: Vec<Arg>,
+    show_help: bool,
+
+    skill: Option<String>,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+}
+
+impl History {
+    pub fn build(args: &[String]) -> Result<Self, String> {
+        let arg_definitions = Self::build_arg_definitions();
+        let parsed_args = parser::parse_and_validate_arg_list(args, &arg_definitions)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        let show_help =
+            bool::set_value_from_arg_or_default(ARG_ID_HELP, &parsed_args, &arg_definitions);
+        let skill =
+            String::set_value_from_arg_or_default(ARG_ID_SKILL, &parsed_args, &arg_definitions);
+        let since =
+            String::set_value_from_arg_or_default(ARG_ID_SINCE, &parsed_args, &arg_definitions);
+        let until =
+            String::set_value_from_arg_or_default(ARG_ID_UNTIL, &parsed_args, &arg_definitions);
+
+        let skill = if skill.is_empty() { None } else { Some(skill) };
+        let since = Self::parse_date(&since)?;
+        let until = Self::parse_date(&until)?;
+
+        if let (Some(since), Some(until)) = (since, until) {
+            if since > until {
+                return Err(Self::build_err_message(Some(
+                    "'since' date must be earlier than or equal to 'until' date".to_string(),
+                )));
+            }
+        }
+
+        Ok(Self {
+            arg_definitions,
+            show_help,
+            skill,
+            since,
+            until,
+        })
+    }
+
+    pub fn wants_to_print_help(&self) -> bool {
+        self.show_help
+    }
+
+    pub fn get_help_text(&self) -> String {
+        let definitions = &self.arg_definitions;
+        let options = help::Options::new("History options", definitions);
+        help::build(
+            &Self::usage(),
+            Some(&Self::additional_info()),
+            &options,
+            &[],
+        )
+    }
+
+    /// Builds a list of sessions matching the filters, followed by a summary per skill.
+    pub fn get_report(&self, sessions: &[Session]) -> String {
+        let sessions: Vec<_> = sessions
+            .iter()
+            .filter(|session| self.matches(session))
+            .collect();
+        if sessions.is_empty() {
+            return "No sessions found.".to_string();
+        }
+
+        let command_width = sessions
+            .iter()
+            .map(|session| session.command.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut report = String::from("Sessions:\n");
+        for session in &sessions {
+            report.push_str(&format!(
+                "  {}  {:<command_width$}  {:>9} ({})  {}{}\n",
+                session.timestamp.format("%Y-%m-%d %H:%M"),
+                session.command,
+                Self::correct_answers(&session.records),
+                Self::accuracy(&session.records),
+                stats::format_duration(&session.records.iter().map(|r| r.duration).sum()),
+                if session.completed {
+                    ""
+                } else {
+                    " (interrupted)"
+                },
+            ));
+        }
+
+        let mut sessions_per_skill: BTreeMap<&str, Vec<&Session>> = BTreeMap::new();
+        for session in &sessions {
+            sessions_per_skill
+                .entry(&session.command)
+                .or_default()
+                .push(session);
+        }
+
+        report.push_str("\nSummary:");
+        for (skill, sessions) in sessions_per_skill {
+            let records: Vec<_> = sessions
+                .iter()
+                .flat_map(|session| session.records.iter().cloned())
+                .collect();
+            report.push_str(&format!(
+                "\n  {skill}: {} session(s), {} question(s)\n",
+                sessions.len(),
+                records.len()
+            ));
+            report.push_str(&format!(
+                "    accuracy: {} ({})\n",
+                Self::accuracy(&records),
+                Self::correct_answers(&records)
+            ));
+            report.push_str(&format!(
+                "    avg time: {}, median time: {}",
+                stats::format_duration(&stats::avg_question_time(&records)),
+                stats::format_duration(&Self::median_time(&records))
+            ));
+        }
+
+        report
+    }
+
+    fn matches(&self, session: &Session) -> bool {
+        let date = session.timestamp.date_naive();
+        self.skill
+            .as_ref()
+            .is_none_or(|skill| *skill == session.command)
+            && self.since.is_none_or(|since| date >= since)
+            && self.until.is_none_or(|until| date <= until)
+    }
+
+    fn correct_answers(records: &[Record]) -> String {
+        let correct = records.iter().filter(|record| record.correct).count();
+        format!("{correct}/{}", records.len())
+    }
+
+    fn accuracy(records: &[Record]) -> String {
+        let answered = u32::try_from(records.len()).expect("Records vector len > u32::MAX");
+        let acc = stats::accuracy(Self::number_of_correct_answers(records), answered);
+        format!("{acc:.2}%")
+    }
+
+    fn number_of_correct_answers(records: &[Record]) -> u32 {
+        let correct = records.iter().filter(|record| record.correct).count();
+        u32::try_from(correct).expect("Records vector len > u32::MAX")
+    }
+
+    fn median_time(records: &[Record]) -> Duration {
+        let mut times: Vec<_> = records.iter().map(|record| record.duration).collect();
+        times.sort();
+        let middle = times.len() / 2;
+        match times.len() {
+            0 => Duration::ZERO,
+            len if len % 2 == 0 => (times[middle - 1] + times[middle]) / 2,
+            _ => times[middle],
+        }
+    }
+
+    fn parse_date(date: &str) -> Result<Option<NaiveDate>, String> {
+        if date.is_empty() {
+            return Ok(None);
+        }
+        NaiveDate::parse_from_str(date, DATE_FORMAT)
+            .map(Some)
+            .map_err(|_| {
+                Self::build_err_message(Some(format!(
+                    "invalid date: '{date}'; expected format is YYYY-MM-DD"
+                )))
+            })
+    }
+
+    fn usage() -> String {
+        format!("Usage: {APP_NAME} [option]... history [history_option]...")
+    }
+
+    fn help_prompt() -> String {
+        format!("Try '{APP_NAME} history --help' for more information.")
+    }
+
+    fn additional_info() -> String {
+        let mut text = String::new();
+        text.push_str("List past sessions and summarise accuracy and time taken per skill.\n");
+        text.push_str("Sessions are read from the history file (see '--history-file' in\n");
+        text.push_str(&format!("'{APP_NAME} --help')."));
+        text
+    }
+
+    fn build_arg_definitions() -> Vec<Arg> {
+        vec![
+            Arg::builder()
+                .id(ARG_ID_HELP)
+                .short_name('h')
+                .long_name("help")
+                .description(vec!["Display help for history command.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(true)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_SKILL)
+                .short_name('s')
+                .long_name("skill")
+                .description(vec![
+                    "Show only sessions of the given skill".to_string(),
+                    "(default: all skills).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(
+                    skill::COMMANDS.iter().map(ToString::to_string).collect(),
+                )))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(String::new()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_SINCE)
+                .long_name("since")
+                .description(vec![
+                    "Show only sessions from the given day".to_string(),
+                    "(YYYY-MM-DD) onwards.".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::Str))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(String::new()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_UNTIL)
+                .long_name("until")
+                .description(vec![
+                    "Show only sessions up to the given day".to_string(),
+                    "(YYYY-MM-DD), inclusive.".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::Str))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(String::new()))
+                .build(),
+        ]
+    }
+
+    fn build_err_message(msg: Option<String>) -> String {
+        if let Some(msg) = msg {
+            format!(
+                "{}: {}: {}\n{}\n{}",
+                APP_NAME,
+                CMD,
+                msg,
+                Self::usage(),
+                Self::help_prompt()
+            )
+        } else {
+            format!("{}\n{}", Self::usage(), Self::help_prompt())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    fn record(correct: bool, millis: u64) -> Record {
+        Record {
+            prompt: "Question".to_string(),
+            correct_answer: "Answer".to_string(),
+            given_answers: vec!["Answer".to_string()],
+            correct,
+            duration: Duration::from_millis(millis),
+            timed_out: false,
+            categories: vec![],
+        }
+    }
+
+    fn session(timestamp: &str, command: &str, records: Vec<Record>) -> Session {
+        Session {
+            timestamp: DateTime::parse_from_rfc3339(timestamp).expect("Test: invalid timestamp"),
+            command: command.to_string(),
+            general_options: vec![],
+            command_options: vec![],
+            completed: true,
+            records,
+        }
+    }
+
+    fn sessions() -> Vec<Session> {
+        vec![
+            session(
+                "2026-01-01T10:00:00+00:00",
+                "powers",
+                vec![record(true, 1000), record(false, 3000)],
+            ),
+            session(
+                "2026-02-01T10:00:00+00:00",
+                "doomsday",
+                vec![record(true, 5000)],
+            ),
+            session(
+                "2026-03-01T10:00:00+00:00",
+                "powers",
+                vec![record(true, 2000), record(true, 10000)],
+            ),
+        ]
+    }
+
+    #[test]
+    fn build_history_defaults() {
+        let args = [];
+        let history = History::build(&args).expect("Should build correctly with no args");
+        assert!(!history.show_help);
+        assert_eq!(history.skill, None);
+        assert_eq!(history.since, None);
+        assert_eq!(history.until, None);
+    }
+
+    #[test]
+    fn build_history_with_args() {
+        let args = [
+            "-s".to_string(),
+            "doomsday".to_string(),
+            "--since=2026-01-01".to_string(),
+            "--until=2026-02-03".to_string(),
+        ];
+        let history = History::build(&args).expect("Should build correctly with args");
+        assert_eq!(history.skill, Some("doomsday".to_string()));
+        assert_eq!(history.since, NaiveDate::from_ymd_opt(2026, 1, 1));
+        assert_eq!(history.until, NaiveDate::from_ymd_opt(2026, 2, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid option argument")]
+    fn build_history_unknown_skill() {
+        let args = ["-s".to_string(), "history".to_string()];
+        History::build(&args).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid date")]
+    fn build_history_invalid_date() {
+        let args = ["--since=2026-13-01".to_string()];
+        History::build(&args).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "must be earlier than or equal to")]
+    fn build_history_mismatched_dates() {
+        let args = [
+            "--since=2026-01-02".to_string(),
+            "--until=2026-01-01".to_string(),
+        ];
+        History::build(&args).unwrap();
+    }
+
+    #[test]
+    fn report_all_sessions() {
+        let history = History::build(&[]).expect("Should build correctly");
+        let report = history.get_report(&sessions());
+
+        assert!(report.contains("2026-01-01 10:00  powers          1/2 (50.00%)  4.0s"));
+        assert!(report.contains("2026-02-01 10:00  doomsday        1/1 (100.00%)  5.0s"));
+        assert!(report.contains("doomsday: 1 session(s), 1 question(s)"));
+        assert!(report.contains("powers: 2 session(s), 4 question(s)"));
+        assert!(report.contains("accuracy: 75.00% (3/4)"));
+        assert!(report.contains("avg time: 4.0s, median time: 2.5s"));
+    }
+
+    #[test]
+    fn report_filtered_sessions() {
+        let args = [
+            "--skill=powers".to_string(),
+            "--since=2026-02-01".to_string(),
+        ];
+        let history = History::build(&args).expect("Should build correctly");
+        let report = history.get_report(&sessions());
+
+        assert!(!report.contains("2026-01-01"));
+        assert!(!report.contains("doomsday"));
+        assert!(report.contains("2026-03-01 10:00  powers"));
+        assert!(report.contains("powers: 1 session(s), 2 question(s)"));
+        assert!(report.contains("avg time: 6.0s, median time: 6.0s"));
+
+        let args = ["--until=2025-12-31".to_string()];
+        let history = History::build(&args).expect("Should build correctly");
+        assert_eq!(history.get_report(&sessions()), "No sessions found.");
+    }
+
+    #[test]
+    fn median() {
+        assert_eq!(History::median_time(&[]), Duration::ZERO);
+        assert_eq!(
+            History::median_time(&[record(true, 3000), record(true, 1000), record(true, 2000)]),
+            Duration::from_millis(2000)
+        );
+        assert_eq!(
+            History::median_time(&[record(true, 4000), record(true, 1000)]),
+            Duration::from_millis(2500)
+        );
+    }
+
+    #[test]
+    fn help_text() {
+        let args = ["-h".to_string()];
+        let history = History::build(&args).expect("Should build correctly");
+        assert!(history.wants_to_print_help());
+        let help_text = history.get_help_text();
+        assert!(help_text.contains("History options"));
+        assert!(help_text.contains("Usage"));
+
+        // Ensure all flags are included
+        assert!(help_text.contains("-h, --help"));
+        assert!(help_text.contains("-s, --skill"));
+        assert!(help_text.contains("--since"));
+        assert!(help_text.contains("--until"));
+    }
+}
@@ -0,0 +1,108 @@
+//! Reading answers from stdin with an optional deadline.
+//!
+//! Lines are read by a background thread and passed over a channel, so waiting for an answer
+//! can be abandoned when time runs out, without leaving the terminal in raw mode (and with
+//! piped input still working).
+
+use std::io::{self, BufRead};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Instant;
+
+#[derive(Default)]
+pub struct Input {
+    lines: OnceLock<Mutex<Receiver<String>>>,
+}
+
+impl Input {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a trimmed line. Returns `None` if `deadline` passed before the line was entered.
+    /// Once stdin is closed, empty lines are returned, or `None` when the deadline passes.
+    pub fn read_line(&self, deadline: Option<Instant>) -> Option<String> {
+        let lines = self
+            .lines
+            .get_or_init(|| Mutex::new(Self::spawn_reader()))
+            .lock()
+            .expect("Input is blocked");
+        let line = match deadline {
+            Some(deadline) => {
+                match lines.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(line) => Some(line),
+                    Err(RecvTimeoutError::Timeout) => return None,
+                    Err(RecvTimeoutError::Disconnected) => {
+                        thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                        return None;
+                    }
+                }
+            }
+            None => lines.recv().ok(),
+        };
+        Some(line.map(|line| line.trim().to_string()).unwrap_or_default())
+    }
+
+    /// Throws away lines entered so far but not read yet, e.g. an answer finished just after
+    /// its question timed out, so that it isn't taken as the answer to the next question.
+    pub fn discard_pending(&self) {
+        if let Some(lines) = self.lines.get() {
+            let lines = lines.lock().expect("Input is blocked");
+            while lines.try_recv().is_ok() {}
+        }
+    }
+
+    fn spawn_reader() -> Receiver<String> {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let line = line.expect("IO operation failed (stdin)");
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        receiver
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn input(receiver: Receiver<String>) -> Input {
+        Input {
+            lines: OnceLock::from(Mutex::new(receiver)),
+        }
+    }
+
+    #[test]
+    fn read_lines() {
+        let (sender, receiver) = mpsc::channel();
+        let input = input(receiver);
+        sender.send(" 42 ".to_string()).expect("Test: line should be sent");
+        assert_eq!(input.read_line(None), Some("42".to_string()));
+        let deadline = Instant::now() + Duration::from_millis(10);
+        assert_eq!(input.read_line(Some(deadline)), None);
+    }
+
+    #[test]
+    fn line_entered_after_timeout_is_discarded() {
+        let (sender, receiver) = mpsc::channel();
+        let input = input(receiver);
+        let deadline = Instant::now() + Duration::from_millis(10);
+        assert_eq!(input.read_line(Some(deadline)), None);
+
+        // Entered after the previous question timed out
+        sender.send("late".to_string()).expect("Test: line should be sent");
+        input.discard_pending();
+        let deadline = Instant::now() + Duration::from_millis(10);
+        assert_eq!(input.read_line(Some(deadline)), None);
+
+        sender.send("next".to_string()).expect("Test: line should be sent");
+        assert_eq!(input.read_line(None), Some("next".to_string()));
+    }
+}
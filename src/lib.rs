@@ -1,10 +1,16 @@
+mod adaptive;
 mod application;
 mod args;
 mod colour;
 mod config;
+mod export;
+mod history;
+mod input;
 mod question;
+mod repetition;
 mod skill;
 mod stats;
+mod worksheet;
 
 pub use application::Application;
 pub use config::Config;
@@ -1,6 +1,15 @@
 use std::cell::{Cell, RefCell};
+use std::cmp;
+use std::collections::{BTreeMap, HashSet};
+use std::iter;
+use std::time::Instant;
+
+use chrono::Local;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 use crate::config::NumberOfQuestions;
+use crate::repetition::Scheduler;
 use crate::skill::Skill;
 
 #[derive(Clone)]
@@ -9,6 +18,155 @@ pub struct Question {
     answer: String,
     alternative_answers: Vec<String>,
     allow_any_case: bool,
+    matcher: Matcher,
+    key: Option<String>,
+    categories: Vec<Category>,
+    explanation: Option<String>,
+}
+
+/// How given answers are compared with the correct one
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Matcher {
+    /// The same text (ignoring case if allowed)
+    #[default]
+    Text,
+    /// The same numbers in the same order. Numbers can be separated with spaces or 'r'
+    /// (e.g. quotient and remainder "8 r 3", "8r3" or "8 3") and use a decimal point
+    /// or comma, trailing zeros don't matter ("2.50" is the same as "2.5"). Only plain
+    /// decimal numbers are accepted, not e.g. "1e1" or "inf".
+    Numbers,
+    /// The same integer in the given base (2 to 36). A base prefix ("0b", "0o" or "0x"),
+    /// '_' separators and leading zeros are allowed and case doesn't matter, e.g. "0x3F",
+    /// "3f" and "0X_003F" are the same.
+    Radix(u32),
+    /// The same multiset of factors in any order. Factors are separated with '*' (or '×')
+    /// and repeated ones can be written as powers, e.g. "2^3*3^2*5", "5 * 3^2 * 2^3" and
+    /// "2*2*2*3*3*5" are the same.
+    Factors,
+}
+
+impl Matcher {
+    fn matches(self, answer: &str, correct_answer: &str, allow_any_case: bool) -> bool {
+        match self {
+            Matcher::Text if allow_any_case => {
+                answer.to_lowercase() == correct_answer.to_lowercase()
+            }
+            Matcher::Text => answer == correct_answer,
+            Matcher::Numbers => match (Self::numbers(answer), Self::numbers(correct_answer)) {
+                (Some(answer), Some(correct_answer)) => answer == correct_answer,
+                _ => false,
+            },
+            Matcher::Radix(radix) => match (
+                Self::integer(answer, radix),
+                Self::integer(correct_answer, radix),
+            ) {
+                (Some(answer), Some(correct_answer)) => answer == correct_answer,
+                _ => false,
+            },
+            Matcher::Factors => match (Self::factors(answer), Self::factors(correct_answer)) {
+                (Some(answer), Some(correct_answer)) => answer == correct_answer,
+                _ => false,
+            },
+        }
+    }
+
+    /// Parses an integer in the given base, see `Matcher::Radix`. Returns `None` if
+    /// anything else is found.
+    fn integer(text: &str, radix: u32) -> Option<u64> {
+        let text = text.trim().to_ascii_lowercase();
+        let prefix = match radix {
+            2 => "0b",
+            8 => "0o",
+            16 => "0x",
+            _ => "",
+        };
+        let digits: String = text
+            .strip_prefix(prefix)
+            .unwrap_or(&text)
+            .chars()
+            .filter(|&c| c != '_')
+            .collect();
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return None;
+        }
+        u64::from_str_radix(&digits, radix).ok()
+    }
+
+    /// Parses factors, see `Matcher::Factors`, into their exponents. Returns `None` if
+    /// anything else is found.
+    fn factors(text: &str) -> Option<BTreeMap<u64, u32>> {
+        let mut factors = BTreeMap::new();
+        for term in text.split(['*', '×']) {
+            let (factor, exponent) = match term.split_once('^') {
+                Some((factor, exponent)) => (factor, exponent.trim().parse::<u32>().ok()?),
+                None => (term, 1),
+            };
+            if exponent == 0 {
+                return None;
+            }
+            let factor = factor.trim().parse::<u64>().ok()?;
+            *factors.entry(factor).or_insert(0) += exponent;
+        }
+        Some(factors)
+    }
+
+    /// Parses numbers separated with whitespace or 'r'. Returns `None` if anything else
+    /// is found.
+    fn numbers(text: &str) -> Option<Vec<f64>> {
+        let numbers = text
+            .split(|c: char| c.is_whitespace() || c.eq_ignore_ascii_case(&'r'))
+            .filter(|part| !part.is_empty())
+            .map(Self::number)
+            .collect::<Option<Vec<_>>>()?;
+        (!numbers.is_empty()).then_some(numbers)
+    }
+
+    /// Parses a plain decimal number: an optional '-', digits and optionally a decimal
+    /// point or comma followed by more digits. Unlike `str::parse`, rejects exponents,
+    /// "inf", "NaN", a leading '+' and a trailing point.
+    fn number(text: &str) -> Option<f64> {
+        let digits = text.strip_prefix('-').unwrap_or(text);
+        let (integer, fraction) = match digits.split_once(['.', ',']) {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+        let is_digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+        if !is_digits(integer) || !fraction.is_none_or(is_digits) {
+            return None;
+        }
+        text.replace(',', ".").parse::<f64>().ok()
+    }
+}
+
+/// Tag used to break statistics down, e.g. exponent 13 or weekday Thursday.
+/// Values of the same category are sorted by `order`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Category {
+    name: String,
+    value: String,
+    order: i64,
+}
+
+impl Category {
+    pub fn new(name: &str, value: &str, order: i64) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+            order,
+        }
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn value(&self) -> &String {
+        &self.value
+    }
+
+    pub fn order(&self) -> i64 {
+        self.order
+    }
 }
 
 impl Question {
@@ -24,16 +182,27 @@ impl Question {
         &self.answer
     }
 
+    /// Stable identifier of the question, e.g. "times_table:7*8". Used for spaced repetition.
+    pub fn key(&self) -> Option<&String> {
+        self.key.as_ref()
+    }
+
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Worked solution, shown with the correct answer if requested (`--explain`)
+    pub fn explanation(&self) -> Option<&String> {
+        self.explanation.as_ref()
+    }
+
     pub fn is_answer_correct(&self, answer: &str) -> bool {
-        if self.allow_any_case {
-            answer.to_ascii_lowercase() == self.answer.to_ascii_lowercase()
-                || self
-                    .alternative_answers
-                    .iter()
-                    .any(|elem| answer.to_ascii_lowercase() == elem.to_ascii_lowercase())
-        } else {
-            answer == self.answer || self.alternative_answers.contains(&answer.to_string())
-        }
+        iter::once(&self.answer)
+            .chain(&self.alternative_answers)
+            .any(|correct_answer| {
+                self.matcher
+                    .matches(answer, correct_answer, self.allow_any_case)
+            })
     }
 }
 
@@ -43,6 +212,10 @@ pub struct Builder {
     answer: String,
     alternative_answers: Vec<String>,
     allow_any_case: bool,
+    matcher: Matcher,
+    key: Option<String>,
+    categories: Vec<Category>,
+    explanation: Option<String>,
 }
 
 impl Builder {
@@ -66,6 +239,26 @@ impl Builder {
         self
     }
 
+    pub fn matcher(mut self, matcher: Matcher) -> Self {
+        self.matcher = matcher;
+        self
+    }
+
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.to_string());
+        self
+    }
+
+    pub fn categories(mut self, categories: &[Category]) -> Self {
+        self.categories = categories.to_vec();
+        self
+    }
+
+    pub fn explanation(mut self, explanation: &str) -> Self {
+        self.explanation = Some(explanation.to_string());
+        self
+    }
+
     pub fn build(self) -> Question {
         assert!(!self.question.is_empty(), "Question cannot be empty");
         assert!(!self.answer.is_empty(), "Answer cannot be empty");
@@ -74,6 +267,10 @@ impl Builder {
             answer: self.answer,
             alternative_answers: self.alternative_answers,
             allow_any_case: self.allow_any_case,
+            matcher: self.matcher,
+            key: self.key,
+            categories: self.categories,
+            explanation: self.explanation,
         }
     }
 }
@@ -82,26 +279,42 @@ pub struct Generator<'a> {
     number_of_questions: NumberOfQuestions,
     current_question: Cell<u32>,
     skill: &'a dyn Skill,
+    scheduler: Option<&'a Scheduler>,
+    scheduled_keys: RefCell<HashSet<String>>,
     cache: RefCell<Option<Vec<Question>>>,
+    deadline: Option<Instant>,
+    rng: RefCell<StdRng>,
 }
 
 impl<'a> Generator<'a> {
+    /// Creates a generator. If `scheduler` is provided, questions due for repetition
+    /// (and weak ones) are served before the ones generated by the skill.
+    /// In time-limited mode, the clock starts when the generator is created.
+    /// With `seed`, the skill generates the same sequence of questions every time.
     pub fn new(
         number_of_questions: NumberOfQuestions,
         skill: &'a dyn Skill,
+        scheduler: Option<&'a Scheduler>,
+        seed: Option<u64>,
     ) -> Generator<'a> {
         Generator {
             number_of_questions,
             current_question: Cell::new(0),
             skill,
+            scheduler,
+            scheduled_keys: RefCell::new(HashSet::new()),
             cache: RefCell::new(None),
+            deadline: match number_of_questions {
+                NumberOfQuestions::TimeLimited(limit) => Some(Instant::now() + limit),
+                _ => None,
+            },
+            rng: RefCell::new(seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64)),
         }
     }
 
     pub fn next_question(&self) -> Result<Question, String> {
         match self.number_of_questions {
-            NumberOfQuestions::Infinite => Ok(self
-                .skill
+            NumberOfQuestions::Infinite | NumberOfQuestions::TimeLimited(_) => Ok(self
                 .generate_questions(1)
                 .first()
                 .expect("Question could not be generated")
@@ -110,13 +323,7 @@ impl<'a> Generator<'a> {
                 self.current_question.set(self.current_question.get() + 1);
                 let mut cache = self.cache.borrow_mut();
                 if cache.is_none() {
-                    let questions = self.skill.generate_questions(num);
-                    assert_eq!(
-                        questions.len(),
-                        num as usize,
-                        "Skill did not generate correct amount of questions"
-                    );
-                    cache.replace(questions);
+                    cache.replace(self.generate_questions(num));
                 }
                 if let Some(questions) = cache.as_ref() {
                     if self.current_question.get() as usize > questions.len() {
@@ -135,14 +342,87 @@ impl<'a> Generator<'a> {
         match self.number_of_questions {
             NumberOfQuestions::Infinite => true,
             NumberOfQuestions::Limited(num) => self.current_question.get() < num,
+            NumberOfQuestions::TimeLimited(_) => self
+                .deadline
+                .is_some_and(|deadline| Instant::now() < deadline),
+        }
+    }
+
+    /// Time the session ends at (time-limited mode only)
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Regenerates the questions that have not been asked yet, e.g. after the skill changed
+    /// its range in adaptive mode.
+    pub fn refresh(&self) {
+        let mut cache = self.cache.borrow_mut();
+        let Some(questions) = cache.as_mut() else {
+            return;
+        };
+        let asked = cmp::min(self.current_question.get() as usize, questions.len());
+        let mut scheduled_keys = self.scheduled_keys.borrow_mut();
+        for question in questions.drain(asked..) {
+            // Scheduled questions that have not been asked yet can be served again
+            if let Some(key) = question.key() {
+                scheduled_keys.remove(key);
+            }
+        }
+        drop(scheduled_keys);
+
+        if let NumberOfQuestions::Limited(num) = self.number_of_questions {
+            let remaining = num - u32::try_from(asked).expect("asked <= num");
+            questions.extend(self.generate_questions(remaining));
         }
     }
+
+    fn generate_questions(&self, count: u32) -> Vec<Question> {
+        let mut questions = self.scheduled_questions(count);
+        let remaining = count - u32::try_from(questions.len()).expect("count is u32");
+        if remaining > 0 {
+            let generated = self
+                .skill
+                .generate_questions(remaining, &mut *self.rng.borrow_mut());
+            assert_eq!(
+                generated.len(),
+                remaining as usize,
+                "Skill did not generate correct amount of questions"
+            );
+            questions.extend(generated);
+        }
+        questions
+    }
+
+    /// Questions chosen by the scheduler. Each of them is served at most once per session.
+    fn scheduled_questions(&self, count: u32) -> Vec<Question> {
+        let Some(scheduler) = self.scheduler else {
+            return vec![];
+        };
+        let mut scheduled_keys = self.scheduled_keys.borrow_mut();
+        let mut questions = vec![];
+        for key in scheduler.prioritised_keys(Local::now().date_naive()) {
+            if questions.len() == count as usize {
+                break;
+            }
+            if scheduled_keys.contains(&key) {
+                continue;
+            }
+            if let Some(question) = self.skill.question_from_key(&key) {
+                scheduled_keys.insert(key);
+                questions.push(question);
+            }
+        }
+        questions
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
     use std::{fmt::Debug, sync::RwLock};
 
+    use rand::RngCore;
+
     use crate::skill::Base;
 
     use super::*;
@@ -172,6 +452,9 @@ mod tests {
             .answer("Answer")
             .alternative_answers(&["Alt1".to_string(), "Alt2".to_string()])
             .allow_any_case(true)
+            .key("skill:question")
+            .categories(&[Category::new("category", "value", 1)])
+            .explanation("Because")
             .build();
 
         assert_eq!(question.prompt, "Question");
@@ -181,6 +464,12 @@ mod tests {
             vec!["Alt1".to_string(), "Alt2".to_string()]
         );
         assert!(question.allow_any_case);
+        assert_eq!(question.key, Some("skill:question".to_string()));
+        assert_eq!(
+            question.categories,
+            vec![Category::new("category", "value", 1)]
+        );
+        assert_eq!(question.explanation, Some("Because".to_string()));
     }
 
     #[test]
@@ -192,6 +481,8 @@ mod tests {
 
         assert_eq!(question.prompt(), "Question");
         assert_eq!(question.correct_answer(), "Answer");
+        assert_eq!(question.key(), None);
+        assert!(question.categories().is_empty());
     }
 
     #[test]
@@ -230,6 +521,139 @@ mod tests {
         assert!(!question.is_answer_correct("Alt3"));
         assert!(question.is_answer_correct("alt1"));
         assert!(question.is_answer_correct("answer"));
+
+        let question = Question::builder()
+            .question("Question")
+            .answer("środa")
+            .allow_any_case(true)
+            .build();
+        assert!(question.is_answer_correct("Środa"));
+        assert!(!question.is_answer_correct("Sroda"));
+    }
+
+    #[test]
+    fn number_answer_verification() {
+        let question = Question::builder()
+            .question("59/7")
+            .answer("8 r 3")
+            .matcher(Matcher::Numbers)
+            .build();
+
+        assert!(question.is_answer_correct("8 r 3"));
+        assert!(question.is_answer_correct("8r3"));
+        assert!(question.is_answer_correct("8 R 3"));
+        assert!(question.is_answer_correct("8 3"));
+        assert!(question.is_answer_correct("08 3.0"));
+        assert!(!question.is_answer_correct("8"));
+        assert!(!question.is_answer_correct("3 r 8"));
+        assert!(!question.is_answer_correct("8 r 3 r 1"));
+        assert!(!question.is_answer_correct("8 rem 3"));
+        assert!(!question.is_answer_correct(""));
+
+        let question = Question::builder()
+            .question("59/7")
+            .answer("8.43")
+            .matcher(Matcher::Numbers)
+            .build();
+
+        assert!(question.is_answer_correct("8.43"));
+        assert!(question.is_answer_correct("8,43"));
+        assert!(question.is_answer_correct("8.430"));
+        assert!(!question.is_answer_correct("8.4"));
+        assert!(!question.is_answer_correct("8.429"));
+
+        let question = Question::builder()
+            .question("50/5")
+            .answer("10")
+            .matcher(Matcher::Numbers)
+            .build();
+
+        assert!(question.is_answer_correct("10"));
+        assert!(question.is_answer_correct("10.0"));
+        assert!(!question.is_answer_correct("1e1"));
+        assert!(!question.is_answer_correct("+10"));
+        assert!(!question.is_answer_correct("10."));
+        assert!(!question.is_answer_correct("10.0.0"));
+        assert!(!question.is_answer_correct("1,0.0"));
+        assert!(!question.is_answer_correct("-"));
+        assert!(!question.is_answer_correct("inf"));
+
+        assert_eq!(Matcher::number("-2,5"), Some(-2.5));
+        for text in ["inf", "infinity", "NaN", "1e1", ".5"] {
+            assert_eq!(Matcher::number(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn radix_answer_verification() {
+        let question = Question::builder()
+            .question("63 in hex")
+            .answer("0x3F")
+            .matcher(Matcher::Radix(16))
+            .build();
+
+        assert!(question.is_answer_correct("0x3F"));
+        assert!(question.is_answer_correct("3f"));
+        assert!(question.is_answer_correct("0X_003F"));
+        assert!(question.is_answer_correct(" 3_f "));
+        assert!(!question.is_answer_correct("63"));
+        assert!(!question.is_answer_correct("0x"));
+        assert!(!question.is_answer_correct("+3F"));
+        assert!(!question.is_answer_correct("0b111111"));
+        assert!(!question.is_answer_correct(""));
+
+        let question = Question::builder()
+            .question("182 in binary")
+            .answer("0b1011_0110")
+            .matcher(Matcher::Radix(2))
+            .build();
+
+        assert!(question.is_answer_correct("10110110"));
+        assert!(question.is_answer_correct("0b10110110"));
+        assert!(question.is_answer_correct("0000_1011_0110"));
+        assert!(!question.is_answer_correct("0xB6"));
+        assert!(!question.is_answer_correct("10110112"));
+
+        let question = Question::builder()
+            .question("0x3F in decimal")
+            .answer("63")
+            .matcher(Matcher::Radix(10))
+            .build();
+
+        assert!(question.is_answer_correct("63"));
+        assert!(question.is_answer_correct("0_63"));
+        assert!(!question.is_answer_correct("0x3F"));
+    }
+
+    #[test]
+    fn factors_answer_verification() {
+        let question = Question::builder()
+            .question("Factorise 360")
+            .answer("2^3*3^2*5")
+            .matcher(Matcher::Factors)
+            .build();
+
+        assert!(question.is_answer_correct("2^3*3^2*5"));
+        assert!(question.is_answer_correct("2*2*2*3*3*5"));
+        assert!(question.is_answer_correct("5 * 3^2 * 2 * 2^2"));
+        assert!(question.is_answer_correct("3 × 2^3 × 5 × 3"));
+        assert!(!question.is_answer_correct("2^3*3*5"));
+        assert!(!question.is_answer_correct("2^3*3^2*5*1"));
+        assert!(!question.is_answer_correct("8*45"));
+        assert!(!question.is_answer_correct("2^3*3^2*5^0*5"));
+        assert!(!question.is_answer_correct("2^3*3^2*"));
+        assert!(!question.is_answer_correct("360"));
+        assert!(!question.is_answer_correct(""));
+
+        let question = Question::builder()
+            .question("Factorise 13")
+            .answer("13")
+            .matcher(Matcher::Factors)
+            .build();
+
+        assert!(question.is_answer_correct("13"));
+        assert!(question.is_answer_correct(" 13^1 "));
+        assert!(!question.is_answer_correct("1*13"));
     }
 
     #[derive(Debug)]
@@ -246,7 +670,7 @@ mod tests {
             String::new()
         }
 
-        fn generate_questions(&self, count: u32) -> Vec<Question> {
+        fn generate_questions(&self, count: u32, _rng: &mut dyn RngCore) -> Vec<Question> {
             *self
                 .generate_questions_calls
                 .write()
@@ -259,6 +683,16 @@ mod tests {
                 count as usize
             ]
         }
+
+        fn question_from_key(&self, key: &str) -> Option<Question> {
+            key.strip_prefix("mock:").map(|id| {
+                Question::builder()
+                    .question(id)
+                    .answer("Answer")
+                    .key(key)
+                    .build()
+            })
+        }
     }
 
     impl SkillMock {
@@ -280,8 +714,12 @@ mod tests {
     fn generator_pregeneration() {
         let number_of_questions = 5;
         let skill_mock = SkillMock::new();
-        let generator =
-            Generator::new(NumberOfQuestions::Limited(number_of_questions), &skill_mock);
+        let generator = Generator::new(
+            NumberOfQuestions::Limited(number_of_questions),
+            &skill_mock,
+            None,
+            None,
+        );
         for _ in 0..number_of_questions {
             assert!(generator.has_next_question());
             let result = generator.next_question();
@@ -298,7 +736,7 @@ mod tests {
     fn generator_infinite_mode() {
         let number_of_questions = 10;
         let skill_mock = SkillMock::new();
-        let generator = Generator::new(NumberOfQuestions::Infinite, &skill_mock);
+        let generator = Generator::new(NumberOfQuestions::Infinite, &skill_mock, None, None);
         for i in 0..number_of_questions {
             assert!(generator.has_next_question());
             let result = generator.next_question();
@@ -308,6 +746,27 @@ mod tests {
         assert!(generator.has_next_question());
     }
 
+    #[test]
+    fn generator_time_limited_mode() {
+        let skill_mock = SkillMock::new();
+        let generator = Generator::new(
+            NumberOfQuestions::TimeLimited(Duration::from_millis(50)),
+            &skill_mock,
+            None,
+            None,
+        );
+        assert!(generator.deadline().is_some());
+        assert!(generator.has_next_question());
+        assert!(generator.next_question().is_ok());
+        assert_eq!(skill_mock.generate_questions_calls(), 1);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(!generator.has_next_question());
+
+        let generator = Generator::new(NumberOfQuestions::Infinite, &skill_mock, None, None);
+        assert!(generator.deadline().is_none());
+    }
+
     #[derive(Debug)]
     struct FaultySkillMock;
 
@@ -320,7 +779,7 @@ mod tests {
             String::new()
         }
 
-        fn generate_questions(&self, _count: u32) -> Vec<Question> {
+        fn generate_questions(&self, _count: u32, _rng: &mut dyn RngCore) -> Vec<Question> {
             // Always generates 2 questions
             vec![
                 Question::builder()
@@ -330,6 +789,10 @@ mod tests {
                 2
             ]
         }
+
+        fn question_from_key(&self, _key: &str) -> Option<Question> {
+            None
+        }
     }
 
     #[test]
@@ -339,7 +802,60 @@ mod tests {
         let generator = Generator::new(
             NumberOfQuestions::Limited(number_of_questions),
             &FaultySkillMock,
+            None,
+            None,
         );
         generator.next_question().unwrap();
     }
+
+    #[test]
+    fn generator_refresh() {
+        let skill_mock = SkillMock::new();
+        let generator = Generator::new(NumberOfQuestions::Limited(3), &skill_mock, None, None);
+        generator.refresh(); // Nothing generated yet
+        assert_eq!(skill_mock.generate_questions_calls(), 0);
+
+        generator.next_question().unwrap();
+        generator.refresh();
+        assert_eq!(skill_mock.generate_questions_calls(), 2);
+        assert_eq!(generator.cache.borrow().as_ref().map(Vec::len), Some(3));
+
+        generator.next_question().unwrap();
+        generator.next_question().unwrap();
+        generator.refresh(); // Nothing left to regenerate
+        assert_eq!(skill_mock.generate_questions_calls(), 2);
+        assert!(!generator.has_next_question());
+        assert!(generator.next_question().is_err());
+    }
+
+    #[test]
+    fn generator_serves_scheduled_questions_first() {
+        let today = Local::now().date_naive();
+        let scheduler = Scheduler::default();
+        scheduler.review("mock:Weak", false, Duration::from_secs(1), today);
+        scheduler.review("other:Foreign", false, Duration::from_secs(1), today);
+
+        let skill_mock = SkillMock::new();
+        let generator = Generator::new(
+            NumberOfQuestions::Limited(3),
+            &skill_mock,
+            Some(&scheduler),
+            None,
+        );
+        let prompts: Vec<_> = (0..3)
+            .map(|_| generator.next_question().unwrap().prompt().clone())
+            .collect();
+        assert_eq!(prompts, vec!["Weak", "Question", "Question"]);
+        assert_eq!(skill_mock.generate_questions_calls(), 1);
+
+        // Infinite mode: scheduled question is served only once
+        let generator = Generator::new(
+            NumberOfQuestions::Infinite,
+            &skill_mock,
+            Some(&scheduler),
+            None,
+        );
+        assert_eq!(generator.next_question().unwrap().prompt(), "Weak");
+        assert_eq!(generator.next_question().unwrap().prompt(), "Question");
+    }
 }
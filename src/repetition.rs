@@ -0,0 +1,318 @@
+//! Spaced repetition based on the SM-2 algorithm
+//! (https://en.wikipedia.org/wiki/SuperMemo#Description_of_SM-2_algorithm).
+//!
+//! Every question is identified by its key (see `Question::key`). The scheduler keeps
+//! ease, interval and due date of each question and persists them between runs in a plain
+//! text file, one question per line, fields separated with tabs:
+//! key, ease, interval in days, number of successful repetitions and due date.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Duration;
+
+use chrono::{Days, NaiveDate};
+
+const INITIAL_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+/// Questions with ease below this value are considered weak and served even if not due yet
+const WEAK_EASE: f64 = 2.0;
+/// Correct answers slower than that are treated as difficult
+const SLOW_ANSWER: Duration = Duration::from_secs(10);
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const FIELD_SEPARATOR: char = '\t';
+
+#[derive(Debug, Clone, PartialEq)]
+struct Item {
+    ease: f64,
+    interval: u32,
+    repetitions: u32,
+    due: NaiveDate,
+}
+
+impl Item {
+    fn new(today: NaiveDate) -> Self {
+        Self {
+            ease: INITIAL_EASE,
+            interval: 0,
+            repetitions: 0,
+            due: today,
+        }
+    }
+
+    /// Updates the item with SM-2 algorithm. `quality` is in range 0-5.
+    fn review(&mut self, quality: u8, today: NaiveDate) {
+        let quality = f64::from(quality.min(5));
+        if quality >= 3.0 {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (f64::from(self.interval) * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+        } else {
+            self.interval = 1;
+            self.repetitions = 0;
+        }
+        self.ease =
+            (self.ease + (0.1 - (5.0 - quality) * (0.08 + (5.0 - quality) * 0.02))).max(MIN_EASE);
+        self.due = today
+            .checked_add_days(Days::new(u64::from(self.interval)))
+            .unwrap_or(NaiveDate::MAX);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    items: RwLock<BTreeMap<String, Item>>,
+}
+
+impl Scheduler {
+    /// Loads the scheduler state from `path`. Missing file is treated as empty state.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(format!("could not read '{}': {err}", path.display())),
+        };
+        let items = Self::parse(&text)
+            .map_err(|line| format!("'{}' is corrupted (line {line})", path.display()))?;
+        Ok(Self {
+            items: RwLock::new(items),
+        })
+    }
+
+    /// Saves the scheduler state to `path`, creating the directory if needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            if !dir.as_os_str().is_empty() {
+                fs::create_dir_all(dir)?;
+            }
+        }
+        fs::write(path, self.serialise())
+    }
+
+    /// Returns keys of questions that should be asked first: due questions (the most
+    /// overdue first), followed by weak questions that are not due yet (the weakest first).
+    pub fn prioritised_keys(&self, today: NaiveDate) -> Vec<String> {
+        let items = self.read();
+
+        let mut due: Vec<_> = items.iter().filter(|(_, item)| item.due <= today).collect();
+        due.sort_by(|(_, a), (_, b)| a.due.cmp(&b.due).then(a.ease.total_cmp(&b.ease)));
+
+        let mut weak: Vec<_> = items
+            .iter()
+            .filter(|(_, item)| item.due > today && item.ease < WEAK_EASE)
+            .collect();
+        weak.sort_by(|(_, a), (_, b)| a.ease.total_cmp(&b.ease));
+
+        due.into_iter()
+            .chain(weak)
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Records the first answer to the question identified by `key`.
+    pub fn review(&self, key: &str, correct: bool, duration: Duration, today: NaiveDate) {
+        let quality = match (correct, duration > SLOW_ANSWER) {
+            (true, false) => 5,
+            (true, true) => 3,
+            (false, _) => 1,
+        };
+        self.write()
+            .entry(key.to_string())
+            .or_insert_with(|| Item::new(today))
+            .review(quality, today);
+    }
+
+    fn serialise(&self) -> String {
+        self.read()
+            .iter()
+            .map(|(key, item)| {
+                format!(
+                    "{key}{sep}{}{sep}{}{sep}{}{sep}{}\n",
+                    item.ease,
+                    item.interval,
+                    item.repetitions,
+                    item.due.format(DATE_FORMAT),
+                    sep = FIELD_SEPARATOR
+                )
+            })
+            .collect()
+    }
+
+    /// On error, returns the number of the offending line.
+    fn parse(text: &str) -> Result<BTreeMap<String, Item>, usize> {
+        let mut items = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            if line.is_empty() {
+                continue;
+            }
+            let fields: Vec<_> = line.split(FIELD_SEPARATOR).collect();
+            let [key, ease, interval, repetitions, due] = fields.as_slice() else {
+                return Err(line_number);
+            };
+            let item = Item {
+                ease: ease.parse().map_err(|_| line_number)?,
+                interval: interval.parse().map_err(|_| line_number)?,
+                repetitions: repetitions.parse().map_err(|_| line_number)?,
+                due: NaiveDate::parse_from_str(due, DATE_FORMAT).map_err(|_| line_number)?,
+            };
+            items.insert((*key).to_string(), item);
+        }
+        Ok(items)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, BTreeMap<String, Item>> {
+        self.items.write().expect("Scheduler is blocked")
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, BTreeMap<String, Item>> {
+        self.items.read().expect("Scheduler is blocked")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("Test: invalid date")
+    }
+
+    #[test]
+    fn sm2_intervals() {
+        let today = date(2026, 1, 1);
+        let mut item = Item::new(today);
+
+        item.review(5, today);
+        assert_eq!(item.interval, 1);
+        assert_eq!(item.due, date(2026, 1, 2));
+        item.review(5, today);
+        assert_eq!(item.interval, 6);
+        item.review(5, today);
+        assert_eq!(item.interval, 16); // 6 * 2.7
+        assert_eq!(item.repetitions, 3);
+        assert!((item.ease - 2.8).abs() < 1e-9);
+
+        item.review(1, today);
+        assert_eq!(item.interval, 1);
+        assert_eq!(item.repetitions, 0);
+        assert!(item.ease < 2.8);
+    }
+
+    #[test]
+    fn ease_has_lower_bound() {
+        let today = date(2026, 1, 1);
+        let mut item = Item::new(today);
+        for _ in 0..20 {
+            item.review(0, today);
+        }
+        assert!((item.ease - MIN_EASE).abs() < 1e-9);
+    }
+
+    #[test]
+    fn prioritised_keys() {
+        let today = date(2026, 1, 10);
+        let scheduler = Scheduler::default();
+        scheduler.write().extend([
+            (
+                "due_later".to_string(),
+                Item {
+                    ease: 2.5,
+                    interval: 1,
+                    repetitions: 1,
+                    due: date(2026, 1, 9),
+                },
+            ),
+            (
+                "due_earlier".to_string(),
+                Item {
+                    ease: 2.5,
+                    interval: 1,
+                    repetitions: 1,
+                    due: date(2026, 1, 5),
+                },
+            ),
+            (
+                "weak".to_string(),
+                Item {
+                    ease: 1.5,
+                    interval: 1,
+                    repetitions: 0,
+                    due: date(2026, 1, 11),
+                },
+            ),
+            (
+                "weaker".to_string(),
+                Item {
+                    ease: 1.3,
+                    interval: 1,
+                    repetitions: 0,
+                    due: date(2026, 1, 11),
+                },
+            ),
+            (
+                "known".to_string(),
+                Item {
+                    ease: 2.7,
+                    interval: 6,
+                    repetitions: 3,
+                    due: date(2026, 1, 16),
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            scheduler.prioritised_keys(today),
+            vec!["due_earlier", "due_later", "weaker", "weak"]
+        );
+    }
+
+    #[test]
+    fn review_answers() {
+        let today = date(2026, 1, 1);
+        let scheduler = Scheduler::default();
+        scheduler.review("fast", true, Duration::from_secs(1), today);
+        scheduler.review("slow", true, Duration::from_secs(30), today);
+        scheduler.review("wrong", false, Duration::from_secs(1), today);
+
+        let items = scheduler.read();
+        assert!(items["fast"].ease > items["slow"].ease);
+        assert!(items["slow"].ease > items["wrong"].ease);
+        assert_eq!(items["wrong"].repetitions, 0);
+        assert_eq!(items["fast"].repetitions, 1);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = env::temp_dir().join(format!(
+            "practicestuff_repetition_test_{}",
+            std::process::id()
+        ));
+        let today = date(2026, 1, 1);
+        let scheduler = Scheduler::default();
+        scheduler.review("times_table:7*8", false, Duration::from_secs(3), today);
+        scheduler.review("powers:2^10", true, Duration::from_secs(3), today);
+        scheduler.save(&path).expect("Test: could not save");
+
+        let loaded = Scheduler::load(&path).expect("Test: could not load");
+        fs::remove_file(&path).expect("Test: could not clean up");
+        assert_eq!(*loaded.read(), *scheduler.read());
+    }
+
+    #[test]
+    fn corrupted_state() {
+        assert_eq!(Scheduler::parse("key\t2.5\t1\t1\n"), Err(1));
+        assert_eq!(
+            Scheduler::parse("key\t2.5\t1\t1\t2026-01-01\nkey\tx\t1\t1\t2026-01-01\n"),
+            Err(2)
+        );
+        assert!(Scheduler::parse("").expect("Test: empty state").is_empty());
+    }
+}
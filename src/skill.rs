@@ -1,32 +1,188 @@
+pub mod arithmetic;
+pub mod bases;
+pub mod calendar;
+pub mod computus;
+pub mod division;
 pub mod doomsday_algorithm;
+pub mod gcd;
 pub mod powers;
+pub mod primes;
+pub mod roman;
+pub mod squares;
 pub mod times_table;
 
+use std::cmp;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use chrono::NaiveDate;
+use rand::RngCore;
 
 use super::question::Question;
+use arithmetic::Arithmetic;
+use bases::Bases;
+use calendar::Calendar;
+use computus::Computus;
+use division::Division;
 use doomsday_algorithm::Doomsday;
+use gcd::Gcd;
 use powers::Powers;
+use primes::Primes;
+use roman::Roman;
+use squares::Squares;
 use times_table::TimesTable;
 
+pub const COMMANDS: [&str; 12] = [
+    powers::CMD,
+    times_table::CMD,
+    doomsday_algorithm::CMD,
+    arithmetic::CMD,
+    division::CMD,
+    squares::CMD,
+    calendar::CMD,
+    computus::CMD,
+    bases::CMD,
+    roman::CMD,
+    primes::CMD,
+    gcd::CMD,
+];
+
 pub trait Base {
     fn wants_to_print_help(&self) -> bool;
     fn get_help_text(&self) -> String;
-    fn generate_questions(&self, count: u32) -> Vec<Question>;
+    /// Generates `count` random questions. All randomness comes from `rng`, so that
+    /// a seeded `rng` always gives the same questions.
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question>;
+    /// Recreates the question identified by `key` (see `Question::key`).
+    /// Returns `None` if the key belongs to another skill or is out of the configured range.
+    fn question_from_key(&self, key: &str) -> Option<Question>;
+    /// Changes the range questions are generated from (adaptive mode).
+    /// Returns `true` if the range has changed.
+    fn adapt(&self, _adjustment: Adjustment) -> bool {
+        false
+    }
+    /// Describes the range questions are currently generated from, e.g. "exponents 1-8".
+    /// Returns `None` if the skill does not support adaptive mode.
+    fn current_range(&self) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Adjustment {
+    /// Narrow the range down to the initial one.
+    Start,
+    Harder,
+    Easier,
+}
+
+/// Difficulty level used in adaptive mode, from 0 (the narrowest range) to `max` (the full
+/// configured range). Until adaptive mode is started, the full range is used.
+#[derive(Debug)]
+struct Level {
+    current: AtomicU32,
+    max: u32,
+}
+
+impl Level {
+    fn new(max: u32) -> Self {
+        Self {
+            current: AtomicU32::new(max),
+            max,
+        }
+    }
+
+    fn get(&self) -> u32 {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    fn adjust(&self, adjustment: Adjustment) -> bool {
+        let current = self.get();
+        let new = match adjustment {
+            Adjustment::Start => 0,
+            Adjustment::Harder => cmp::min(current + 1, self.max),
+            Adjustment::Easier => current.saturating_sub(1),
+        };
+        self.current.store(new, Ordering::Relaxed);
+        new != current
+    }
 }
 
 pub trait Skill: Base + Debug + Sync + Send {}
 impl<T: Base + Debug + Sync + Send> Skill for T {}
 
+fn question_key(command: &str, id: &str) -> String {
+    format!("{command}:{id}")
+}
+
+fn question_id<'a>(command: &str, key: &'a str) -> Option<&'a str> {
+    key.strip_prefix(command)?.strip_prefix(':')
+}
+
+/// Checks the range of years of a calendar skill. Years of the Gregorian reform and before
+/// are rejected if `reform_limit` is given, it names what doesn't work for them (e.g.
+/// "Doomsday algorithm"). Without it (i.e. Julian dates are asked too), they are allowed.
+fn check_year_range(
+    lower_boundary: i32,
+    upper_boundary: i32,
+    reform_limit: Option<&str>,
+) -> Result<(), String> {
+    if lower_boundary > upper_boundary {
+        return Err("lower boundary must be less than or equal to upper boundary".to_string());
+    }
+
+    let reform = doomsday_algorithm::GREGORIAN_CALENDAR_INTRODUCTION;
+    if let Some(reform_limit) = reform_limit.filter(|_| lower_boundary <= reform) {
+        return Err(format!(
+            "year boundary too low; {reform_limit} does not work for dates on {reform} and before"
+        ));
+    }
+
+    if lower_boundary < 1 {
+        return Err("year boundary too low; years before 1 are not supported".to_string());
+    }
+
+    if NaiveDate::from_ymd_opt(upper_boundary, 12, 31).is_none() {
+        return Err("year boundaries cannot exceed 262142".to_string()); // Limitation of NaiveDate
+    }
+
+    Ok(())
+}
+
 pub fn build(command: &str, args: &[String]) -> Result<Box<dyn Skill>, String> {
     match command {
         powers::CMD => Ok(Box::new(Powers::build(args)?)),
         times_table::CMD => Ok(Box::new(TimesTable::build(args)?)),
         doomsday_algorithm::CMD => Ok(Box::new(Doomsday::build(args)?)),
+        arithmetic::CMD => Ok(Box::new(Arithmetic::build(args)?)),
+        division::CMD => Ok(Box::new(Division::build(args)?)),
+        squares::CMD => Ok(Box::new(Squares::build(args)?)),
+        calendar::CMD => Ok(Box::new(Calendar::build(args)?)),
+        computus::CMD => Ok(Box::new(Computus::build(args)?)),
+        bases::CMD => Ok(Box::new(Bases::build(args)?)),
+        roman::CMD => Ok(Box::new(Roman::build(args)?)),
+        primes::CMD => Ok(Box::new(Primes::build(args)?)),
+        gcd::CMD => Ok(Box::new(Gcd::build(args)?)),
         _ => unreachable!("all commands should be added here"),
     }
 }
 
+/// Converts `args` to the owned strings skills are built from.
+#[cfg(test)]
+fn to_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(ToString::to_string).collect()
+}
+
+/// Checks that `message` is an error message of `cmd` with `msg`, usage and help prompt.
+#[cfg(test)]
+fn assert_err_message(message: &str, cmd: &str, msg: &str) {
+    assert!(message.contains(crate::application::APP_NAME));
+    assert!(message.contains(cmd));
+    assert!(message.contains(msg));
+    assert!(message.contains("Usage"));
+    assert!(message.contains("for more information"));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,6 +195,41 @@ mod tests {
         build(command, &args).unwrap();
     }
 
+    #[test]
+    fn question_keys() {
+        let key = question_key(powers::CMD, "2^10");
+        assert_eq!(key, "powers:2^10");
+        assert_eq!(question_id(powers::CMD, &key), Some("2^10"));
+        assert_eq!(question_id(times_table::CMD, &key), None);
+        assert_eq!(question_id("power", &key), None);
+    }
+
+    #[test]
+    fn year_ranges() {
+        assert!(check_year_range(1583, 2000, Some("Doomsday algorithm")).is_ok());
+        assert!(check_year_range(1, 2000, None).is_ok());
+        let err = check_year_range(1582, 2000, Some("Doomsday algorithm")).unwrap_err();
+        assert!(err.contains("Doomsday algorithm does not work for dates on 1582 and before"));
+        assert!(check_year_range(0, 2000, None).is_err_and(|err| err.contains("before 1")));
+        assert!(check_year_range(2000, 1999, None).is_err_and(|err| err.contains("lower boundary")));
+        assert!(check_year_range(1, 262_143, None).is_err_and(|err| err.contains("262142")));
+    }
+
+    #[test]
+    fn difficulty_level() {
+        let level = Level::new(2);
+        assert_eq!(level.get(), 2);
+        assert!(!level.adjust(Adjustment::Harder));
+        assert!(level.adjust(Adjustment::Start));
+        assert_eq!(level.get(), 0);
+        assert!(!level.adjust(Adjustment::Easier));
+        assert!(level.adjust(Adjustment::Harder));
+        assert!(level.adjust(Adjustment::Harder));
+        assert_eq!(level.get(), 2);
+        assert!(level.adjust(Adjustment::Easier));
+        assert_eq!(level.get(), 1);
+    }
+
     #[test]
     fn build_powers() {
         let command = powers::CMD;
@@ -0,0 +1,707 @@
+use std::iter;
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use super::{question_id, question_key, Base};
+use crate::application::APP_NAME;
+use crate::args::prelude::*;
+use crate::question::{Category, Question};
+
+pub const CMD: &str = "arithmetic";
+
+const ARG_ID_HELP: &str = "help";
+const ARG_ID_OPERATION: &str = "operation";
+const ARG_ID_DIGITS: &str = "digits";
+const ARG_ID_DIGITS_2: &str = "digits_2";
+const ARG_ID_OPERANDS: &str = "operands";
+const ARG_ID_NO_CARRY: &str = "no_carry";
+const ARG_ID_NEGATIVE: &str = "negative";
+
+const OPERATION_ADD: &str = "add";
+const OPERATION_SUB: &str = "sub";
+const OPERATION_MIXED: &str = "mixed";
+
+const DEFAULT_DIGITS: u32 = 2;
+const MAX_DIGITS: u32 = 9;
+const DEFAULT_OPERANDS: u32 = 2;
+const MAX_OPERANDS: u32 = 9;
+
+const CATEGORY_OPERATION: &str = "operation";
+const CATEGORY_CARRY: &str = "carrying/borrowing";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+    Add,
+    Sub,
+    Mixed,
+}
+
+impl Operation {
+    fn from_string(value: &str) -> Operation {
+        match value {
+            OPERATION_ADD => Operation::Add,
+            OPERATION_SUB => Operation::Sub,
+            OPERATION_MIXED => Operation::Mixed,
+            _ => panic!("incorrect value for Operation"),
+        }
+    }
+
+    fn allows(self, operator: char) -> bool {
+        match self {
+            Operation::Add => operator == '+',
+            Operation::Sub => operator == '-',
+            Operation::Mixed => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Arithmetic {
+    arg_definitions: Vec<Arg>,
+    show_help: bool,
+
+    operation: Operation,
+    /// Number of digits of the first operand
+    digits: u32,
+    /// Number of digits of the other operands
+    digits_2: u32,
+    operands: u32,
+    no_carry: bool,
+    negative: bool,
+}
+
+impl Arithmetic {
+    pub fn build(args: &[String]) -> Result<Self, String> {
+        let arg_definitions = Self::build_arg_definitions();
+        let parsed_args = parser::parse_and_validate_arg_list(args, &arg_definitions)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        let show_help =
+            bool::set_value_from_arg_or_default(ARG_ID_HELP, &parsed_args, &arg_definitions);
+        let operation =
+            String::set_value_from_arg_or_default(ARG_ID_OPERATION, &parsed_args, &arg_definitions);
+        let operation = Operation::from_string(&operation);
+        let digits =
+            u32::set_value_from_arg_or_default(ARG_ID_DIGITS, &parsed_args, &arg_definitions);
+        let digits_2 =
+            u32::set_value_from_arg_or_default(ARG_ID_DIGITS_2, &parsed_args, &arg_definitions);
+        let digits_2 = if digits_2 == 0 { digits } else { digits_2 };
+        let operands =
+            u32::set_value_from_arg_or_default(ARG_ID_OPERANDS, &parsed_args, &arg_definitions);
+        let no_carry =
+            bool::set_value_from_arg_or_default(ARG_ID_NO_CARRY, &parsed_args, &arg_definitions);
+        let negative =
+            bool::set_value_from_arg_or_default(ARG_ID_NEGATIVE, &parsed_args, &arg_definitions);
+
+        let arithmetic = Self {
+            arg_definitions,
+            show_help,
+            operation,
+            digits,
+            digits_2,
+            operands,
+            no_carry,
+            negative,
+        };
+        arithmetic
+            .validate()
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+        Ok(arithmetic)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_DIGITS).contains(&self.digits) || !(1..=MAX_DIGITS).contains(&self.digits_2) {
+            return Err(format!(
+                "number of digits must be between 1 and {MAX_DIGITS}"
+            ));
+        }
+        if !(2..=MAX_OPERANDS).contains(&self.operands) {
+            return Err(format!(
+                "number of operands must be between 2 and {MAX_OPERANDS}"
+            ));
+        }
+        if self.no_carry && self.negative {
+            return Err("negative results cannot be combined with no carrying".to_string());
+        }
+        if self.operation != Operation::Add && !self.negative {
+            if self.no_carry && self.digits_2 > self.digits {
+                return Err(
+                    "subtraction without borrowing needs the first operand to be the longest"
+                        .to_string(),
+                );
+            }
+            let (_, max_first) = Self::operand_range(self.digits);
+            let (min_other, _) = Self::operand_range(self.digits_2);
+            if u64::from(self.operands - 1) * min_other > max_first {
+                return Err(
+                    "subtraction would always give negative results, use --negative".to_string(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn build_arg_definitions() -> Vec<Arg> {
+        vec![
+            Arg::builder()
+                .id(ARG_ID_HELP)
+                .short_name('h')
+                .long_name("help")
+                .description(vec!["Display help for arithmetic command.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(true)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_OPERATION)
+                .short_name('o')
+                .long_name("operation")
+                .description(vec![
+                    "Set the operation: add (addition), sub".to_string(),
+                    "(subtraction) or mixed (either of them,".to_string(),
+                    "chosen randomly) (default: add).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    OPERATION_ADD.to_string(),
+                    OPERATION_SUB.to_string(),
+                    OPERATION_MIXED.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(OPERATION_ADD.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_DIGITS)
+                .short_name('d')
+                .long_name("digits")
+                .description(vec![
+                    "Set the number of digits".to_string(),
+                    format!("of the first operand (default: {DEFAULT_DIGITS})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_DIGITS))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_DIGITS_2)
+                .long_name("digits-2")
+                .description(vec![
+                    "Set the number of digits of the other".to_string(),
+                    "operands (default: same as the first).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(0))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_OPERANDS)
+                .short_name('c')
+                .long_name("operands")
+                .description(vec![
+                    "Set the number of operands, e.g. 3 for".to_string(),
+                    format!("12 + 34 + 56 (default: {DEFAULT_OPERANDS})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_OPERANDS))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_NO_CARRY)
+                .long_name("no-carry")
+                .description(vec![
+                    "Ask only questions that need no carrying".to_string(),
+                    "(addition) or borrowing (subtraction).".to_string(),
+                ])
+                .kind(ArgKind::Flag)
+                .stop_parsing(false)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_NEGATIVE)
+                .long_name("negative")
+                .description(vec!["Allow negative results of subtraction.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(false)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+        ]
+    }
+
+    fn usage() -> String {
+        format!("Usage: {APP_NAME} [option]... arithmetic [arithmetic_option]...")
+    }
+
+    fn help_prompt() -> String {
+        format!("Try '{APP_NAME} arithmetic --help' for more information.")
+    }
+
+    fn additional_info() -> String {
+        format!("Practise mental addition and subtraction.\nBy default, two {DEFAULT_DIGITS}-digit numbers are added. The number of digits can be set\nseparately for the first operand and the other ones.")
+    }
+
+    fn build_err_message(msg: Option<String>) -> String {
+        if let Some(msg) = msg {
+            format!(
+                "{}: {}: {}\n{}\n{}",
+                APP_NAME,
+                CMD,
+                msg,
+                Self::usage(),
+                Self::help_prompt()
+            )
+        } else {
+            format!("{}\n{}", Self::usage(), Self::help_prompt())
+        }
+    }
+
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let operator = match self.operation {
+            Operation::Add => '+',
+            Operation::Sub => '-',
+            Operation::Mixed => *['+', '-'].choose(rng).expect("not empty"),
+        };
+        let operands = if operator == '+' && self.no_carry {
+            self.operands_without_carry(rng)
+        } else if operator == '+' || self.negative {
+            self.random_operands(rng)
+        } else if self.no_carry {
+            self.operands_without_borrow(rng)
+        } else {
+            self.operands_with_non_negative_result(rng)
+        };
+        Self::question(operator, &operands)
+    }
+
+    /// Digit counts of all operands
+    fn digit_counts(&self) -> Vec<u32> {
+        iter::once(self.digits)
+            .chain(iter::repeat_n(self.digits_2, self.operands as usize - 1))
+            .collect()
+    }
+
+    /// The smallest and the largest number with `digits` digits
+    fn operand_range(digits: u32) -> (u64, u64) {
+        (10_u64.pow(digits - 1), 10_u64.pow(digits) - 1)
+    }
+
+    fn random_operands(&self, rng: &mut dyn RngCore) -> Vec<u64> {
+        self.digit_counts()
+            .into_iter()
+            .map(|digits| {
+                let (min, max) = Self::operand_range(digits);
+                rng.gen_range(min..=max)
+            })
+            .collect()
+    }
+
+    /// Subtrahends are chosen one by one, so that their sum doesn't exceed the first operand
+    fn operands_with_non_negative_result(&self, rng: &mut dyn RngCore) -> Vec<u64> {
+        let (min_first, max_first) = Self::operand_range(self.digits);
+        let (min_other, max_other) = Self::operand_range(self.digits_2);
+        let count = u64::from(self.operands - 1);
+
+        let mut subtrahends = vec![];
+        let mut sum = 0;
+        for i in 0..count {
+            let reserved = (count - i - 1) * min_other;
+            let max = max_other.min(max_first - sum - reserved);
+            let subtrahend = rng.gen_range(min_other..=max);
+            sum += subtrahend;
+            subtrahends.push(subtrahend);
+        }
+        subtrahends.shuffle(rng);
+
+        let first = rng.gen_range(min_first.max(sum)..=max_first);
+        iter::once(first).chain(subtrahends).collect()
+    }
+
+    /// Operands are built column by column, so that no column adds up to more than 9
+    fn operands_without_carry(&self, rng: &mut dyn RngCore) -> Vec<u64> {
+        let digit_counts = self.digit_counts();
+        let mut operands = vec![0; digit_counts.len()];
+        for column in 0..self.digits.max(self.digits_2) {
+            let (indices, mins) = Self::column(&digit_counts, column);
+            let digits = Self::column_digits(&mins, 9, rng);
+            for (i, digit) in indices.into_iter().zip(digits) {
+                operands[i] += digit * 10_u64.pow(column);
+            }
+        }
+        operands
+    }
+
+    /// Digits of the first operand are at least the sum of the subtrahends' digits in each column
+    fn operands_without_borrow(&self, rng: &mut dyn RngCore) -> Vec<u64> {
+        let digit_counts = self.digit_counts();
+        let mut operands = vec![0; digit_counts.len()];
+        for column in 0..self.digits {
+            let (indices, mins) = Self::column(&digit_counts[1..], column);
+            let digits = Self::column_digits(&mins, 9, rng);
+            let sum: u64 = digits.iter().sum();
+            for (i, digit) in indices.into_iter().zip(digits) {
+                operands[i + 1] += digit * 10_u64.pow(column);
+            }
+            let min_first = if column == self.digits - 1 { 1 } else { 0 };
+            operands[0] += rng.gen_range(sum.max(min_first)..=9) * 10_u64.pow(column);
+        }
+        operands
+    }
+
+    /// Indices of operands with a digit in `column` and minimum values of these digits
+    /// (leading digits can't be 0)
+    fn column(digit_counts: &[u32], column: u32) -> (Vec<usize>, Vec<u64>) {
+        digit_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &digits)| digits > column)
+            .map(|(i, &digits)| (i, u64::from(digits - 1 == column)))
+            .unzip()
+    }
+
+    /// Random digits (at least `mins`) that add up to at most `max_sum`
+    fn column_digits(mins: &[u64], max_sum: u64, rng: &mut dyn RngCore) -> Vec<u64> {
+        let mut order: Vec<_> = (0..mins.len()).collect();
+        order.shuffle(rng);
+        let mut digits = vec![0; mins.len()];
+        let mut reserved: u64 = mins.iter().sum();
+        let mut sum = 0;
+        for i in order {
+            reserved -= mins[i];
+            digits[i] = rng.gen_range(mins[i]..=max_sum - sum - reserved);
+            sum += digits[i];
+        }
+        digits
+    }
+
+    fn has_carry(operands: &[u64]) -> bool {
+        let mut operands = operands.to_vec();
+        while operands.iter().any(|&operand| operand > 0) {
+            if operands.iter().map(|operand| operand % 10).sum::<u64>() > 9 {
+                return true;
+            }
+            operands.iter_mut().for_each(|operand| *operand /= 10);
+        }
+        false
+    }
+
+    fn has_borrow(operands: &[u64]) -> bool {
+        let mut operands = operands.to_vec();
+        while operands.iter().any(|&operand| operand > 0) {
+            let subtracted: u64 = operands[1..].iter().map(|operand| operand % 10).sum();
+            if subtracted > operands[0] % 10 {
+                return true;
+            }
+            operands.iter_mut().for_each(|operand| *operand /= 10);
+        }
+        false
+    }
+
+    fn result(operator: char, operands: &[u64]) -> i64 {
+        let operands = operands
+            .iter()
+            .map(|&operand| i64::try_from(operand).expect("operands have at most 9 digits"));
+        if operator == '+' {
+            operands.sum()
+        } else {
+            let mut operands = operands;
+            let first = operands.next().expect("at least 2 operands");
+            first - operands.sum::<i64>()
+        }
+    }
+
+    fn question(operator: char, operands: &[u64]) -> Question {
+        let result = Self::result(operator, operands);
+        let id = operands
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(&operator.to_string());
+        let prompt = operands
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(&format!(" {operator} "));
+        let (operation, carry) = if operator == '+' {
+            ("addition", Self::has_carry(operands))
+        } else {
+            ("subtraction", Self::has_borrow(operands))
+        };
+        Question::builder()
+            .question(&prompt)
+            .answer(&result.to_string())
+            .key(&question_key(CMD, &id))
+            .categories(&[
+                Category::new(CATEGORY_OPERATION, operation, 0),
+                Category::new(CATEGORY_CARRY, if carry { "yes" } else { "no" }, 0),
+            ])
+            .build()
+    }
+
+    /// Checks whether the question could have been generated with the current options
+    fn fits(&self, operator: char, operands: &[u64]) -> bool {
+        let digit_counts = self.digit_counts();
+        let digits_match = operands.len() == digit_counts.len()
+            && operands.iter().zip(digit_counts).all(|(&operand, digits)| {
+                let (min, max) = Self::operand_range(digits);
+                (min..=max).contains(&operand)
+            });
+        if !digits_match || !self.operation.allows(operator) {
+            return false;
+        }
+        match operator {
+            '+' => !(self.no_carry && Self::has_carry(operands)),
+            _ => {
+                (self.negative || Self::result(operator, operands) >= 0)
+                    && !(self.no_carry && Self::has_borrow(operands))
+            }
+        }
+    }
+}
+
+impl Base for Arithmetic {
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
+            .take(count as usize)
+            .collect()
+    }
+
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let id = question_id(CMD, key)?;
+        let operator = if id.contains('+') { '+' } else { '-' };
+        let operands = id
+            .split(operator)
+            .map(|operand| operand.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        if self.fits(operator, &operands) {
+            Some(Self::question(operator, &operands))
+        } else {
+            None
+        }
+    }
+
+    fn wants_to_print_help(&self) -> bool {
+        self.show_help
+    }
+
+    fn get_help_text(&self) -> String {
+        let definitions = &self.arg_definitions;
+        let options = help::Options::new("Arithmetic options", definitions);
+        help::build(
+            &Self::usage(),
+            Some(&Self::additional_info()),
+            &options,
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::{assert_err_message, to_args};
+
+    fn operands(question: &Question) -> Vec<u64> {
+        question
+            .prompt()
+            .split(' ')
+            .filter_map(|part| part.parse().ok())
+            .collect()
+    }
+
+    #[test]
+    fn build_arithmetic_defaults() {
+        let arithmetic =
+            Arithmetic::build(&to_args(&[])).expect("Should build correctly with no args");
+        assert!(!arithmetic.show_help);
+        assert_eq!(arithmetic.operation, Operation::Add);
+        assert_eq!(arithmetic.digits, 2);
+        assert_eq!(arithmetic.digits_2, 2);
+        assert_eq!(arithmetic.operands, 2);
+        assert!(!arithmetic.no_carry);
+        assert!(!arithmetic.negative);
+    }
+
+    #[test]
+    fn build_arithmetic_with_args() {
+        let arithmetic = Arithmetic::build(&to_args(&[
+            "-o",
+            "mixed",
+            "-d",
+            "3",
+            "--digits-2=1",
+            "-c",
+            "4",
+        ]))
+        .expect("Should build correctly with args");
+        assert_eq!(arithmetic.operation, Operation::Mixed);
+        assert_eq!(arithmetic.digit_counts(), vec![3, 1, 1, 1]);
+    }
+
+    #[test]
+    fn build_arithmetic_incorrect_args() {
+        for (args, err) in [
+            (vec!["-d", "0"], "number of digits"),
+            (vec!["--digits-2=10"], "number of digits"),
+            (vec!["-c", "1"], "number of operands"),
+            (vec!["-c", "10"], "number of operands"),
+            (vec!["--no-carry", "--negative"], "cannot be combined"),
+            (
+                vec!["-o", "sub", "-d", "1", "--digits-2=2"],
+                "use --negative",
+            ),
+            (vec!["-o", "mixed", "--digits-2=3"], "use --negative"),
+            (
+                vec!["-o", "mixed", "--no-carry", "--digits-2=3"],
+                "first operand to be the longest",
+            ),
+            (vec!["-o", "mul"], "invalid option argument"),
+        ] {
+            assert!(
+                Arithmetic::build(&to_args(&args)).is_err_and(|message| message.contains(err)),
+                "{args:?}"
+            );
+        }
+        assert!(Arithmetic::build(&to_args(&[
+            "-o",
+            "sub",
+            "-d",
+            "1",
+            "--digits-2=2",
+            "--negative"
+        ]))
+        .is_ok());
+    }
+
+    #[test]
+    fn error_message() {
+        let message = Arithmetic::build_err_message(Some(
+            "something extraordinarily wrong happened".to_string(),
+        ));
+        assert_err_message(&message, CMD, "something extraordinarily wrong happened");
+    }
+
+    #[test]
+    fn addition() {
+        let arithmetic = Arithmetic::build(&to_args(&["-d", "3", "--digits-2=1", "-c", "3"]))
+            .expect("Should build");
+        for question in arithmetic.generate_questions(50, &mut rand::thread_rng()) {
+            let operands = operands(&question);
+            assert_eq!(operands.len(), 3);
+            assert!((100..=999).contains(&operands[0]));
+            assert!(operands[1..]
+                .iter()
+                .all(|operand| (1..=9).contains(operand)));
+            assert_eq!(
+                question.correct_answer(),
+                &operands.iter().sum::<u64>().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn addition_without_carry() {
+        let arithmetic = Arithmetic::build(&to_args(&["-d", "3", "-c", "3", "--no-carry"]))
+            .expect("Should build");
+        for question in arithmetic.generate_questions(50, &mut rand::thread_rng()) {
+            let operands = operands(&question);
+            assert!(operands.iter().all(|operand| (100..=999).contains(operand)));
+            assert!(!Arithmetic::has_carry(&operands), "{}", question.prompt());
+            assert_eq!(
+                question.categories()[1],
+                Category::new(CATEGORY_CARRY, "no", 0)
+            );
+        }
+    }
+
+    #[test]
+    fn subtraction() {
+        let arithmetic =
+            Arithmetic::build(&to_args(&["-o", "sub", "-c", "3"])).expect("Should build");
+        for question in arithmetic.generate_questions(50, &mut rand::thread_rng()) {
+            let operands = operands(&question);
+            assert!(operands.iter().all(|operand| (10..=99).contains(operand)));
+            let result: i64 = question.correct_answer().parse().unwrap();
+            assert!(result >= 0, "{}", question.prompt());
+            assert_eq!(
+                question.categories()[0],
+                Category::new(CATEGORY_OPERATION, "subtraction", 0)
+            );
+        }
+
+        let arithmetic = Arithmetic::build(&to_args(&[
+            "-o",
+            "sub",
+            "-d",
+            "1",
+            "--digits-2=2",
+            "--negative",
+        ]))
+        .expect("Should build");
+        let question = arithmetic.generate_question(&mut rand::thread_rng());
+        assert!(question.correct_answer().starts_with('-'));
+    }
+
+    #[test]
+    fn subtraction_without_borrow() {
+        let arithmetic = Arithmetic::build(&to_args(&[
+            "-o",
+            "sub",
+            "-d",
+            "4",
+            "--digits-2=2",
+            "-c",
+            "3",
+            "--no-carry",
+        ]))
+        .expect("Should build");
+        for question in arithmetic.generate_questions(50, &mut rand::thread_rng()) {
+            let operands = operands(&question);
+            assert!((1000..=9999).contains(&operands[0]));
+            assert!(!Arithmetic::has_borrow(&operands), "{}", question.prompt());
+        }
+    }
+
+    #[test]
+    fn carry_and_borrow() {
+        assert!(!Arithmetic::has_carry(&[12, 34, 53]));
+        assert!(Arithmetic::has_carry(&[15, 5]));
+        assert!(Arithmetic::has_carry(&[50, 60]));
+        assert!(!Arithmetic::has_borrow(&[99, 45, 54]));
+        assert!(Arithmetic::has_borrow(&[30, 1]));
+        assert!(Arithmetic::has_borrow(&[130, 40]));
+    }
+
+    #[test]
+    fn question_from_key() {
+        let arithmetic = Arithmetic::build(&to_args(&["-o", "mixed"])).expect("Should build");
+        let question = arithmetic
+            .question_from_key("arithmetic:45-17")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "45 - 17");
+        assert_eq!(question.correct_answer(), "28");
+        assert_eq!(question.key(), Some(&"arithmetic:45-17".to_string()));
+        assert!(arithmetic.question_from_key("arithmetic:45+17").is_some());
+
+        assert!(arithmetic.question_from_key("arithmetic:17-45").is_none());
+        assert!(arithmetic.question_from_key("arithmetic:5+17").is_none());
+        assert!(arithmetic
+            .question_from_key("arithmetic:15+17+19")
+            .is_none());
+        assert!(arithmetic.question_from_key("arithmetic:15*17").is_none());
+        assert!(arithmetic.question_from_key("powers:2^3").is_none());
+
+        let arithmetic = Arithmetic::build(&to_args(&["--no-carry"])).expect("Should build");
+        assert!(arithmetic.question_from_key("arithmetic:15+14").is_some());
+        assert!(arithmetic.question_from_key("arithmetic:15+17").is_none());
+        assert!(arithmetic.question_from_key("arithmetic:45-17").is_none());
+    }
+
+    #[test]
+    fn help_text() {
+        let arithmetic = Arithmetic::build(&to_args(&["-h"])).expect("Should build correctly");
+        assert!(arithmetic.wants_to_print_help());
+        let help_text = arithmetic.get_help_text();
+        assert!(help_text.contains("Arithmetic options"));
+        assert!(help_text.contains("-o, --operation"));
+        assert!(help_text.contains("--no-carry"));
+    }
+}
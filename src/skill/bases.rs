@@ -0,0 +1,484 @@
+use std::iter;
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use super::{question_id, question_key, Adjustment, Base, Level};
+use crate::application::APP_NAME;
+use crate::args::prelude::*;
+use crate::question::{Category, Matcher, Question};
+
+pub const CMD: &str = "bases";
+
+const ARG_ID_HELP: &str = "help";
+const ARG_ID_FROM: &str = "from";
+const ARG_ID_TO: &str = "to";
+const ARG_ID_BIT_WIDTH: &str = "bit_width";
+
+const RADIX_BINARY: &str = "binary";
+const RADIX_OCTAL: &str = "octal";
+const RADIX_DECIMAL: &str = "decimal";
+const RADIX_HEX: &str = "hex";
+const RADIX_MIXED: &str = "mixed";
+
+const DEFAULT_BIT_WIDTH: u32 = 8;
+const MAX_BIT_WIDTH: u32 = 64;
+const CATEGORY_CONVERSION: &str = "conversion";
+
+/// Bit width of the initial range of adaptive mode
+const ADAPTIVE_INITIAL_BIT_WIDTH: u32 = 4;
+
+/// Base numbers are written in
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Radix {
+    Binary,
+    Octal,
+    Decimal,
+    Hex,
+}
+
+impl Radix {
+    const ALL: [Radix; 4] = [Radix::Binary, Radix::Octal, Radix::Decimal, Radix::Hex];
+
+    /// Returns `None` for mixed, i.e. any of them
+    fn from_string(value: &str) -> Option<Radix> {
+        match value {
+            RADIX_BINARY => Some(Radix::Binary),
+            RADIX_OCTAL => Some(Radix::Octal),
+            RADIX_DECIMAL => Some(Radix::Decimal),
+            RADIX_HEX => Some(Radix::Hex),
+            RADIX_MIXED => None,
+            _ => panic!("incorrect value for Radix"),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Radix::Binary => RADIX_BINARY,
+            Radix::Octal => RADIX_OCTAL,
+            Radix::Decimal => RADIX_DECIMAL,
+            Radix::Hex => RADIX_HEX,
+        }
+    }
+
+    fn radix(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Hex => 16,
+        }
+    }
+
+    /// Writes `value` the way it's usually written in code, e.g. "0b1011_0110", "0o266",
+    /// "182" or "0xB6". Binary and hex digits are grouped by 4.
+    fn format(self, value: u64) -> String {
+        let (prefix, digits) = match self {
+            Radix::Binary => ("0b", format!("{value:b}")),
+            Radix::Octal => ("0o", format!("{value:o}")),
+            Radix::Decimal => return value.to_string(),
+            Radix::Hex => ("0x", format!("{value:X}")),
+        };
+        let groups: Vec<String> = digits
+            .as_bytes()
+            .rchunks(4)
+            .rev()
+            .map(|group| String::from_utf8_lossy(group).to_string())
+            .collect();
+        let separator = if self == Radix::Octal { "" } else { "_" };
+        format!("{prefix}{}", groups.join(separator))
+    }
+}
+
+#[derive(Debug)]
+pub struct Bases {
+    arg_definitions: Vec<Arg>,
+    show_help: bool,
+
+    /// `None` stands for any base
+    from: Option<Radix>,
+    /// `None` stands for any base
+    to: Option<Radix>,
+    bit_width: u32,
+
+    level: Level,
+}
+
+impl Bases {
+    pub fn build(args: &[String]) -> Result<Self, String> {
+        let arg_definitions = Self::build_arg_definitions();
+        let parsed_args = parser::parse_and_validate_arg_list(args, &arg_definitions)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        let show_help =
+            bool::set_value_from_arg_or_default(ARG_ID_HELP, &parsed_args, &arg_definitions);
+        let from =
+            String::set_value_from_arg_or_default(ARG_ID_FROM, &parsed_args, &arg_definitions);
+        let to = String::set_value_from_arg_or_default(ARG_ID_TO, &parsed_args, &arg_definitions);
+        let bit_width =
+            u32::set_value_from_arg_or_default(ARG_ID_BIT_WIDTH, &parsed_args, &arg_definitions);
+
+        let from = Radix::from_string(&from);
+        let to = Radix::from_string(&to);
+        if from.is_some() && from == to {
+            return Err(Self::build_err_message(Some(
+                "source and target bases must be different".to_string(),
+            )));
+        }
+
+        if !(1..=MAX_BIT_WIDTH).contains(&bit_width) {
+            return Err(Self::build_err_message(Some(format!(
+                "bit width must be between 1 and {MAX_BIT_WIDTH}"
+            ))));
+        }
+
+        let level = Level::new(bit_width.saturating_sub(ADAPTIVE_INITIAL_BIT_WIDTH));
+
+        Ok(Self {
+            arg_definitions,
+            show_help,
+            from,
+            to,
+            bit_width,
+            level,
+        })
+    }
+
+    fn build_arg_definitions() -> Vec<Arg> {
+        let radixes = || {
+            ValueKind::OneOfStr(vec![
+                RADIX_BINARY.to_string(),
+                RADIX_OCTAL.to_string(),
+                RADIX_DECIMAL.to_string(),
+                RADIX_HEX.to_string(),
+                RADIX_MIXED.to_string(),
+            ])
+        };
+        vec![
+            Arg::builder()
+                .id(ARG_ID_HELP)
+                .short_name('h')
+                .long_name("help")
+                .description(vec!["Display help for bases command.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(true)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_FROM)
+                .short_name('f')
+                .long_name("from")
+                .description(vec![
+                    "Set the base numbers are given in: binary,".to_string(),
+                    "octal, decimal, hex or mixed (any of them)".to_string(),
+                    "(default: mixed).".to_string(),
+                ])
+                .kind(ArgKind::Value(radixes()))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(RADIX_MIXED.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_TO)
+                .short_name('t')
+                .long_name("to")
+                .description(vec![
+                    "Set the base numbers are converted to: binary,".to_string(),
+                    "octal, decimal, hex or mixed (any of them".to_string(),
+                    "other than the source one) (default: mixed).".to_string(),
+                ])
+                .kind(ArgKind::Value(radixes()))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(RADIX_MIXED.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_BIT_WIDTH)
+                .short_name('w')
+                .long_name("bit-width")
+                .description(vec![format!(
+                    "Set the maximum number of bits (default: {DEFAULT_BIT_WIDTH})."
+                )])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_BIT_WIDTH))
+                .build(),
+        ]
+    }
+
+    fn usage() -> String {
+        format!("Usage: {APP_NAME} [option]... bases [bases_option]...")
+    }
+
+    fn help_prompt() -> String {
+        format!("Try '{APP_NAME} bases --help' for more information.")
+    }
+
+    fn additional_info() -> String {
+        let mut text = String::new();
+        text.push_str("Practise converting numbers between binary, octal, decimal and hex,\n");
+        text.push_str("e.g. 'What is 0x3F in decimal?'. Numbers are unsigned and fit in the bit\n");
+        text.push_str(&format!(
+            "width (1-{MAX_BIT_WIDTH}). Answers can have a base prefix (0b, 0o or 0x), '_'\n"
+        ));
+        text.push_str("separators and leading zeros, and hex digits can be in any case.");
+        text
+    }
+
+    fn build_err_message(msg: Option<String>) -> String {
+        if let Some(msg) = msg {
+            format!(
+                "{}: {}: {}\n{}\n{}",
+                APP_NAME,
+                CMD,
+                msg,
+                Self::usage(),
+                Self::help_prompt()
+            )
+        } else {
+            format!("{}\n{}", Self::usage(), Self::help_prompt())
+        }
+    }
+
+    /// Bit width at the current difficulty level
+    fn adaptive_bit_width(&self) -> u32 {
+        self.bit_width - (self.level.max - self.level.get())
+    }
+
+    fn max_value(bit_width: u32) -> u64 {
+        u64::MAX >> (u64::BITS - bit_width)
+    }
+
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let value = rng.gen_range(0..=Self::max_value(self.adaptive_bit_width()));
+        let from = self.from.unwrap_or_else(|| {
+            let radixes: Vec<_> = Radix::ALL
+                .into_iter()
+                .filter(|&radix| Some(radix) != self.to)
+                .collect();
+            *radixes.choose(rng).expect("not empty")
+        });
+        let to = self.to.unwrap_or_else(|| {
+            let radixes: Vec<_> = Radix::ALL
+                .into_iter()
+                .filter(|&radix| radix != from)
+                .collect();
+            *radixes.choose(rng).expect("not empty")
+        });
+        Self::question(value, from, to)
+    }
+
+    fn question(value: u64, from: Radix, to: Radix) -> Question {
+        let order = |radix| {
+            Radix::ALL
+                .iter()
+                .position(|&other| other == radix)
+                .expect("all radixes are listed") as i64
+        };
+        Question::builder()
+            .question(&format!("What is {} in {}?", from.format(value), to.name()))
+            .answer(&to.format(value))
+            .allow_any_case(true)
+            .matcher(Matcher::Radix(to.radix()))
+            .key(&question_key(
+                CMD,
+                &format!("{}-{}-{value}", from.name(), to.name()),
+            ))
+            .categories(&[Category::new(
+                CATEGORY_CONVERSION,
+                &format!("{} to {}", from.name(), to.name()),
+                order(from) * 4 + order(to),
+            )])
+            .build()
+    }
+}
+
+impl Base for Bases {
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
+            .take(count as usize)
+            .collect()
+    }
+
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let mut parts = question_id(CMD, key)?.splitn(3, '-');
+        let mut radix = || {
+            let name = parts.next()?;
+            Radix::ALL.into_iter().find(|radix| radix.name() == name)
+        };
+        let (from, to) = (radix()?, radix()?);
+        let value = parts.next()?.parse::<u64>().ok()?;
+        let allows = |configured: Option<Radix>, radix| configured.is_none_or(|c| c == radix);
+        (from != to
+            && allows(self.from, from)
+            && allows(self.to, to)
+            && value <= Self::max_value(self.bit_width))
+        .then(|| Self::question(value, from, to))
+    }
+
+    fn adapt(&self, adjustment: Adjustment) -> bool {
+        self.level.adjust(adjustment)
+    }
+
+    fn current_range(&self) -> Option<String> {
+        Some(format!("up to {} bits", self.adaptive_bit_width()))
+    }
+
+    fn wants_to_print_help(&self) -> bool {
+        self.show_help
+    }
+
+    fn get_help_text(&self) -> String {
+        let definitions = &self.arg_definitions;
+        let options = help::Options::new("Bases options", definitions);
+        help::build(
+            &Self::usage(),
+            Some(&Self::additional_info()),
+            &options,
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::{assert_err_message, to_args};
+
+    #[test]
+    fn build_bases_defaults() {
+        let bases = Bases::build(&to_args(&[])).expect("Should build correctly with no args");
+        assert!(!bases.show_help);
+        assert_eq!(bases.from, None);
+        assert_eq!(bases.to, None);
+        assert_eq!(bases.bit_width, 8);
+
+        let bases = Bases::build(&to_args(&["-f", "hex", "--to=binary", "-w", "64"]))
+            .expect("Should build");
+        assert_eq!(bases.from, Some(Radix::Hex));
+        assert_eq!(bases.to, Some(Radix::Binary));
+        assert_eq!(bases.bit_width, 64);
+    }
+
+    #[test]
+    fn build_bases_incorrect_args() {
+        let err = Bases::build(&to_args(&["-f", "hex", "-t", "hex"])).unwrap_err();
+        assert!(err.contains("source and target bases must be different"));
+        let err = Bases::build(&to_args(&["-w", "0"])).unwrap_err();
+        assert!(err.contains("bit width must be between 1 and 64"));
+        let err = Bases::build(&to_args(&["-w", "65"])).unwrap_err();
+        assert!(err.contains("bit width must be between 1 and 64"));
+        let err = Bases::build(&to_args(&["--from=ternary"])).unwrap_err();
+        assert!(err.contains("invalid option argument"));
+    }
+
+    #[test]
+    fn error_message() {
+        let message = Bases::build_err_message(Some("something wrong".to_string()));
+        assert_err_message(&message, CMD, "something wrong");
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(Radix::Binary.format(182), "0b1011_0110");
+        assert_eq!(Radix::Binary.format(5), "0b101");
+        assert_eq!(Radix::Binary.format(0), "0b0");
+        assert_eq!(Radix::Octal.format(182), "0o266");
+        assert_eq!(Radix::Decimal.format(182), "182");
+        assert_eq!(Radix::Hex.format(63), "0x3F");
+        assert_eq!(Radix::Hex.format(0xDEAD_BEEF), "0xDEAD_BEEF");
+        assert_eq!(Radix::Hex.format(0x1_0000), "0x1_0000");
+    }
+
+    #[test]
+    fn questions() {
+        let question = Bases::question(63, Radix::Hex, Radix::Decimal);
+        assert_eq!(question.prompt(), "What is 0x3F in decimal?");
+        assert_eq!(question.correct_answer(), "63");
+        assert_eq!(question.key(), Some(&"bases:hex-decimal-63".to_string()));
+        assert_eq!(
+            question.categories(),
+            [Category::new("conversion", "hex to decimal", 14)]
+        );
+
+        let question = Bases::question(182, Radix::Binary, Radix::Hex);
+        assert_eq!(question.prompt(), "What is 0b1011_0110 in hex?");
+        for answer in ["0xB6", "b6", "0x_b6", "0X00B6"] {
+            assert!(question.is_answer_correct(answer), "{answer}");
+        }
+        assert!(!question.is_answer_correct("182"));
+
+        let question = Bases::question(200, Radix::Decimal, Radix::Binary);
+        assert_eq!(question.prompt(), "What is 200 in binary?");
+        assert_eq!(question.correct_answer(), "0b1100_1000");
+        assert!(question.is_answer_correct("11001000"));
+        assert!(question.is_answer_correct("1100_1000"));
+    }
+
+    #[test]
+    fn generated_questions() {
+        let bases =
+            Bases::build(&to_args(&["-t", "decimal", "-w", "6"])).expect("Should build correctly");
+        for question in bases.generate_questions(50, &mut rand::thread_rng()) {
+            assert!(question.prompt().ends_with(" in decimal?"));
+            assert!(question.correct_answer().parse::<u64>().unwrap() < 64);
+        }
+
+        let bases = Bases::build(&to_args(&["-w", "64"])).expect("Should build correctly");
+        for question in bases.generate_questions(50, &mut rand::thread_rng()) {
+            let category = &question.categories()[0];
+            let (from, to) = category.value().split_once(" to ").unwrap();
+            assert_ne!(from, to);
+            assert!(bases.question_from_key(question.key().unwrap()).is_some());
+        }
+    }
+
+    #[test]
+    fn question_from_key() {
+        let bases = Bases::build(&to_args(&["-f", "hex"])).expect("Should build correctly");
+        assert!(bases.question_from_key("bases:hex-decimal-255").is_some());
+        assert!(bases.question_from_key("bases:hex-binary-0").is_some());
+        assert!(bases.question_from_key("bases:hex-decimal-256").is_none());
+        assert!(bases.question_from_key("bases:decimal-hex-63").is_none());
+        assert!(bases.question_from_key("bases:hex-hex-63").is_none());
+        assert!(bases.question_from_key("bases:hex-decimal-x").is_none());
+        assert!(bases.question_from_key("powers:2^8").is_none());
+    }
+
+    #[test]
+    fn adaptive_range() {
+        let bases = Bases::build(&to_args(&["-w", "16"])).expect("Should build correctly");
+        assert_eq!(bases.current_range(), Some("up to 16 bits".to_string()));
+
+        assert!(bases.adapt(Adjustment::Start));
+        assert_eq!(bases.current_range(), Some("up to 4 bits".to_string()));
+        assert!(bases
+            .generate_questions(20, &mut rand::thread_rng())
+            .iter()
+            .all(|question| question
+                .key()
+                .unwrap()
+                .rsplit('-')
+                .next()
+                .unwrap()
+                .parse::<u64>()
+                .unwrap()
+                < 16));
+        assert!(bases.adapt(Adjustment::Harder));
+        assert_eq!(bases.current_range(), Some("up to 5 bits".to_string()));
+
+        let bases = Bases::build(&to_args(&["-w", "2"])).expect("Should build correctly");
+        assert!(!bases.adapt(Adjustment::Start));
+        assert_eq!(bases.current_range(), Some("up to 2 bits".to_string()));
+    }
+
+    #[test]
+    fn help_text() {
+        let bases = Bases::build(&to_args(&["-h"])).expect("Should build correctly");
+        assert!(bases.wants_to_print_help());
+        let help_text = bases.get_help_text();
+        assert!(help_text.contains("Bases options"));
+        assert!(help_text.contains("Usage"));
+        assert!(help_text.contains("-f, --from"));
+        assert!(help_text.contains("-t, --to"));
+        assert!(help_text.contains("-w, --bit-width"));
+    }
+}
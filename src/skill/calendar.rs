@@ -0,0 +1,534 @@
+use std::iter;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use super::{check_year_range, question_id, question_key, Base};
+use crate::application::APP_NAME;
+use crate::args::prelude::*;
+use crate::question::{Category, Matcher, Question};
+
+pub const CMD: &str = "calendar";
+
+const ARG_ID_HELP: &str = "help";
+const ARG_ID_MODE: &str = "mode";
+const ARG_ID_LOWER_BOUNDARY: &str = "lower_boundary";
+const ARG_ID_UPPER_BOUNDARY: &str = "upper_boundary";
+const ARG_ID_MAX_DAYS: &str = "max_days";
+
+const MODE_DAY_OF_YEAR: &str = "day-of-year";
+const MODE_DIFFERENCE: &str = "difference";
+const MODE_OFFSET: &str = "offset";
+const MODE_MIXED: &str = "mixed";
+
+const DEFAULT_LOWER_BOUNDARY: i32 = 1900;
+const DEFAULT_UPPER_BOUNDARY: i32 = 2100;
+const DEFAULT_MAX_DAYS: u32 = 365;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const CATEGORY_QUESTION: &str = "question";
+const CATEGORY_MONTH: &str = "month";
+
+/// What is asked about dates
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    /// The number of a day in its year, e.g. 289 for 2026-10-16
+    DayOfYear,
+    /// The number of days from one date to another
+    Difference,
+    /// The date a number of days after another one
+    Offset,
+}
+
+impl Kind {
+    const ALL: [Kind; 3] = [Kind::DayOfYear, Kind::Difference, Kind::Offset];
+
+    /// Category value and its order in the summary
+    fn category(self) -> Category {
+        let (value, order) = match self {
+            Kind::DayOfYear => ("day of the year", 0),
+            Kind::Difference => ("days between dates", 1),
+            Kind::Offset => ("date after days", 2),
+        };
+        Category::new(CATEGORY_QUESTION, value, order)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Only(Kind),
+    /// Any kind, chosen randomly
+    Mixed,
+}
+
+impl Mode {
+    fn from_string(value: &str) -> Mode {
+        match value {
+            MODE_DAY_OF_YEAR => Mode::Only(Kind::DayOfYear),
+            MODE_DIFFERENCE => Mode::Only(Kind::Difference),
+            MODE_OFFSET => Mode::Only(Kind::Offset),
+            MODE_MIXED => Mode::Mixed,
+            _ => panic!("incorrect value for Mode"),
+        }
+    }
+
+    fn allows(self, kind: Kind) -> bool {
+        match self {
+            Mode::Only(only) => only == kind,
+            Mode::Mixed => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Calendar {
+    arg_definitions: Vec<Arg>,
+    show_help: bool,
+
+    mode: Mode,
+    lower_boundary: i32,
+    upper_boundary: i32,
+    /// Maximum number of days between two dates
+    max_days: u32,
+}
+
+impl Calendar {
+    pub fn build(args: &[String]) -> Result<Self, String> {
+        let arg_definitions = Self::build_arg_definitions();
+        let parsed_args = parser::parse_and_validate_arg_list(args, &arg_definitions)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        let show_help =
+            bool::set_value_from_arg_or_default(ARG_ID_HELP, &parsed_args, &arg_definitions);
+        let mode =
+            String::set_value_from_arg_or_default(ARG_ID_MODE, &parsed_args, &arg_definitions);
+        let lower_boundary = i32::set_value_from_arg_or_default(
+            ARG_ID_LOWER_BOUNDARY,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let upper_boundary = i32::set_value_from_arg_or_default(
+            ARG_ID_UPPER_BOUNDARY,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let max_days =
+            u32::set_value_from_arg_or_default(ARG_ID_MAX_DAYS, &parsed_args, &arg_definitions);
+
+        check_year_range(lower_boundary, upper_boundary, Some("Gregorian calendar"))
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+        if max_days == 0 {
+            return Err(Self::build_err_message(Some(
+                "maximum number of days must be at least 1".to_string(),
+            )));
+        }
+
+        Ok(Self {
+            arg_definitions,
+            show_help,
+            mode: Mode::from_string(&mode),
+            lower_boundary,
+            upper_boundary,
+            max_days,
+        })
+    }
+
+    fn build_arg_definitions() -> Vec<Arg> {
+        vec![
+            Arg::builder()
+                .id(ARG_ID_HELP)
+                .short_name('h')
+                .long_name("help")
+                .description(vec!["Display help for calendar command.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(true)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_MODE)
+                .short_name('m')
+                .long_name("mode")
+                .description(vec![
+                    "Set the kind of questions: day-of-year (the".to_string(),
+                    "number of the day in its year), difference".to_string(),
+                    "(days between two dates), offset (the date".to_string(),
+                    "some days after a date) or mixed (all of".to_string(),
+                    "them) (default: mixed).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    MODE_DAY_OF_YEAR.to_string(),
+                    MODE_DIFFERENCE.to_string(),
+                    MODE_OFFSET.to_string(),
+                    MODE_MIXED.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(MODE_MIXED.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_LOWER_BOUNDARY)
+                .short_name('l')
+                .long_name("lower-boundary")
+                .description(vec![format!(
+                    "Set the minimum year (default: {DEFAULT_LOWER_BOUNDARY})."
+                )])
+                .kind(ArgKind::Value(ValueKind::Int))
+                .stop_parsing(false)
+                .default_value(ArgValue::Int(DEFAULT_LOWER_BOUNDARY))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_UPPER_BOUNDARY)
+                .short_name('u')
+                .long_name("upper-boundary")
+                .description(vec![format!(
+                    "Set the maximum year (default: {DEFAULT_UPPER_BOUNDARY})."
+                )])
+                .kind(ArgKind::Value(ValueKind::Int))
+                .stop_parsing(false)
+                .default_value(ArgValue::Int(DEFAULT_UPPER_BOUNDARY))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_MAX_DAYS)
+                .long_name("max-days")
+                .description(vec![
+                    "Set the maximum number of days between".to_string(),
+                    format!("two dates (default: {DEFAULT_MAX_DAYS})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_MAX_DAYS))
+                .build(),
+        ]
+    }
+
+    fn usage() -> String {
+        format!("Usage: {APP_NAME} [option]... calendar [calendar_option]...")
+    }
+
+    fn help_prompt() -> String {
+        format!("Try '{APP_NAME} calendar --help' for more information.")
+    }
+
+    fn additional_info() -> String {
+        format!("Practise calendar calculations: the day of the year (e.g. 289 for 2026-10-16),\nthe number of days between two dates and the date some days after a date.\nDates are Gregorian (default: years {DEFAULT_LOWER_BOUNDARY}-{DEFAULT_UPPER_BOUNDARY}) and presented as YYYY-MM-DD.\nDates are answered in the same form, e.g. '2026-07-04' (or '2026-7-4').")
+    }
+
+    fn build_err_message(msg: Option<String>) -> String {
+        if let Some(msg) = msg {
+            format!(
+                "{}: {}: {}\n{}\n{}",
+                APP_NAME,
+                CMD,
+                msg,
+                Self::usage(),
+                Self::help_prompt()
+            )
+        } else {
+            format!("{}\n{}", Self::usage(), Self::help_prompt())
+        }
+    }
+
+    /// The first and the last day of the year range
+    fn date_range(&self) -> (NaiveDate, NaiveDate) {
+        (
+            NaiveDate::from_ymd_opt(self.lower_boundary, 1, 1).expect("checked in build()"),
+            NaiveDate::from_ymd_opt(self.upper_boundary, 12, 31).expect("checked in build()"),
+        )
+    }
+
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let kind = match self.mode {
+            Mode::Only(kind) => kind,
+            Mode::Mixed => *Kind::ALL.choose(rng).expect("not empty"),
+        };
+        let (first, last) = self.date_range();
+        let last = match kind {
+            Kind::DayOfYear => last,
+            // Leave at least one day for the second date
+            Kind::Difference | Kind::Offset => last - Duration::days(1),
+        };
+        let date = rng.gen_range(first.num_days_from_ce()..=last.num_days_from_ce());
+        let date = NaiveDate::from_num_days_from_ce_opt(date).expect("within range");
+        let mut random_days = || {
+            let days_left = (self.date_range().1 - date).num_days();
+            rng.gen_range(1..=days_left.min(self.max_days.into()))
+        };
+        match kind {
+            Kind::DayOfYear => Self::day_of_year_question(date),
+            Kind::Difference => {
+                Self::difference_question(date, date + Duration::days(random_days()))
+            }
+            Kind::Offset => Self::offset_question(date, random_days()),
+        }
+    }
+
+    fn month_category(date: NaiveDate) -> Category {
+        Category::new(
+            CATEGORY_MONTH,
+            &date.format("%B").to_string(),
+            date.month().into(),
+        )
+    }
+
+    fn day_of_year_question(date: NaiveDate) -> Question {
+        let date_text = date.format(DATE_FORMAT);
+        Question::builder()
+            .question(&format!("Which day of the year is {date_text}?"))
+            .answer(&date.ordinal().to_string())
+            .matcher(Matcher::Numbers)
+            .key(&question_key(CMD, &date_text.to_string()))
+            .categories(&[Kind::DayOfYear.category(), Self::month_category(date)])
+            .build()
+    }
+
+    fn difference_question(from: NaiveDate, to: NaiveDate) -> Question {
+        let (from_text, to_text) = (from.format(DATE_FORMAT), to.format(DATE_FORMAT));
+        Question::builder()
+            .question(&format!("How many days between {from_text} and {to_text}?"))
+            .answer(&(to - from).num_days().to_string())
+            .matcher(Matcher::Numbers)
+            .key(&question_key(CMD, &format!("{from_text}..{to_text}")))
+            .categories(&[Kind::Difference.category(), Self::month_category(from)])
+            .build()
+    }
+
+    fn offset_question(date: NaiveDate, days: i64) -> Question {
+        let answer = date + Duration::days(days);
+        let date_text = date.format(DATE_FORMAT);
+        // Leading zeros of the month and the day can be left out
+        let unpadded = format!("{}-{}-{}", answer.year(), answer.month(), answer.day());
+        let answer = answer.format(DATE_FORMAT).to_string();
+        let alternative_answers = if unpadded == answer {
+            vec![]
+        } else {
+            vec![unpadded]
+        };
+        Question::builder()
+            .question(&format!("What date is {days} days after {date_text}?"))
+            .answer(&answer)
+            .alternative_answers(&alternative_answers)
+            .key(&question_key(CMD, &format!("{date_text}+{days}")))
+            .categories(&[Kind::Offset.category(), Self::month_category(date)])
+            .build()
+    }
+
+    /// Checks whether the dates could have been generated with the current options
+    fn fits(&self, kind: Kind, from: NaiveDate, to: NaiveDate) -> bool {
+        let (first, last) = self.date_range();
+        let days = (to - from).num_days();
+        self.mode.allows(kind)
+            && first <= from
+            && to <= last
+            && (kind == Kind::DayOfYear || (1..=i64::from(self.max_days)).contains(&days))
+    }
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, DATE_FORMAT).ok()
+}
+
+impl Base for Calendar {
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
+            .take(count as usize)
+            .collect()
+    }
+
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let id = question_id(CMD, key)?;
+        if let Some((from, to)) = id.split_once("..") {
+            let (from, to) = (parse_date(from)?, parse_date(to)?);
+            self.fits(Kind::Difference, from, to)
+                .then(|| Self::difference_question(from, to))
+        } else if let Some((date, days)) = id.split_once('+') {
+            let date = parse_date(date)?;
+            let days = days.parse::<u32>().ok()?;
+            let to = date.checked_add_signed(Duration::days(days.into()))?;
+            self.fits(Kind::Offset, date, to)
+                .then(|| Self::offset_question(date, days.into()))
+        } else {
+            let date = parse_date(id)?;
+            self.fits(Kind::DayOfYear, date, date)
+                .then(|| Self::day_of_year_question(date))
+        }
+    }
+
+    fn wants_to_print_help(&self) -> bool {
+        self.show_help
+    }
+
+    fn get_help_text(&self) -> String {
+        let definitions = &self.arg_definitions;
+        let options = help::Options::new("Calendar options", definitions);
+        help::build(
+            &Self::usage(),
+            Some(&Self::additional_info()),
+            &options,
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::{assert_err_message, to_args};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn build_calendar_defaults() {
+        let calendar = Calendar::build(&to_args(&[])).expect("Should build correctly with no args");
+        assert!(!calendar.show_help);
+        assert_eq!(calendar.mode, Mode::Mixed);
+        assert_eq!(calendar.lower_boundary, 1900);
+        assert_eq!(calendar.upper_boundary, 2100);
+        assert_eq!(calendar.max_days, 365);
+
+        let calendar = Calendar::build(&to_args(&["-m", "offset", "-l", "2000", "--max-days=30"]))
+            .expect("Should build correctly with args");
+        assert_eq!(calendar.mode, Mode::Only(Kind::Offset));
+        assert_eq!(calendar.lower_boundary, 2000);
+        assert_eq!(calendar.max_days, 30);
+    }
+
+    #[test]
+    fn build_calendar_incorrect_args() {
+        let err = Calendar::build(&to_args(&["-l", "2000", "-u", "1999"])).unwrap_err();
+        assert!(err.contains("lower boundary must be less than or equal to upper boundary"));
+        let err = Calendar::build(&to_args(&["-l", "1582"])).unwrap_err();
+        assert!(err.contains("does not work for dates on 1582 and before"));
+        let err = Calendar::build(&to_args(&["-u", "262143"])).unwrap_err();
+        assert!(err.contains("cannot exceed 262142"));
+        let err = Calendar::build(&to_args(&["--max-days=0"])).unwrap_err();
+        assert!(err.contains("maximum number of days must be at least 1"));
+        let err = Calendar::build(&to_args(&["--mode=weekday"])).unwrap_err();
+        assert!(err.contains("invalid option argument"));
+    }
+
+    #[test]
+    fn error_message() {
+        let message = Calendar::build_err_message(Some("something wrong".to_string()));
+        assert_err_message(&message, CMD, "something wrong");
+    }
+
+    #[test]
+    fn day_of_year_questions() {
+        let question = Calendar::day_of_year_question(date(2026, 10, 16));
+        assert_eq!(question.prompt(), "Which day of the year is 2026-10-16?");
+        assert_eq!(question.correct_answer(), "289");
+        assert_eq!(question.key(), Some(&"calendar:2026-10-16".to_string()));
+        assert_eq!(
+            question.categories(),
+            [
+                Category::new("question", "day of the year", 0),
+                Category::new("month", "October", 10)
+            ]
+        );
+        assert_eq!(
+            Calendar::day_of_year_question(date(2024, 12, 31)).correct_answer(),
+            "366"
+        );
+    }
+
+    #[test]
+    fn difference_questions() {
+        let question = Calendar::difference_question(date(2026, 3, 1), date(2026, 7, 4));
+        assert_eq!(
+            question.prompt(),
+            "How many days between 2026-03-01 and 2026-07-04?"
+        );
+        assert_eq!(question.correct_answer(), "125");
+        assert_eq!(
+            question.key(),
+            Some(&"calendar:2026-03-01..2026-07-04".to_string())
+        );
+    }
+
+    #[test]
+    fn offset_questions() {
+        let question = Calendar::offset_question(date(2026, 3, 1), 100);
+        assert_eq!(question.prompt(), "What date is 100 days after 2026-03-01?");
+        assert_eq!(question.correct_answer(), "2026-06-09");
+        assert!(question.is_answer_correct("2026-6-9"));
+        assert!(!question.is_answer_correct("2026-06-10"));
+        assert_eq!(question.key(), Some(&"calendar:2026-03-01+100".to_string()));
+
+        let question = Calendar::offset_question(date(2024, 12, 1), 30);
+        assert_eq!(question.correct_answer(), "2024-12-31");
+        assert!(question.is_answer_correct("2024-12-31"));
+    }
+
+    #[test]
+    fn generated_questions_fit_options() {
+        for mode in ["day-of-year", "difference", "offset", "mixed"] {
+            let calendar = Calendar::build(&to_args(&[
+                "-m",
+                mode,
+                "-l",
+                "2024",
+                "-u",
+                "2024",
+                "--max-days=10",
+            ]))
+            .expect("Should build correctly");
+            for question in calendar.generate_questions(50, &mut rand::thread_rng()) {
+                let key = question.key().expect("Questions should have keys");
+                let recreated = calendar
+                    .question_from_key(key)
+                    .unwrap_or_else(|| panic!("{key} should be recognised"));
+                assert_eq!(recreated.prompt(), question.prompt());
+            }
+        }
+    }
+
+    #[test]
+    fn question_from_key() {
+        let calendar = Calendar::build(&to_args(&["-l", "2000", "-u", "2030", "--max-days=200"]))
+            .expect("Should build correctly");
+        assert!(calendar.question_from_key("calendar:2026-10-16").is_some());
+        assert!(calendar
+            .question_from_key("calendar:2026-03-01..2026-07-04")
+            .is_some());
+        assert!(calendar
+            .question_from_key("calendar:2026-03-01+100")
+            .is_some());
+
+        assert!(calendar.question_from_key("calendar:1999-10-16").is_none());
+        assert!(calendar.question_from_key("calendar:2026-02-30").is_none());
+        assert!(calendar
+            .question_from_key("calendar:2026-07-04..2026-03-01")
+            .is_none());
+        assert!(calendar
+            .question_from_key("calendar:2026-01-01..2026-12-31")
+            .is_none());
+        assert!(calendar
+            .question_from_key("calendar:2030-12-31+1")
+            .is_none());
+        assert!(calendar
+            .question_from_key("calendar:2026-03-01+0")
+            .is_none());
+        assert!(calendar.question_from_key("doomsday:2026-10-16").is_none());
+
+        let calendar =
+            Calendar::build(&to_args(&["-m", "difference"])).expect("Should build correctly");
+        assert!(calendar.question_from_key("calendar:2026-10-16").is_none());
+        assert!(calendar
+            .question_from_key("calendar:2026-03-01+100")
+            .is_none());
+    }
+
+    #[test]
+    fn help_text() {
+        let calendar = Calendar::build(&to_args(&["-h"])).expect("Should build correctly");
+        assert!(calendar.wants_to_print_help());
+        let help_text = calendar.get_help_text();
+        assert!(help_text.contains("Calendar options"));
+        assert!(help_text.contains("Usage"));
+        assert!(help_text.contains("-m, --mode"));
+        assert!(help_text.contains("-l, --lower-boundary"));
+        assert!(help_text.contains("-u, --upper-boundary"));
+        assert!(help_text.contains("--max-days"));
+    }
+}
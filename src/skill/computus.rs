@@ -0,0 +1,566 @@
+use std::iter;
+
+use chrono::NaiveDate;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use super::{check_year_range, question_id, question_key, Base};
+use crate::application::APP_NAME;
+use crate::args::prelude::*;
+use crate::question::{Category, Question};
+
+pub const CMD: &str = "computus";
+
+const ARG_ID_HELP: &str = "help";
+const ARG_ID_EASTER: &str = "easter";
+const ARG_ID_LOWER_BOUNDARY: &str = "lower_boundary";
+const ARG_ID_UPPER_BOUNDARY: &str = "upper_boundary";
+
+const EASTER_WESTERN: &str = "western";
+const EASTER_ORTHODOX: &str = "orthodox";
+const EASTER_BOTH: &str = "both";
+
+const DEFAULT_LOWER_BOUNDARY: i32 = 1900;
+const DEFAULT_UPPER_BOUNDARY: i32 = 2100;
+const CATEGORY_EASTER: &str = "easter";
+const CATEGORY_MONTH: &str = "month";
+
+/// Which Easter is asked for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Easter {
+    /// Calculated in the Gregorian calendar
+    Western,
+    /// Calculated (and answered) in the Julian calendar
+    Orthodox,
+}
+
+impl Easter {
+    fn name(self) -> &'static str {
+        match self {
+            Easter::Western => EASTER_WESTERN,
+            Easter::Orthodox => EASTER_ORTHODOX,
+        }
+    }
+
+    /// The date of Easter and of the Paschal full moon before it, both as days after
+    /// the last day of February (e.g. 22 for March 22, 32 for April 1)
+    fn dates(self, year: i32) -> (i32, i32) {
+        match self {
+            Easter::Western => {
+                // Anonymous Gregorian algorithm
+                let a = year.rem_euclid(19);
+                let (b, c) = (year.div_euclid(100), year.rem_euclid(100));
+                let (d, e) = (b / 4, b % 4);
+                let f = (b + 8) / 25;
+                let g = (b - f + 1) / 3;
+                let h = (19 * a + b - d - g + 15) % 30;
+                let (i, k) = (c / 4, c % 4);
+                let l = (32 + 2 * e + 2 * i - h - k) % 7;
+                let m = (a + 11 * h + 22 * l) / 451;
+                // The full moon is moved a day earlier in two cases, so that it doesn't
+                // fall on April 19 or on April 18 in the later part of the lunar cycle
+                let full_moon = match h {
+                    29 => 21 + 28,
+                    28 if a > 10 => 21 + 27,
+                    _ => 21 + h,
+                };
+                (22 + h + l - 7 * m, full_moon)
+            }
+            Easter::Orthodox => {
+                // Meeus' Julian algorithm
+                let (a, b, c) = (year.rem_euclid(4), year.rem_euclid(7), year.rem_euclid(19));
+                let d = (19 * c + 15) % 30;
+                let e = (2 * a + 4 * b - d + 34) % 7;
+                (22 + d + e, 21 + d)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Only(Easter),
+    /// Either of them, chosen randomly
+    Both,
+}
+
+impl Mode {
+    fn from_string(value: &str) -> Mode {
+        match value {
+            EASTER_WESTERN => Mode::Only(Easter::Western),
+            EASTER_ORTHODOX => Mode::Only(Easter::Orthodox),
+            EASTER_BOTH => Mode::Both,
+            _ => panic!("incorrect value for Mode"),
+        }
+    }
+
+    fn allows(self, easter: Easter) -> bool {
+        match self {
+            Mode::Only(only) => only == easter,
+            Mode::Both => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Computus {
+    arg_definitions: Vec<Arg>,
+    show_help: bool,
+
+    mode: Mode,
+    lower_boundary: i32,
+    upper_boundary: i32,
+}
+
+impl Computus {
+    pub fn build(args: &[String]) -> Result<Self, String> {
+        let arg_definitions = Self::build_arg_definitions();
+        let parsed_args = parser::parse_and_validate_arg_list(args, &arg_definitions)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        let show_help =
+            bool::set_value_from_arg_or_default(ARG_ID_HELP, &parsed_args, &arg_definitions);
+        let mode =
+            String::set_value_from_arg_or_default(ARG_ID_EASTER, &parsed_args, &arg_definitions);
+        let lower_boundary = i32::set_value_from_arg_or_default(
+            ARG_ID_LOWER_BOUNDARY,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let upper_boundary = i32::set_value_from_arg_or_default(
+            ARG_ID_UPPER_BOUNDARY,
+            &parsed_args,
+            &arg_definitions,
+        );
+
+        let mode = Mode::from_string(&mode);
+        // Orthodox Easter is calculated in the Julian calendar, also before the reform
+        let reform_limit = (mode != Mode::Only(Easter::Orthodox)).then_some("Gregorian calendar");
+        check_year_range(lower_boundary, upper_boundary, reform_limit)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        Ok(Self {
+            arg_definitions,
+            show_help,
+            mode,
+            lower_boundary,
+            upper_boundary,
+        })
+    }
+
+    fn build_arg_definitions() -> Vec<Arg> {
+        vec![
+            Arg::builder()
+                .id(ARG_ID_HELP)
+                .short_name('h')
+                .long_name("help")
+                .description(vec!["Display help for computus command.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(true)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_EASTER)
+                .short_name('e')
+                .long_name("easter")
+                .description(vec![
+                    "Set the Easter to calculate: western".to_string(),
+                    "(Gregorian calendar), orthodox (Julian".to_string(),
+                    "calendar) or both (default: western).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    EASTER_WESTERN.to_string(),
+                    EASTER_ORTHODOX.to_string(),
+                    EASTER_BOTH.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(EASTER_WESTERN.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_LOWER_BOUNDARY)
+                .short_name('l')
+                .long_name("lower-boundary")
+                .description(vec![format!(
+                    "Set the minimum year (default: {DEFAULT_LOWER_BOUNDARY})."
+                )])
+                .kind(ArgKind::Value(ValueKind::Int))
+                .stop_parsing(false)
+                .default_value(ArgValue::Int(DEFAULT_LOWER_BOUNDARY))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_UPPER_BOUNDARY)
+                .short_name('u')
+                .long_name("upper-boundary")
+                .description(vec![format!(
+                    "Set the maximum year (default: {DEFAULT_UPPER_BOUNDARY})."
+                )])
+                .kind(ArgKind::Value(ValueKind::Int))
+                .stop_parsing(false)
+                .default_value(ArgValue::Int(DEFAULT_UPPER_BOUNDARY))
+                .build(),
+        ]
+    }
+
+    fn usage() -> String {
+        format!("Usage: {APP_NAME} [option]... computus [computus_option]...")
+    }
+
+    fn help_prompt() -> String {
+        format!("Try '{APP_NAME} computus --help' for more information.")
+    }
+
+    fn additional_info() -> String {
+        let mut text = String::new();
+        text.push_str("Practise computus, the calculation of the date of Easter\n");
+        text.push_str(
+            "(https://en.wikipedia.org/wiki/Date_of_Easter). Easter is the first Sunday\n",
+        );
+        text.push_str(
+            "after the Paschal full moon, the ecclesiastical full moon on or after March 21.\n",
+        );
+        text.push_str(
+            "Answers are accepted as 'April 5', 'Apr 5', '5 April', '04-05' or '05.04'.\n",
+        );
+        text.push_str(
+            "\nWestern Easter needs years after 1582. Orthodox Easter is calculated and\n",
+        );
+        text.push_str(
+            "answered in the Julian calendar, which is 13 days behind the Gregorian one\n",
+        );
+        text.push_str("in 1900-2099 (e.g. April 22 is May 5).");
+        text
+    }
+
+    fn build_err_message(msg: Option<String>) -> String {
+        if let Some(msg) = msg {
+            format!(
+                "{}: {}: {}\n{}\n{}",
+                APP_NAME,
+                CMD,
+                msg,
+                Self::usage(),
+                Self::help_prompt()
+            )
+        } else {
+            format!("{}\n{}", Self::usage(), Self::help_prompt())
+        }
+    }
+
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let easter = match self.mode {
+            Mode::Only(easter) => easter,
+            Mode::Both => *[Easter::Western, Easter::Orthodox]
+                .choose(rng)
+                .expect("not empty"),
+        };
+        let year = rng.gen_range(self.lower_boundary..=self.upper_boundary);
+        Self::question(year, easter)
+    }
+
+    /// Month and day of a date given as days after the last day of February
+    fn month_and_day(days: i32) -> (u32, u32) {
+        let (month, day) = if days > 31 { (4, days - 31) } else { (3, days) };
+        (month, u32::try_from(day).expect("Easter is after March 21"))
+    }
+
+    fn month_name(month: u32) -> String {
+        NaiveDate::from_ymd_opt(2000, month, 1)
+            .expect("month is between 1 and 12")
+            .format("%B")
+            .to_string()
+    }
+
+    /// "April 5" and its accepted alternatives, e.g. "Apr 5", "5 April", "04-05", "5.4."
+    fn date_answers(month: u32, day: u32) -> (String, Vec<String>) {
+        let name = Self::month_name(month);
+        let short_name = &name[..3];
+        let answer = format!("{name} {day}");
+        let mut alternative_answers = vec![
+            format!("{short_name} {day}"),
+            format!("{day} {name}"),
+            format!("{day} {short_name}"),
+            format!("{month:02}-{day:02}"),
+            format!("{month}-{day}"),
+            format!("{day:02}.{month:02}"),
+            format!("{day}.{month}"),
+            format!("{day:02}.{month:02}."),
+            format!("{day}.{month}."),
+        ];
+        alternative_answers.sort();
+        alternative_answers.dedup();
+        alternative_answers.retain(|alternative| *alternative != answer);
+        (answer, alternative_answers)
+    }
+
+    fn question(year: i32, easter: Easter) -> Question {
+        let (easter_date, full_moon) = easter.dates(year);
+        let (month, day) = Self::month_and_day(easter_date);
+        let (answer, alternative_answers) = Self::date_answers(month, day);
+        let prompt = match easter {
+            Easter::Western => format!("When is Easter in {year}?"),
+            Easter::Orthodox => format!("When is Orthodox Easter in {year} (Julian calendar)?"),
+        };
+
+        let (full_moon_month, full_moon_day) = Self::month_and_day(full_moon);
+        // Easter is a Sunday, 1 to 7 days after the full moon
+        let full_moon_weekday = match easter_date - full_moon {
+            7 => "Sunday",
+            6 => "Monday",
+            5 => "Tuesday",
+            4 => "Wednesday",
+            3 => "Thursday",
+            2 => "Friday",
+            _ => "Saturday",
+        };
+        let explanation = [
+            format!(
+                "Golden number: {year} mod 19 + 1 = {}",
+                year.rem_euclid(19) + 1
+            ),
+            format!(
+                "Paschal full moon: {} {full_moon_day} ({full_moon_weekday})",
+                Self::month_name(full_moon_month)
+            ),
+            format!("Easter is the Sunday after: {answer}"),
+        ];
+
+        Question::builder()
+            .question(&prompt)
+            .answer(&answer)
+            .alternative_answers(&alternative_answers)
+            .allow_any_case(true)
+            .key(&question_key(CMD, &format!("{}-{year}", easter.name())))
+            .categories(&[
+                Category::new(
+                    CATEGORY_EASTER,
+                    easter.name(),
+                    i64::from(easter == Easter::Orthodox),
+                ),
+                Category::new(CATEGORY_MONTH, &Self::month_name(month), month.into()),
+            ])
+            .explanation(&explanation.join("\n"))
+            .build()
+    }
+}
+
+impl Base for Computus {
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
+            .take(count as usize)
+            .collect()
+    }
+
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let (easter, year) = question_id(CMD, key)?.split_once('-')?;
+        let easter = match easter {
+            EASTER_WESTERN => Easter::Western,
+            EASTER_ORTHODOX => Easter::Orthodox,
+            _ => return None,
+        };
+        let year = year.parse::<i32>().ok()?;
+        (self.mode.allows(easter) && (self.lower_boundary..=self.upper_boundary).contains(&year))
+            .then(|| Self::question(year, easter))
+    }
+
+    fn wants_to_print_help(&self) -> bool {
+        self.show_help
+    }
+
+    fn get_help_text(&self) -> String {
+        let definitions = &self.arg_definitions;
+        let options = help::Options::new("Computus options", definitions);
+        help::build(
+            &Self::usage(),
+            Some(&Self::additional_info()),
+            &options,
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Datelike, Duration, Weekday};
+
+    use super::*;
+    use crate::skill::{assert_err_message, to_args};
+
+    #[test]
+    fn build_computus_defaults() {
+        let computus = Computus::build(&to_args(&[])).expect("Should build correctly with no args");
+        assert!(!computus.show_help);
+        assert_eq!(computus.mode, Mode::Only(Easter::Western));
+        assert_eq!(computus.lower_boundary, 1900);
+        assert_eq!(computus.upper_boundary, 2100);
+
+        let computus = Computus::build(&to_args(&["-e", "orthodox", "-l", "1000"]))
+            .expect("Should build correctly");
+        assert_eq!(computus.mode, Mode::Only(Easter::Orthodox));
+        assert_eq!(computus.lower_boundary, 1000);
+    }
+
+    #[test]
+    fn build_computus_incorrect_args() {
+        let err = Computus::build(&to_args(&["-l", "1500"])).unwrap_err();
+        assert!(err.contains("does not work for dates on 1582 and before"));
+        let err = Computus::build(&to_args(&["-e", "both", "-l", "1500"])).unwrap_err();
+        assert!(err.contains("does not work for dates on 1582 and before"));
+        let err = Computus::build(&to_args(&["-e", "orthodox", "-l", "0"])).unwrap_err();
+        assert!(err.contains("years before 1 are not supported"));
+        let err = Computus::build(&to_args(&["-l", "2000", "-u", "1999"])).unwrap_err();
+        assert!(err.contains("lower boundary must be less than or equal to upper boundary"));
+        let err = Computus::build(&to_args(&["--easter=coptic"])).unwrap_err();
+        assert!(err.contains("invalid option argument"));
+    }
+
+    #[test]
+    fn error_message() {
+        let message = Computus::build_err_message(Some("something wrong".to_string()));
+        assert_err_message(&message, CMD, "something wrong");
+    }
+
+    #[test]
+    fn western_easter() {
+        for (year, month, day) in [
+            (1818, 3, 22),
+            (1943, 4, 25),
+            (1954, 4, 18),
+            (1981, 4, 19),
+            (2000, 4, 23),
+            (2019, 4, 21),
+            (2024, 3, 31),
+            (2025, 4, 20),
+            (2026, 4, 5),
+            (2038, 4, 25),
+            (2285, 3, 22),
+        ] {
+            let (easter, _) = Easter::Western.dates(year);
+            assert_eq!(Computus::month_and_day(easter), (month, day), "{year}");
+        }
+
+        // Paschal full moons by golden number in 1900-2199
+        let full_moons = [
+            45, 34, 23, 42, 31, 49, 39, 28, 47, 36, 25, 44, 33, 22, 41, 30, 48, 38, 27,
+        ];
+        for year in 1900..2200 {
+            let (_, full_moon) = Easter::Western.dates(year);
+            assert_eq!(full_moon, full_moons[year as usize % 19], "{year}");
+        }
+
+        // Easter is a Sunday after the full moon, not more than a week later
+        for year in 1583..3000 {
+            let (easter, full_moon) = Easter::Western.dates(year);
+            let (month, day) = Computus::month_and_day(easter);
+            let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+            assert_eq!(date.weekday(), Weekday::Sun, "{year}");
+            assert!((1..=7).contains(&(easter - full_moon)), "{year}");
+            assert!((21..=49).contains(&full_moon), "{year}");
+        }
+    }
+
+    #[test]
+    fn orthodox_easter() {
+        // Julian dates, 13 days before the Gregorian ones in 1900-2099
+        for (year, month, day) in [(2024, 4, 22), (2025, 4, 7), (2026, 3, 30), (2010, 3, 22)] {
+            let (easter, _) = Easter::Orthodox.dates(year);
+            assert_eq!(Computus::month_and_day(easter), (month, day), "{year}");
+        }
+
+        for year in 1900..2100 {
+            let (easter, full_moon) = Easter::Orthodox.dates(year);
+            let (month, day) = Computus::month_and_day(easter);
+            let date = NaiveDate::from_ymd_opt(year, month, day).unwrap() + Duration::days(13);
+            assert_eq!(date.weekday(), Weekday::Sun, "{year}");
+            assert!((1..=7).contains(&(easter - full_moon)), "{year}");
+        }
+    }
+
+    #[test]
+    fn answers() {
+        let question = Computus::question(2026, Easter::Western);
+        assert_eq!(question.prompt(), "When is Easter in 2026?");
+        assert_eq!(question.correct_answer(), "April 5");
+        for answer in [
+            "april 5", "Apr 5", "5 April", "04-05", "4-5", "05.04", "5.4.",
+        ] {
+            assert!(question.is_answer_correct(answer), "{answer}");
+        }
+        assert!(!question.is_answer_correct("April 12"));
+        assert!(!question.is_answer_correct("05-04"));
+        assert_eq!(question.key(), Some(&"computus:western-2026".to_string()));
+        assert_eq!(
+            question.categories(),
+            [
+                Category::new("easter", "western", 0),
+                Category::new("month", "April", 4)
+            ]
+        );
+
+        let question = Computus::question(2024, Easter::Orthodox);
+        assert_eq!(
+            question.prompt(),
+            "When is Orthodox Easter in 2024 (Julian calendar)?"
+        );
+        assert_eq!(question.correct_answer(), "April 22");
+        assert_eq!(question.key(), Some(&"computus:orthodox-2024".to_string()));
+    }
+
+    #[test]
+    fn explanation() {
+        let question = Computus::question(2024, Easter::Western);
+        assert_eq!(
+            question.explanation(),
+            Some(
+                &"Golden number: 2024 mod 19 + 1 = 11\n\
+                  Paschal full moon: March 25 (Monday)\n\
+                  Easter is the Sunday after: March 31"
+                    .to_string()
+            )
+        );
+        // The full moon is moved from April 19
+        let explanation = Computus::question(1981, Easter::Western)
+            .explanation()
+            .unwrap()
+            .clone();
+        assert!(explanation.contains("Paschal full moon: April 18 (Saturday)"));
+    }
+
+    #[test]
+    fn question_from_key() {
+        let computus = Computus::build(&to_args(&["-e", "both"])).expect("Should build correctly");
+        assert!(computus
+            .question_from_key("computus:western-2024")
+            .is_some());
+        assert!(computus
+            .question_from_key("computus:orthodox-2024")
+            .is_some());
+        assert!(computus
+            .question_from_key("computus:western-1800")
+            .is_none());
+        assert!(computus.question_from_key("computus:coptic-2024").is_none());
+        assert!(computus.question_from_key("computus:2024").is_none());
+
+        let computus = Computus::build(&to_args(&[])).expect("Should build correctly");
+        assert!(computus
+            .question_from_key("computus:orthodox-2024")
+            .is_none());
+        for question in computus.generate_questions(10, &mut rand::thread_rng()) {
+            assert!(question.prompt().starts_with("When is Easter in "));
+            assert!(computus
+                .question_from_key(question.key().unwrap())
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn help_text() {
+        let computus = Computus::build(&to_args(&["-h"])).expect("Should build correctly");
+        assert!(computus.wants_to_print_help());
+        let help_text = computus.get_help_text();
+        assert!(help_text.contains("Computus options"));
+        assert!(help_text.contains("Usage"));
+        assert!(help_text.contains("-e, --easter"));
+        assert!(help_text.contains("-l, --lower-boundary"));
+        assert!(help_text.contains("-u, --upper-boundary"));
+    }
+}
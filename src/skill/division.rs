@@ -0,0 +1,512 @@
+use std::iter;
+
+use rand::{Rng, RngCore};
+
+use super::{question_id, question_key, Base};
+use crate::application::APP_NAME;
+use crate::args::prelude::*;
+use crate::question::{Category, Matcher, Question};
+
+pub const CMD: &str = "division";
+
+const ARG_ID_HELP: &str = "help";
+const ARG_ID_MODE: &str = "mode";
+const ARG_ID_DECIMAL_PLACES: &str = "decimal_places";
+const ARG_ID_LOWER_BOUNDARY_1: &str = "lower_boundary_1";
+const ARG_ID_UPPER_BOUNDARY_1: &str = "upper_boundary_1";
+const ARG_ID_LOWER_BOUNDARY_2: &str = "lower_boundary_2";
+const ARG_ID_UPPER_BOUNDARY_2: &str = "upper_boundary_2";
+
+const MODE_EXACT: &str = "exact";
+const MODE_REMAINDER: &str = "remainder";
+const MODE_DECIMAL: &str = "decimal";
+
+const DEFAULT_LOWER_BOUNDARY: u32 = 1;
+const DEFAULT_UPPER_BOUNDARY: u32 = 10;
+const DEFAULT_DECIMAL_PLACES: u32 = 2;
+const MAX_DECIMAL_PLACES: u32 = 6;
+const CATEGORY_DIVISOR: &str = "divisor";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// The dividend is a multiple of the divisor
+    Exact,
+    /// Quotient and remainder, e.g. "8 r 3"
+    Remainder,
+    /// Quotient rounded to the given number of decimal places
+    Decimal(u32),
+}
+
+impl Mode {
+    fn from_string(value: &str, decimal_places: u32) -> Mode {
+        match value {
+            MODE_EXACT => Mode::Exact,
+            MODE_REMAINDER => Mode::Remainder,
+            MODE_DECIMAL => Mode::Decimal(decimal_places),
+            _ => panic!("incorrect value for Mode"),
+        }
+    }
+
+    /// Suffix of the question key, so that questions of different modes are told apart
+    fn key_suffix(self) -> String {
+        match self {
+            Mode::Exact => String::new(),
+            Mode::Remainder => "r".to_string(),
+            Mode::Decimal(places) => format!("d{places}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Division {
+    arg_definitions: Vec<Arg>,
+    show_help: bool,
+
+    mode: Mode,
+    /// Range of quotients
+    lower_boundary_1: u32,
+    upper_boundary_1: u32,
+    /// Range of divisors
+    lower_boundary_2: u32,
+    upper_boundary_2: u32,
+}
+
+impl Division {
+    pub fn build(args: &[String]) -> Result<Self, String> {
+        let arg_definitions = Self::build_arg_definitions();
+        let parsed_args = parser::parse_and_validate_arg_list(args, &arg_definitions)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        let show_help =
+            bool::set_value_from_arg_or_default(ARG_ID_HELP, &parsed_args, &arg_definitions);
+        let mode =
+            String::set_value_from_arg_or_default(ARG_ID_MODE, &parsed_args, &arg_definitions);
+        let decimal_places = u32::set_value_from_arg_or_default(
+            ARG_ID_DECIMAL_PLACES,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let lower_boundary_1 = u32::set_value_from_arg_or_default(
+            ARG_ID_LOWER_BOUNDARY_1,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let upper_boundary_1 = u32::set_value_from_arg_or_default(
+            ARG_ID_UPPER_BOUNDARY_1,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let lower_boundary_2 = u32::set_value_from_arg_or_default(
+            ARG_ID_LOWER_BOUNDARY_2,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let upper_boundary_2 = u32::set_value_from_arg_or_default(
+            ARG_ID_UPPER_BOUNDARY_2,
+            &parsed_args,
+            &arg_definitions,
+        );
+
+        if lower_boundary_1 > upper_boundary_1 || lower_boundary_2 > upper_boundary_2 {
+            return Err(Self::build_err_message(Some(
+                "lower boundary must be less than or equal to upper boundary".to_string(),
+            )));
+        }
+        if lower_boundary_2 == 0 {
+            return Err(Self::build_err_message(Some(
+                "divisor must be at least 1".to_string(),
+            )));
+        }
+        if !(1..=MAX_DECIMAL_PLACES).contains(&decimal_places) {
+            return Err(Self::build_err_message(Some(format!(
+                "number of decimal places must be between 1 and {MAX_DECIMAL_PLACES}"
+            ))));
+        }
+
+        Ok(Self {
+            arg_definitions,
+            show_help,
+            mode: Mode::from_string(&mode, decimal_places),
+            lower_boundary_1,
+            upper_boundary_1,
+            lower_boundary_2,
+            upper_boundary_2,
+        })
+    }
+
+    fn build_arg_definitions() -> Vec<Arg> {
+        vec![
+            Arg::builder()
+                .id(ARG_ID_HELP)
+                .short_name('h')
+                .long_name("help")
+                .description(vec!["Display help for division command.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(true)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_MODE)
+                .short_name('m')
+                .long_name("mode")
+                .description(vec![
+                    "Set the kind of questions: exact (no".to_string(),
+                    "remainder), remainder (quotient and".to_string(),
+                    "remainder, e.g. 8 r 3) or decimal (quotient".to_string(),
+                    "rounded to --decimal-places)".to_string(),
+                    "(default: exact).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    MODE_EXACT.to_string(),
+                    MODE_REMAINDER.to_string(),
+                    MODE_DECIMAL.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(MODE_EXACT.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_DECIMAL_PLACES)
+                .long_name("decimal-places")
+                .description(vec![
+                    "Set the number of decimal places".to_string(),
+                    format!("in decimal mode (default: {DEFAULT_DECIMAL_PLACES})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_DECIMAL_PLACES))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_LOWER_BOUNDARY_1)
+                .long_name("lower-boundary-1")
+                .description(vec![
+                    "Set the minimum value".to_string(),
+                    format!("of the quotient (default: {DEFAULT_LOWER_BOUNDARY})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_LOWER_BOUNDARY))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_UPPER_BOUNDARY_1)
+                .long_name("upper-boundary-1")
+                .description(vec![
+                    "Set the maximum value".to_string(),
+                    format!("of the quotient (default: {DEFAULT_UPPER_BOUNDARY})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_UPPER_BOUNDARY))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_LOWER_BOUNDARY_2)
+                .long_name("lower-boundary-2")
+                .description(vec![
+                    "Set the minimum value".to_string(),
+                    format!("of the divisor (default: {DEFAULT_LOWER_BOUNDARY})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_LOWER_BOUNDARY))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_UPPER_BOUNDARY_2)
+                .long_name("upper-boundary-2")
+                .description(vec![
+                    "Set the maximum value".to_string(),
+                    format!("of the divisor (default: {DEFAULT_UPPER_BOUNDARY})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_UPPER_BOUNDARY))
+                .build(),
+        ]
+    }
+
+    fn usage() -> String {
+        format!("Usage: {APP_NAME} [option]... division [division_option]...")
+    }
+
+    fn help_prompt() -> String {
+        format!("Try '{APP_NAME} division --help' for more information.")
+    }
+
+    fn additional_info() -> String {
+        format!("Practise division, the inverse of the times table. The dividend is the product\nof a quotient and a divisor from the configured ranges (default: {DEFAULT_LOWER_BOUNDARY}-{DEFAULT_UPPER_BOUNDARY}),\nplus a remainder in remainder and decimal modes.\nAnswer remainder questions with the quotient and the remainder, e.g. '8 r 3'.\nDecimal quotients are rounded half up.")
+    }
+
+    fn build_err_message(msg: Option<String>) -> String {
+        if let Some(msg) = msg {
+            format!(
+                "{}: {}: {}\n{}\n{}",
+                APP_NAME,
+                CMD,
+                msg,
+                Self::usage(),
+                Self::help_prompt()
+            )
+        } else {
+            format!("{}\n{}", Self::usage(), Self::help_prompt())
+        }
+    }
+
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let quotient = rng.gen_range(self.lower_boundary_1..=self.upper_boundary_1);
+        let divisor = rng.gen_range(self.lower_boundary_2..=self.upper_boundary_2);
+        let remainder = match self.mode {
+            Mode::Exact => 0,
+            Mode::Remainder | Mode::Decimal(_) => rng.gen_range(0..divisor),
+        };
+        // u32::MAX ^ 2 + u32::MAX < u64::MAX
+        let dividend = u64::from(quotient) * u64::from(divisor) + u64::from(remainder);
+        Self::question(dividend, divisor, self.mode)
+    }
+
+    fn question(dividend: u64, divisor: u32, mode: Mode) -> Question {
+        let quotient = dividend / u64::from(divisor);
+        let remainder = dividend % u64::from(divisor);
+        let (prompt, answer, alternative_answers) = match mode {
+            Mode::Exact => (
+                format!("{dividend}/{divisor}"),
+                quotient.to_string(),
+                vec![],
+            ),
+            Mode::Remainder => (
+                format!("{dividend}/{divisor} (with remainder)"),
+                format!("{quotient} r {remainder}"),
+                // No remainder can be left out
+                if remainder == 0 {
+                    vec![quotient.to_string()]
+                } else {
+                    vec![]
+                },
+            ),
+            Mode::Decimal(places) => (
+                format!("{dividend}/{divisor} ({places} decimal places)"),
+                Self::rounded_quotient(dividend, divisor, places),
+                vec![],
+            ),
+        };
+        let id = format!("{dividend}/{divisor}{}", mode.key_suffix());
+
+        Question::builder()
+            .question(&prompt)
+            .answer(&answer)
+            .alternative_answers(&alternative_answers)
+            .matcher(Matcher::Numbers)
+            .key(&question_key(CMD, &id))
+            .categories(&[Category::new(
+                CATEGORY_DIVISOR,
+                &divisor.to_string(),
+                divisor.into(),
+            )])
+            .build()
+    }
+
+    /// `dividend / divisor` rounded half up to `places` decimal places. Integer arithmetic
+    /// avoids floating point rounding errors.
+    fn rounded_quotient(dividend: u64, divisor: u32, places: u32) -> String {
+        let scale = 10_u128.pow(places);
+        let divisor = u128::from(divisor);
+        let scaled = u128::from(dividend) * scale;
+        let mut quotient = scaled / divisor;
+        if 2 * (scaled % divisor) >= divisor {
+            quotient += 1;
+        }
+        format!(
+            "{}.{:0width$}",
+            quotient / scale,
+            quotient % scale,
+            width = places as usize
+        )
+    }
+
+    /// Parses the question id, e.g. "59/7r" into (59, 7, Mode::Remainder)
+    fn parse_id(id: &str) -> Option<(u64, u32, Mode)> {
+        let (dividend, divisor) = id.split_once('/')?;
+        let (divisor, mode) = if let Some(divisor) = divisor.strip_suffix('r') {
+            (divisor, Mode::Remainder)
+        } else if let Some((divisor, places)) = divisor.split_once('d') {
+            (divisor, Mode::Decimal(places.parse().ok()?))
+        } else {
+            (divisor, Mode::Exact)
+        };
+        Some((dividend.parse().ok()?, divisor.parse().ok()?, mode))
+    }
+
+    /// Checks whether the question could have been generated with the current options
+    fn fits(&self, dividend: u64, divisor: u32, mode: Mode) -> bool {
+        if mode != self.mode || !(self.lower_boundary_2..=self.upper_boundary_2).contains(&divisor)
+        {
+            return false;
+        }
+        let quotient = dividend / u64::from(divisor);
+        (u64::from(self.lower_boundary_1)..=u64::from(self.upper_boundary_1)).contains(&quotient)
+            && (mode != Mode::Exact || dividend.is_multiple_of(u64::from(divisor)))
+    }
+}
+
+impl Base for Division {
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
+            .take(count as usize)
+            .collect()
+    }
+
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let (dividend, divisor, mode) = Self::parse_id(question_id(CMD, key)?)?;
+        if self.fits(dividend, divisor, mode) {
+            Some(Self::question(dividend, divisor, mode))
+        } else {
+            None
+        }
+    }
+
+    fn wants_to_print_help(&self) -> bool {
+        self.show_help
+    }
+
+    fn get_help_text(&self) -> String {
+        let definitions = &self.arg_definitions;
+        let options = help::Options::new("Division options", definitions);
+        help::build(
+            &Self::usage(),
+            Some(&Self::additional_info()),
+            &options,
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::{assert_err_message, to_args};
+
+    #[test]
+    fn build_division_defaults() {
+        let division = Division::build(&to_args(&[])).expect("Should build correctly with no args");
+        assert!(!division.show_help);
+        assert_eq!(division.mode, Mode::Exact);
+        assert_eq!(division.lower_boundary_1, 1);
+        assert_eq!(division.upper_boundary_1, 10);
+        assert_eq!(division.lower_boundary_2, 1);
+        assert_eq!(division.upper_boundary_2, 10);
+
+        let division = Division::build(&to_args(&["-m", "decimal", "--decimal-places=3"]))
+            .expect("Should build correctly with args");
+        assert_eq!(division.mode, Mode::Decimal(3));
+    }
+
+    #[test]
+    fn build_division_incorrect_args() {
+        let err = Division::build(&to_args(&["--lower-boundary-1=5", "--upper-boundary-1=4"]))
+            .unwrap_err();
+        assert!(err.contains("lower boundary must be less than or equal to upper boundary"));
+        let err = Division::build(&to_args(&["--lower-boundary-2=0"])).unwrap_err();
+        assert!(err.contains("divisor must be at least 1"));
+        let err = Division::build(&to_args(&["--decimal-places=7"])).unwrap_err();
+        assert!(err.contains("number of decimal places must be between 1 and 6"));
+        let err = Division::build(&to_args(&["--mode=fractions"])).unwrap_err();
+        assert!(err.contains("invalid option argument"));
+    }
+
+    #[test]
+    fn error_message() {
+        let message = Division::build_err_message(Some("something wrong".to_string()));
+        assert_err_message(&message, CMD, "something wrong");
+    }
+
+    #[test]
+    fn exact_questions() {
+        let division =
+            Division::build(&to_args(&["--lower-boundary-2=2"])).expect("Should build correctly");
+        for question in division.generate_questions(50, &mut rand::thread_rng()) {
+            let (dividend, divisor) = question
+                .prompt()
+                .split_once('/')
+                .expect("Prompt should contain a division");
+            let dividend: u32 = dividend.parse().unwrap();
+            let divisor: u32 = divisor.parse().unwrap();
+            assert!((2..=10).contains(&divisor));
+            assert_eq!(dividend % divisor, 0);
+            assert!((1..=10).contains(&(dividend / divisor)));
+            assert!(question.is_answer_correct(&(dividend / divisor).to_string()));
+        }
+    }
+
+    #[test]
+    fn remainder_questions() {
+        let question = Division::question(59, 7, Mode::Remainder);
+        assert_eq!(question.prompt(), "59/7 (with remainder)");
+        assert_eq!(question.correct_answer(), "8 r 3");
+        assert!(question.is_answer_correct("8r3"));
+        assert!(!question.is_answer_correct("8"));
+        assert_eq!(question.key(), Some(&"division:59/7r".to_string()));
+
+        let question = Division::question(56, 7, Mode::Remainder);
+        assert!(question.is_answer_correct("8 r 0"));
+        assert!(question.is_answer_correct("8"));
+    }
+
+    #[test]
+    fn decimal_questions() {
+        let question = Division::question(59, 7, Mode::Decimal(2));
+        assert_eq!(question.prompt(), "59/7 (2 decimal places)");
+        assert_eq!(question.correct_answer(), "8.43");
+        assert!(question.is_answer_correct("8,43"));
+        assert_eq!(question.key(), Some(&"division:59/7d2".to_string()));
+
+        assert_eq!(Division::rounded_quotient(1, 8, 2), "0.13");
+        assert_eq!(Division::rounded_quotient(1, 3, 3), "0.333");
+        assert_eq!(Division::rounded_quotient(2, 3, 1), "0.7");
+        assert_eq!(Division::rounded_quotient(10, 2, 2), "5.00");
+        assert_eq!(
+            Division::rounded_quotient(u64::MAX, 1, 6),
+            format!("{}.000000", u64::MAX)
+        );
+    }
+
+    #[test]
+    fn question_from_key() {
+        let division =
+            Division::build(&to_args(&["--mode=remainder"])).expect("Should build correctly");
+        let question = division
+            .question_from_key("division:59/7r")
+            .expect("Key should be recognised");
+        assert_eq!(question.correct_answer(), "8 r 3");
+
+        assert!(division.question_from_key("division:59/7").is_none());
+        assert!(division.question_from_key("division:59/7d2").is_none());
+        assert!(division.question_from_key("division:59/11r").is_none());
+        assert!(division.question_from_key("division:5/7r").is_none());
+        assert!(division.question_from_key("division:59:7r").is_none());
+        assert!(division.question_from_key("times_table:7*8").is_none());
+
+        let division = Division::build(&to_args(&[])).expect("Should build correctly");
+        assert!(division.question_from_key("division:56/7").is_some());
+        assert!(division.question_from_key("division:59/7").is_none());
+
+        let division =
+            Division::build(&to_args(&["-m", "decimal"])).expect("Should build correctly");
+        assert!(division.question_from_key("division:59/7d2").is_some());
+        assert!(division.question_from_key("division:59/7d3").is_none());
+    }
+
+    #[test]
+    fn question_categories() {
+        let question = Division::question(56, 7, Mode::Exact);
+        assert_eq!(question.categories(), [Category::new("divisor", "7", 7)]);
+    }
+
+    #[test]
+    fn help_text() {
+        let division = Division::build(&to_args(&["-h"])).expect("Should build correctly");
+        assert!(division.wants_to_print_help());
+        let help_text = division.get_help_text();
+        assert!(help_text.contains("Division options"));
+        assert!(help_text.contains("Usage"));
+        assert!(help_text.contains("-m, --mode"));
+        assert!(help_text.contains("--decimal-places"));
+        assert!(help_text.contains("--upper-boundary-2"));
+    }
+}
@@ -1,20 +1,92 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{Datelike, Local, NaiveDate, Weekday};
+use std::cmp;
 use std::iter;
 
-use rand::Rng;
+use rand::{Rng, RngCore};
 
-use super::Base;
+use self::calendar::{Calendar, Date, System, GREGORIAN_REFORM};
+use self::date_format::{DateFormat, FORMAT_ISO};
+use self::weekday_answers::{
+    Language, WeekdayAnswers, WeekdayNumbers, LANGUAGE_ENGLISH, LANGUAGE_FRENCH, LANGUAGE_GERMAN,
+    LANGUAGE_POLISH, LANGUAGE_SPANISH, NUMBERS_MIXED, NUMBERS_MONDAY_1, NUMBERS_SUNDAY_0,
+    NUMBERS_SUNDAY_1,
+};
+use super::{check_year_range, question_id, question_key, Adjustment, Base, Level};
 use crate::application::APP_NAME;
 use crate::args::prelude::*;
-use crate::question::Question;
+use crate::question::{Category, Question};
+
+mod calendar;
+mod date_format;
+mod weekday_answers;
 
 pub const CMD: &str = "doomsday";
 
 const ARG_ID_HELP: &str = "help";
 const ARG_ID_LOWER_BOUNDARY: &str = "lower_boundary";
 const ARG_ID_UPPER_BOUNDARY: &str = "upper_boundary";
+const ARG_ID_MODE: &str = "mode";
+const ARG_ID_CALENDAR: &str = "calendar";
+const ARG_ID_ADOPTION_DATE: &str = "adoption_date";
+const ARG_ID_DATE_FORMAT: &str = "date_format";
+const ARG_ID_LANGUAGE: &str = "language";
+const ARG_ID_WEEKDAY_NUMBERS: &str = "weekday_numbers";
+
+const MODE_DATE: &str = "date";
+const MODE_CENTURY: &str = "century";
+const MODE_YEAR: &str = "year";
+const MODE_MONTH: &str = "month";
+
+const CALENDAR_GREGORIAN: &str = "gregorian";
+const CALENDAR_JULIAN: &str = "julian";
+const CALENDAR_HISTORICAL: &str = "historical";
+/// Adoption of the Gregorian calendar in Great Britain and its colonies
+const DEFAULT_ADOPTION_DATE: &str = "1752-09-14";
+
+pub(super) const GREGORIAN_CALENDAR_INTRODUCTION: i32 = GREGORIAN_REFORM.0;
+// Arbitrary, from Gregorian calendar adoption to ~400 years into the future
+const DYNAMIC_LOWER_BOUNDARY: i32 = 1753;
+const DYNAMIC_UPPER_BOUNDARY: i32 = 2617;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const CATEGORY_WEEKDAY: &str = "weekday";
+const CATEGORY_MONTH: &str = "month";
+const CATEGORY_CENTURY: &str = "century";
+// Adaptive mode starts with years around the current one and widens the range step by step
+const ADAPTIVE_INITIAL_YEARS: i32 = 10;
+const ADAPTIVE_STEP_YEARS: i32 = 20;
+/// Prefixes of question keys of the sub-drills, e.g. "doomsday:century-19"
+const KEY_PREFIX_CENTURY: &str = "century-";
+const KEY_PREFIX_YEAR: &str = "year-";
+const KEY_PREFIX_MONTH: &str = "month-";
+const KEY_SUFFIX_LEAP: &str = "-leap";
+const KEY_SUFFIX_COMMON: &str = "-common";
+/// Separates the date from the preset chosen in mixed date format, e.g. "2024-03-14/dmy"
+const KEY_SEPARATOR_PRESET: char = '/';
+
+/// Which step of the algorithm is practised
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// The weekday of a full date
+    Date,
+    /// The anchor day of a century
+    Century,
+    /// The doomsday of a year
+    Year,
+    /// The doomsday dates of a month
+    Month,
+}
 
-const GREGORIAN_CALENDAR_INTRODUCTION: i32 = 1582;
+impl Mode {
+    fn from_string(value: &str) -> Mode {
+        match value {
+            MODE_DATE => Mode::Date,
+            MODE_CENTURY => Mode::Century,
+            MODE_YEAR => Mode::Year,
+            MODE_MONTH => Mode::Month,
+            _ => panic!("incorrect value for Mode"),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Doomsday {
@@ -23,8 +95,15 @@ pub struct Doomsday {
 
     lower_boundary: i32,
     upper_boundary: i32,
+    mode: Mode,
+    calendar: Calendar,
+    date_format: DateFormat,
+    weekday_answers: WeekdayAnswers,
 
     default_boundaries: bool,
+
+    adaptive_centre: i32,
+    level: Level,
 }
 
 impl Doomsday {
@@ -45,16 +124,64 @@ impl Doomsday {
             &parsed_args,
             &arg_definitions,
         );
+        let mode =
+            String::set_value_from_arg_or_default(ARG_ID_MODE, &parsed_args, &arg_definitions);
+        let calendar =
+            String::set_value_from_arg_or_default(ARG_ID_CALENDAR, &parsed_args, &arg_definitions);
+        let adoption_date = String::set_value_from_arg_or_default(
+            ARG_ID_ADOPTION_DATE,
+            &parsed_args,
+            &arg_definitions,
+        );
+
+        let date_format = String::set_value_from_arg_or_default(
+            ARG_ID_DATE_FORMAT,
+            &parsed_args,
+            &arg_definitions,
+        );
+
+        let language =
+            String::set_value_from_arg_or_default(ARG_ID_LANGUAGE, &parsed_args, &arg_definitions);
+        let weekday_numbers = String::set_value_from_arg_or_default(
+            ARG_ID_WEEKDAY_NUMBERS,
+            &parsed_args,
+            &arg_definitions,
+        );
 
+        let calendar = Self::build_calendar(&calendar, &adoption_date)?;
+        let date_format =
+            DateFormat::build(&date_format).map_err(|err| Self::build_err_message(Some(err)))?;
         let default_boundaries =
-            Self::check_boundaries(lower_boundary, upper_boundary, &arg_definitions)?;
+            Self::check_boundaries(lower_boundary, upper_boundary, calendar, &arg_definitions)?;
+
+        let adaptive_centre = Local::now().year().clamp(lower_boundary, upper_boundary);
+        let max_distance = cmp::max(
+            adaptive_centre - lower_boundary,
+            upper_boundary - adaptive_centre,
+        );
+        let level = Level::new(
+            u32::try_from(
+                (max_distance - ADAPTIVE_INITIAL_YEARS + ADAPTIVE_STEP_YEARS - 1)
+                    / ADAPTIVE_STEP_YEARS,
+            )
+            .unwrap_or(0),
+        );
 
         Ok(Self {
             arg_definitions,
             show_help,
             lower_boundary,
             upper_boundary,
+            mode: Mode::from_string(&mode),
+            calendar,
+            date_format,
+            weekday_answers: WeekdayAnswers::new(
+                Language::from_string(&language),
+                WeekdayNumbers::from_string(&weekday_numbers),
+            ),
             default_boundaries,
+            adaptive_centre,
+            level,
         })
     }
 
@@ -75,12 +202,25 @@ impl Doomsday {
             "By default, the dates range ± 100-140 years from now, with a slight chance\n",
         );
         text.push_str(
-            "to go beyond that. Questions are presented in a form of YYYY-MM-DD, while\n",
+            "to go beyond that. Dates are presented as YYYY-MM-DD (see --date-format),\n",
+        );
+        text.push_str("while answers are expected in English ('Monday', 'Mon', 'Mo'; see\n");
+        text.push_str("--language) or as numbers (Monday - 1, Tuesday - 2, etc; see\n");
+        text.push_str("--weekday-numbers).\n");
+        text.push_str(
+            "\nEach step of the algorithm can be practised alone with --mode: the anchor day\n",
+        );
+        text.push_str(
+            "of a century, the doomsday of a year (e.g. with the 'odd + 11' or the '12s'\n",
         );
-        text.push_str("answers are expected in English ('Monday', 'Mon', 'Mo') or as numbers\n");
-        text.push_str("(Monday - 1, Tuesday - 2, etc).\n");
         text.push_str(
-            "\nNote: the algorithm works only for Gregorian calendar introduced during\n",
+            "method) or the doomsday dates of a month. Months are answered with a day of\n",
+        );
+        text.push_str(
+            "the month, e.g. '4' for April, and any doomsday of the month is accepted.\n",
+        );
+        text.push_str(
+            "\nNote: by default, dates are in the Gregorian calendar introduced during\n",
         );
         text.push_str(
             "Gregorian reform in 1582. Some countries did not adopt even until 2006, so\n",
@@ -89,7 +229,19 @@ impl Doomsday {
             "depending on where you live, weekdays of dates between 1582 and 2006 might be\n",
         );
         text.push_str(
-            "off (see https://en.wikipedia.org/wiki/Gregorian_calendar#Adoption_by_country).",
+            "off (see https://en.wikipedia.org/wiki/Gregorian_calendar#Adoption_by_country).\n",
+        );
+        text.push_str(
+            "Use '--calendar=julian' for dates before the reform or '--calendar=historical'\n",
+        );
+        text.push_str(
+            "with the --adoption-date of your country, e.g. 1918-02-14 for Russia. Julian\n",
+        );
+        text.push_str(
+            "dates are marked '(Julian)'. The algorithm works for them too, only the anchor\n",
+        );
+        text.push_str(
+            "days differ: they go back one day per century and repeat every 7 centuries.",
         );
 
         text
@@ -134,6 +286,109 @@ impl Doomsday {
                 .stop_parsing(false)
                 .default_value(ArgValue::Int(2115))
                 .build(),
+            Arg::builder()
+                .id(ARG_ID_MODE)
+                .short_name('m')
+                .long_name("mode")
+                .description(vec![
+                    "Set the step of the algorithm to practise:".to_string(),
+                    "date (the weekday of a full date), century".to_string(),
+                    "(the anchor day of a century), year (the".to_string(),
+                    "doomsday of a year) or month (the doomsday".to_string(),
+                    "dates of a month) (default: date).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    MODE_DATE.to_string(),
+                    MODE_CENTURY.to_string(),
+                    MODE_YEAR.to_string(),
+                    MODE_MONTH.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(MODE_DATE.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_CALENDAR)
+                .short_name('c')
+                .long_name("calendar")
+                .description(vec![
+                    "Set the calendar of the dates: gregorian".to_string(),
+                    "(years after 1582), julian or historical".to_string(),
+                    "(julian until --adoption-date, gregorian".to_string(),
+                    "since) (default: gregorian).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    CALENDAR_GREGORIAN.to_string(),
+                    CALENDAR_JULIAN.to_string(),
+                    CALENDAR_HISTORICAL.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(CALENDAR_GREGORIAN.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_ADOPTION_DATE)
+                .long_name("adoption-date")
+                .description(vec![
+                    "Set the first Gregorian date of the historical".to_string(),
+                    "calendar, e.g. 1918-02-14 for Russia".to_string(),
+                    format!("(default: {DEFAULT_ADOPTION_DATE}, Great Britain)."),
+                ])
+                .kind(ArgKind::Value(ValueKind::Str))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(DEFAULT_ADOPTION_DATE.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_DATE_FORMAT)
+                .short_name('f')
+                .long_name("date-format")
+                .description(vec![
+                    "Set the format of dates in questions: iso".to_string(),
+                    "(2024-03-14), dmy (14.03.2024), mdy".to_string(),
+                    "(03/14/2024), long (14 March 2024), mixed".to_string(),
+                    "(all of them) or a strftime pattern of".to_string(),
+                    "the year, the month and the day, e.g.".to_string(),
+                    "'%d/%m/%Y' (default: iso).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::Str))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(FORMAT_ISO.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_LANGUAGE)
+                .long_name("language")
+                .description(vec![
+                    "Set the language of weekday answers: en".to_string(),
+                    "(English), pl (Polish), de (German), fr".to_string(),
+                    "(French) or es (Spanish) (default: en).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    LANGUAGE_ENGLISH.to_string(),
+                    LANGUAGE_POLISH.to_string(),
+                    LANGUAGE_GERMAN.to_string(),
+                    LANGUAGE_FRENCH.to_string(),
+                    LANGUAGE_SPANISH.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(LANGUAGE_ENGLISH.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_WEEKDAY_NUMBERS)
+                .long_name("weekday-numbers")
+                .description(vec![
+                    "Set the numbers of weekday answers: mixed".to_string(),
+                    "(Monday 1 to Saturday 6, Sunday 0 or 7),".to_string(),
+                    "monday-1 (Monday 1 to Sunday 7), sunday-0".to_string(),
+                    "(Sunday 0 to Saturday 6) or sunday-1".to_string(),
+                    "(Sunday 1 to Saturday 7) (default: mixed).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    NUMBERS_MIXED.to_string(),
+                    NUMBERS_MONDAY_1.to_string(),
+                    NUMBERS_SUNDAY_0.to_string(),
+                    NUMBERS_SUNDAY_1.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(NUMBERS_MIXED.to_string()))
+                .build(),
         ]
     }
 
@@ -152,16 +407,41 @@ impl Doomsday {
         }
     }
 
+    fn build_calendar(calendar: &str, adoption_date: &str) -> Result<Calendar, String> {
+        match calendar {
+            CALENDAR_GREGORIAN => Ok(Calendar::Gregorian),
+            CALENDAR_JULIAN => Ok(Calendar::Julian),
+            CALENDAR_HISTORICAL => NaiveDate::parse_from_str(adoption_date, DATE_FORMAT)
+                .map_err(|_| {
+                    Self::build_err_message(Some(format!(
+                        "invalid adoption date '{adoption_date}'; expected YYYY-MM-DD"
+                    )))
+                })
+                .and_then(|date| {
+                    Calendar::historical(date).ok_or_else(|| {
+                        let (year, month, day) = GREGORIAN_REFORM;
+                        Self::build_err_message(Some(format!(
+                            "adoption date cannot be before the Gregorian reform \
+                             ({year}-{month:02}-{day:02})"
+                        )))
+                    })
+                }),
+            _ => panic!("incorrect value for Calendar"),
+        }
+    }
+
     fn check_boundaries(
         lower_boundary: i32,
         upper_boundary: i32,
+        calendar: Calendar,
         arg_definitions: &[Arg]
     ) -> Result<bool, String> {
-        if lower_boundary > upper_boundary {
-            return Err(Self::build_err_message(Some(
-                "lower boundary must be less than or equal to upper boundary".to_string(),
-            )));
-        }
+        check_year_range(
+            lower_boundary,
+            upper_boundary,
+            (calendar == Calendar::Gregorian).then_some("Doomsday algorithm"),
+        )
+        .map_err(|err| Self::build_err_message(Some(err)))?;
 
         let default_lower_boundary = arg_definitions
             .iter()
@@ -179,55 +459,149 @@ impl Doomsday {
         let default_boundaries = lower_boundary.to_string() == default_lower_boundary
             && upper_boundary.to_string() == default_upper_boundary;
 
-        if lower_boundary <= GREGORIAN_CALENDAR_INTRODUCTION {
-            return Err(Self::build_err_message(Some(
-                format!("year boundary too low; Doomsday algorithm does not work for dates on {GREGORIAN_CALENDAR_INTRODUCTION} and before")
-            )));
-        }
-
-        if NaiveDate::from_ymd_opt(upper_boundary, 12, 31).is_none() {
-            return Err(Self::build_err_message(Some(
-                "year boundaries cannot exceed 262142".to_string(), // Limitation of NaiveDate
-            )));
-        }
-
         Ok(default_boundaries)
     }
 
-    fn generate_question(&self) -> Question {
-        let (year_from, year_to) = self.calculate_year_range();
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let (year_from, year_to) = self.calculate_year_range(rng);
 
-        let date_from = NaiveDate::from_ymd_opt(year_from, 1, 1)
-            .unwrap() // checked in build()
-            .num_days_from_ce();
-        let date_to = NaiveDate::from_ymd_opt(year_to, 12, 31)
-            .unwrap()
-            .num_days_from_ce();
+        let date_from = self.calendar.year_start(year_from);
+        let date_to = self.calendar.year_end(year_to);
 
-        let mut rng = rand::thread_rng();
         let date = rng.gen_range(date_from..=date_to);
-        let date = NaiveDate::from_num_days_from_ce_opt(date).unwrap();
+        let date = self.calendar.date_from_days(date);
+
+        match self.mode {
+            Mode::Date => Question::from_date(
+                date,
+                self.calendar,
+                &self.date_format,
+                self.date_format.choose_preset(rng),
+                self.weekday_answers,
+            ),
+            Mode::Century => Question::from_century(
+                date.year().div_euclid(100),
+                self.calendar,
+                self.weekday_answers,
+            ),
+            Mode::Year => Question::from_year(date.year(), self.calendar, self.weekday_answers),
+            Mode::Month => Question::from_month(date.month(), date.leap_year()),
+        }
+    }
+
+    /// Removes the calendar suffix from a key id of a date, a century or a year. Gregorian
+    /// keys have no suffix, so keys of other calendars fail to parse afterwards.
+    fn strip_calendar<'a>(&self, id: &'a str) -> Option<&'a str> {
+        id.strip_suffix(&self.calendar.key_suffix())
+    }
 
-        Question::from_date(date)
+    /// Recreates a sub-drill question from its key id, e.g. "year-2024"
+    fn sub_drill_question(&self, id: &str, year_from: i32, year_to: i32) -> Option<Question> {
+        if let Some(century) = id.strip_prefix(KEY_PREFIX_CENTURY) {
+            let century = self.strip_calendar(century)?.parse::<i32>().ok()?;
+            let in_range = (year_from.div_euclid(100)..=year_to.div_euclid(100)).contains(&century);
+            (self.mode == Mode::Century && in_range)
+                .then(|| Question::from_century(century, self.calendar, self.weekday_answers))
+        } else if let Some(year) = id.strip_prefix(KEY_PREFIX_YEAR) {
+            let year = self.strip_calendar(year)?.parse::<i32>().ok()?;
+            (self.mode == Mode::Year && (year_from..=year_to).contains(&year))
+                .then(|| Question::from_year(year, self.calendar, self.weekday_answers))
+        } else if let Some(month) = id.strip_prefix(KEY_PREFIX_MONTH) {
+            let (month, leap_year) = match month.strip_suffix(KEY_SUFFIX_LEAP) {
+                Some(month) => (month, true),
+                None => (
+                    month.strip_suffix(KEY_SUFFIX_COMMON).unwrap_or(month),
+                    false,
+                ),
+            };
+            let month = month.parse::<u32>().ok()?;
+            if self.mode != Mode::Month || !(1..=12).contains(&month) {
+                return None;
+            }
+            // The leap year suffix is used only (and always) for January and February
+            let question = Question::from_month(month, leap_year);
+            (question.key() == Some(&question_key(CMD, id))).then_some(question)
+        } else {
+            None
+        }
     }
 
-    fn calculate_year_range(&self) -> (i32, i32) {
-        let mut rng = rand::thread_rng();
+    fn calculate_year_range(&self, rng: &mut dyn RngCore) -> (i32, i32) {
+        if self.level.get() < self.level.max {
+            return self.adaptive_year_range();
+        }
+
         if self.default_boundaries && rng.gen_range(0..100) < 8 {
-            (1753, 2617) // Arbitrary, from Gregorian calendar adoption to ~400 years into the future
+            (DYNAMIC_LOWER_BOUNDARY, DYNAMIC_UPPER_BOUNDARY)
         } else {
             (self.lower_boundary, self.upper_boundary)
         }
     }
+
+    /// Years around the current one (clamped to boundaries) at the current difficulty level
+    fn adaptive_year_range(&self) -> (i32, i32) {
+        let level = i32::try_from(self.level.get()).unwrap_or(i32::MAX);
+        let distance = level
+            .saturating_mul(ADAPTIVE_STEP_YEARS)
+            .saturating_add(ADAPTIVE_INITIAL_YEARS);
+        (
+            cmp::max(
+                self.adaptive_centre.saturating_sub(distance),
+                self.lower_boundary,
+            ),
+            cmp::min(
+                self.adaptive_centre.saturating_add(distance),
+                self.upper_boundary,
+            ),
+        )
+    }
 }
 
 impl Base for Doomsday {
-    fn generate_questions(&self, count: u32) -> Vec<Question> {
-        iter::repeat_with(|| self.generate_question())
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
             .take(count as usize)
             .collect()
     }
 
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let id = question_id(CMD, key)?;
+        let (year_from, year_to) = if self.default_boundaries {
+            (DYNAMIC_LOWER_BOUNDARY, DYNAMIC_UPPER_BOUNDARY)
+        } else {
+            (self.lower_boundary, self.upper_boundary)
+        };
+        if self.mode != Mode::Date {
+            return self.sub_drill_question(id, year_from, year_to);
+        }
+        // Keys without a known preset are shown in ISO format in mixed date format
+        let (id, preset) = match id.split_once(KEY_SEPARATOR_PRESET) {
+            Some((id, preset)) => (id, self.date_format.find_preset(preset)),
+            None => (id, None),
+        };
+        let date = self.calendar.parse(self.strip_calendar(id)?)?;
+        if (year_from..=year_to).contains(&date.year()) {
+            Some(Question::from_date(
+                date,
+                self.calendar,
+                &self.date_format,
+                preset,
+                self.weekday_answers,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn adapt(&self, adjustment: Adjustment) -> bool {
+        self.level.adjust(adjustment)
+    }
+
+    fn current_range(&self) -> Option<String> {
+        let (year_from, year_to) = self.adaptive_year_range();
+        Some(format!("years {year_from}-{year_to}"))
+    }
+
     fn wants_to_print_help(&self) -> bool {
         self.show_help
     }
@@ -244,57 +618,336 @@ impl Base for Doomsday {
     }
 }
 
+/// Month days that are doomsdays, the easiest to remember first (e.g. 4/4, 6/6, 9/5 "9 to 5"),
+/// indexed by month number - 1. Leap years differ only in January and February.
+const DOOMSDAYS: [u32; 12] = [3, 28, 14, 4, 9, 6, 11, 8, 5, 10, 7, 12];
+const DOOMSDAYS_LEAP_YEAR: [u32; 2] = [4, 29];
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
 impl Question {
-    fn from_date(date: NaiveDate) -> Question {
-        let (answer, alternative_answers) = match date.weekday() {
-            Weekday::Mon => (
-                "monday".to_string(),
-                vec!["mo".to_string(), "mon".to_string(), 1.to_string()],
+    /// Question answered with a weekday
+    fn with_weekday_answer(
+        prompt: &str,
+        weekday: Weekday,
+        answers: WeekdayAnswers,
+        id: &str,
+        categories: &[Category],
+        explanation: &[String],
+    ) -> Question {
+        let (answer, alternative_answers) = answers.answers(weekday);
+        Question::builder()
+            .question(prompt)
+            .answer(&answer)
+            .alternative_answers(&alternative_answers)
+            .allow_any_case(true)
+            .key(&question_key(CMD, id))
+            .categories(categories)
+            .explanation(&explanation.join("\n"))
+            .build()
+    }
+
+    fn weekday_name(weekday: Weekday) -> &'static str {
+        WEEKDAY_NAMES[weekday.num_days_from_monday() as usize]
+    }
+
+    fn add_days(weekday: Weekday, days: i64) -> Weekday {
+        (0..days.rem_euclid(7)).fold(weekday, |day, _| day.succ())
+    }
+
+    fn month_name(month: u32) -> String {
+        NaiveDate::from_ymd_opt(2000, month, 1)
+            .expect("month is between 1 and 12")
+            .format("%B")
+            .to_string()
+    }
+
+    /// The most memorable doomsday of the month
+    fn month_doomsday(month: u32, leap_year: bool) -> u32 {
+        let index = month as usize - 1;
+        if leap_year && month <= 2 {
+            DOOMSDAYS_LEAP_YEAR[index]
+        } else {
+            DOOMSDAYS[index]
+        }
+    }
+
+    /// Marks Julian dates, centuries and years in prompts and explanations
+    fn system_suffix(system: System) -> &'static str {
+        match system {
+            System::Gregorian => "",
+            System::Julian => " (Julian)",
+        }
+    }
+
+    /// Steps from the anchor day to the doomsday of `year`
+    fn explain_year(year: i32, system: System) -> Vec<String> {
+        let century = year.div_euclid(100);
+        let anchor = Self::doomsday(century * 100, system);
+        let y = year.rem_euclid(100);
+
+        let (twelves, remainder) = (y / 12, y % 12);
+        let quarter = remainder / 4;
+        let offset = twelves + remainder + quarter;
+        let twelves_method = format!(
+            "Year {y} (12s method): {y} / 12 = {twelves} remainder {remainder}, \
+             {remainder} / 4 = {quarter}, {twelves} + {remainder} + {quarter} = {offset}"
+        );
+
+        let mut steps = vec![];
+        let mut t = y;
+        if t % 2 == 1 {
+            steps.push(format!("{t} + 11 = {}", t + 11));
+            t += 11;
+        }
+        steps.push(format!("{t} / 2 = {}", t / 2));
+        t /= 2;
+        if t % 2 == 1 {
+            steps.push(format!("{t} + 11 = {}", t + 11));
+            t += 11;
+        }
+        steps.push(format!("7 - {t} mod 7 = {}", 7 - t % 7));
+        let odd_plus_11_method = format!("Year {y} (odd + 11 method): {}", steps.join(", "));
+
+        vec![
+            format!(
+                "Anchor day of the {century}00s{}: {}",
+                Self::system_suffix(system),
+                Self::weekday_name(anchor)
             ),
-            Weekday::Tue => (
-                "tuesday".to_string(),
-                vec!["tu".to_string(), "tue".to_string(), 2.to_string()],
+            twelves_method,
+            odd_plus_11_method,
+            format!(
+                "Doomsday of {year}: {} + {offset} = {}",
+                Self::weekday_name(anchor),
+                Self::weekday_name(Self::add_days(anchor, offset.into()))
             ),
-            Weekday::Wed => (
-                "wednesday".to_string(),
-                vec!["we".to_string(), "wed".to_string(), 3.to_string()],
+        ]
+    }
+
+    /// The doomsday of `year` in `system`, i.e. the weekday of 4/4, 6/6, 8/8 etc.
+    fn doomsday(year: i32, system: System) -> Weekday {
+        system
+            .date(year, 4, 4)
+            .expect("4/4 exists in every year")
+            .weekday()
+    }
+
+    fn century_category(century: i32) -> Category {
+        Category::new(CATEGORY_CENTURY, &format!("{century}00s"), century.into())
+    }
+
+    /// `calendar` tells the key apart from the same date in other calendars. The key is
+    /// the same in every `date_format`, apart from the `preset` chosen in mixed format.
+    fn from_date(
+        date: Date,
+        calendar: Calendar,
+        date_format: &DateFormat,
+        preset: Option<&str>,
+        answers: WeekdayAnswers,
+    ) -> Question {
+        let century = date.year().div_euclid(100);
+        let categories = [
+            Category::new(
+                CATEGORY_WEEKDAY,
+                Self::weekday_name(date.weekday()),
+                date.weekday().num_days_from_monday().into(),
             ),
-            Weekday::Thu => (
-                "thursday".to_string(),
-                vec!["th".to_string(), "thu".to_string(), 4.to_string()],
+            Category::new(
+                CATEGORY_MONTH,
+                &Self::month_name(date.month()),
+                date.month().into(),
             ),
-            Weekday::Fri => (
-                "friday".to_string(),
-                vec!["fr".to_string(), "fri".to_string(), 5.to_string()],
+            Self::century_category(century),
+        ];
+        let mut explanation = Self::explain_year(date.year(), date.system());
+        explanation.extend(Self::explain_day(date));
+        Self::with_weekday_answer(
+            &format!(
+                "What is the weekday of {}{}?",
+                date_format.format(&date, preset),
+                Self::system_suffix(date.system())
             ),
-            Weekday::Sat => (
-                "saturday".to_string(),
-                vec!["sa".to_string(), "sat".to_string(), 6.to_string()],
+            date.weekday(),
+            answers,
+            &match preset {
+                Some(preset) => format!(
+                    "{date}{}{KEY_SEPARATOR_PRESET}{preset}",
+                    calendar.key_suffix()
+                ),
+                None => format!("{date}{}", calendar.key_suffix()),
+            },
+            &categories,
+            &explanation,
+        )
+    }
+
+    /// Steps from the doomsday of the year to the weekday of `date`
+    fn explain_day(date: Date) -> Vec<String> {
+        let doomsday = Self::doomsday(date.year(), date.system());
+        let leap_year = date.leap_year();
+        let month = Self::month_name(date.month());
+        let day = i64::from(date.day());
+        let memorable = i64::from(Self::month_doomsday(date.month(), leap_year));
+        // Doomsdays of the month are a multiple of 7 days apart
+        let mut closest = memorable + (day - memorable + 3).div_euclid(7) * 7;
+        if closest < 1 {
+            closest += 7;
+        } else if u32::try_from(closest)
+            .ok()
+            .and_then(|day| date.with_day(day))
+            .is_none()
+        {
+            closest -= 7;
+        }
+        let year_kind = match (date.month(), leap_year) {
+            (1 | 2, true) => " (leap year)",
+            (1 | 2, false) => " (common year)",
+            _ => "",
+        };
+        let offset = day - closest;
+        let doomsday_name = Self::weekday_name(doomsday);
+        let weekday_name = Self::weekday_name(date.weekday());
+        let last_step = match offset {
+            0 => format!("{month} {day} is a doomsday: {weekday_name}"),
+            1.. => format!(
+                "{month} {day} is {offset} days after: {doomsday_name} + {offset} = {weekday_name}"
             ),
-            Weekday::Sun => (
-                "sunday".to_string(),
-                vec![
-                    "su".to_string(),
-                    "sun".to_string(),
-                    0.to_string(),
-                    7.to_string(),
-                ],
+            _ => format!(
+                "{month} {day} is {} days before: {doomsday_name} - {} = {weekday_name}",
+                -offset, -offset
             ),
         };
+        vec![
+            format!("Closest doomsday: {month} {closest}{year_kind}"),
+            last_step,
+        ]
+    }
+
+    /// The anchor day of a century is the doomsday of its first year
+    fn from_century(century: i32, calendar: Calendar, answers: WeekdayAnswers) -> Question {
+        let system = calendar.doomsday_system(century * 100);
+        let anchor = Self::doomsday(century * 100, system);
+        let explanation = match system {
+            System::Gregorian => {
+                let anchors: Vec<_> = (0..4)
+                    .map(|i| Self::weekday_name(Self::doomsday(2000 + i * 100, system)))
+                    .collect();
+                [
+                    format!(
+                        "Anchor days repeat every 4 centuries: {} (2000s), {} (2100s), \
+                         {} (2200s), {} (2300s)",
+                        anchors[0], anchors[1], anchors[2], anchors[3]
+                    ),
+                    format!(
+                        "{century} mod 4 = {}: {}",
+                        century.rem_euclid(4),
+                        Self::weekday_name(anchor)
+                    ),
+                ]
+            }
+            System::Julian => {
+                let base = Self::weekday_name(Self::doomsday(0, system));
+                let remainder = century.rem_euclid(7);
+                [
+                    format!(
+                        "Julian anchor days repeat every 7 centuries and go back one day \
+                         each century: {base} (0s, 700s, 1400s)"
+                    ),
+                    format!(
+                        "{century} mod 7 = {remainder}: {base} - {remainder} = {}",
+                        Self::weekday_name(anchor)
+                    ),
+                ]
+            }
+        };
+        Self::with_weekday_answer(
+            &format!(
+                "What is the anchor day of the {century}00s{}?",
+                Self::system_suffix(system)
+            ),
+            anchor,
+            answers,
+            &format!("{KEY_PREFIX_CENTURY}{century}{}", calendar.key_suffix()),
+            &[Self::century_category(century)],
+            &explanation,
+        )
+    }
+
+    fn from_year(year: i32, calendar: Calendar, answers: WeekdayAnswers) -> Question {
+        let system = calendar.doomsday_system(year);
+        Self::with_weekday_answer(
+            &format!(
+                "What is the doomsday of {year}{}?",
+                Self::system_suffix(system)
+            ),
+            Self::doomsday(year, system),
+            answers,
+            &format!("{KEY_PREFIX_YEAR}{year}{}", calendar.key_suffix()),
+            &[Self::century_category(year.div_euclid(100))],
+            &Self::explain_year(year, system),
+        )
+    }
+
+    /// Any day of the month falling on the doomsday is accepted. Only January and February
+    /// depend on `leap_year`.
+    fn from_month(month: u32, leap_year: bool) -> Question {
+        let name = Self::month_name(month);
+        let doomsday = Self::month_doomsday(month, leap_year);
+        let (prompt, id) = if month <= 2 {
+            let (year_kind, suffix) = if leap_year {
+                ("a leap", KEY_SUFFIX_LEAP)
+            } else {
+                ("a common", KEY_SUFFIX_COMMON)
+            };
+            (
+                format!("Which day of {name} is a doomsday in {year_kind} year?"),
+                format!("{KEY_PREFIX_MONTH}{month:02}{suffix}"),
+            )
+        } else {
+            (
+                format!("Which day of {name} is a doomsday?"),
+                format!("{KEY_PREFIX_MONTH}{month:02}"),
+            )
+        };
+        let explanation = [
+            "Memorable doomsdays: 4/4, 6/6, 8/8, 10/10, 12/12, 5/9 and 9/5, 7/11 and 11/7,"
+                .to_string(),
+            "3/14 (Pi Day), 1/3 and 2/28 (1/4 and 2/29 in leap years)".to_string(),
+            "Other doomsdays of the month are a multiple of 7 days away".to_string(),
+        ];
+        let alternative_answers: Vec<_> = (1..=31)
+            .filter(|&day| day != doomsday && day % 7 == doomsday % 7)
+            .filter(|&day| {
+                // 2000 is a leap year, 2001 is not
+                let year = if leap_year { 2000 } else { 2001 };
+                NaiveDate::from_ymd_opt(year, month, day).is_some()
+            })
+            .map(|day| day.to_string())
+            .collect();
         Question::builder()
-            .question(&format!(
-                "What is the weekday of {}?",
-                date.format("%Y-%m-%d")
-            ))
-            .answer(&answer)
+            .question(&prompt)
+            .answer(&doomsday.to_string())
             .alternative_answers(&alternative_answers)
-            .allow_any_case(true)
+            .key(&question_key(CMD, &id))
+            .categories(&[Category::new(CATEGORY_MONTH, &name, month.into())])
+            .explanation(&explanation.join("\n"))
             .build()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
     use super::*;
 
     #[test]
@@ -348,7 +1001,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "does not work for dates on 1582 and before")]
+    #[should_panic(expected = "Doomsday algorithm does not work for dates on 1582 and before")]
     fn build_doomsday_year_too_low() {
         let args = ["-l".to_string(), "1582".to_string()];
         Doomsday::build(&args).unwrap();
@@ -381,8 +1034,508 @@ mod tests {
             "2000".to_string(),
         ];
         let doomsday = Doomsday::build(&args).expect("Should build correctly");
-        let question = doomsday.generate_question();
+        let question = doomsday.generate_question(&mut rand::thread_rng());
         assert!(question.prompt().contains("2000-"));
+        assert!(question
+            .key()
+            .is_some_and(|key| key.starts_with("doomsday:2000-")));
+    }
+
+    #[test]
+    fn question_from_key() {
+        let args = [
+            "-l".to_string(),
+            "2000".to_string(),
+            "-u".to_string(),
+            "2030".to_string(),
+        ];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+
+        let question = doomsday
+            .question_from_key("doomsday:2024-03-14")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "What is the weekday of 2024-03-14?");
+        assert_eq!(question.correct_answer(), "thursday");
+        assert_eq!(
+            question.categories(),
+            [
+                Category::new("weekday", "Thursday", 3),
+                Category::new("month", "March", 3),
+                Category::new("century", "2000s", 20)
+            ]
+        );
+
+        assert!(doomsday.question_from_key("doomsday:1999-12-31").is_none());
+        assert!(doomsday.question_from_key("doomsday:2024-02-30").is_none());
+        assert!(doomsday.question_from_key("powers:2^3").is_none());
+
+        // Dynamic range is used with default boundaries
+        let doomsday = Doomsday::build(&[]).expect("Should build correctly");
+        assert!(doomsday.question_from_key("doomsday:1800-01-01").is_some());
+        assert!(doomsday.question_from_key("doomsday:1700-01-01").is_none());
+    }
+
+    #[test]
+    fn century_questions() {
+        let args = ["-m".to_string(), "century".to_string()];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:century-19")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "What is the anchor day of the 1900s?");
+        assert_eq!(question.correct_answer(), "wednesday");
+        assert_eq!(
+            question.categories(),
+            [Category::new("century", "1900s", 19)]
+        );
+        assert_eq!(
+            Question::from_century(20, Calendar::Gregorian, WeekdayAnswers::default())
+                .correct_answer(),
+            "tuesday"
+        );
+        assert_eq!(
+            Question::from_century(21, Calendar::Gregorian, WeekdayAnswers::default())
+                .correct_answer(),
+            "sunday"
+        );
+        assert_eq!(
+            Question::from_century(18, Calendar::Gregorian, WeekdayAnswers::default())
+                .correct_answer(),
+            "friday"
+        );
+
+        assert!(doomsday.question_from_key("doomsday:century-16").is_none());
+        assert!(doomsday.question_from_key("doomsday:year-2024").is_none());
+        assert!(doomsday.question_from_key("doomsday:2024-03-14").is_none());
+        assert!(doomsday
+            .generate_questions(10, &mut rand::thread_rng())
+            .iter()
+            .all(|question| question.prompt().starts_with("What is the anchor day")));
+    }
+
+    #[test]
+    fn year_questions() {
+        let args = ["--mode=year".to_string()];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:year-2024")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "What is the doomsday of 2024?");
+        assert_eq!(question.correct_answer(), "thursday");
+        assert!(question.is_answer_correct("Thu"));
+        assert!(question.is_answer_correct("4"));
+        assert_eq!(
+            Question::from_year(1999, Calendar::Gregorian, WeekdayAnswers::default())
+                .correct_answer(),
+            "sunday"
+        );
+
+        assert!(doomsday.question_from_key("doomsday:year-1700").is_none());
+        assert!(doomsday.question_from_key("doomsday:century-20").is_none());
+    }
+
+    #[test]
+    fn month_questions() {
+        let args = ["--mode=month".to_string()];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:month-04")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "Which day of April is a doomsday?");
+        assert_eq!(question.correct_answer(), "4");
+        for day in ["4", "11", "18", "25"] {
+            assert!(question.is_answer_correct(day));
+        }
+        assert!(!question.is_answer_correct("5"));
+        assert!(!question.is_answer_correct("32"));
+        assert_eq!(question.categories(), [Category::new("month", "April", 4)]);
+
+        let question = Question::from_month(1, true);
+        assert_eq!(
+            question.prompt(),
+            "Which day of January is a doomsday in a leap year?"
+        );
+        assert_eq!(question.correct_answer(), "4");
+        assert!(question.is_answer_correct("25"));
+        assert!(!question.is_answer_correct("31"));
+        assert_eq!(question.key(), Some(&"doomsday:month-01-leap".to_string()));
+
+        let question = Question::from_month(2, false);
+        assert_eq!(question.correct_answer(), "28");
+        assert!(question.is_answer_correct("7"));
+        assert!(!question.is_answer_correct("29"));
+        assert_eq!(
+            question.key(),
+            Some(&"doomsday:month-02-common".to_string())
+        );
+
+        // Every accepted day is the doomsday of a year of that kind
+        for (year, leap_year) in [(2023, false), (2024, true)] {
+            let doomsday_weekday = Question::doomsday(year, System::Gregorian);
+            for month in 1..=12 {
+                let question = Question::from_month(month, leap_year);
+                for day in (1..=31).filter(|day| question.is_answer_correct(&day.to_string())) {
+                    let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+                    assert_eq!(date.weekday(), doomsday_weekday);
+                }
+            }
+        }
+
+        assert!(doomsday
+            .question_from_key("doomsday:month-01-leap")
+            .is_some());
+        assert!(doomsday.question_from_key("doomsday:month-01").is_none());
+        assert!(doomsday
+            .question_from_key("doomsday:month-04-leap")
+            .is_none());
+        assert!(doomsday.question_from_key("doomsday:month-13").is_none());
+    }
+
+    #[test]
+    fn calendars() {
+        let args = [
+            "-c".to_string(),
+            "julian".to_string(),
+            "-l".to_string(),
+            "1".to_string(),
+        ];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        assert_eq!(doomsday.calendar, Calendar::Julian);
+        let question = doomsday
+            .question_from_key("doomsday:1582-10-04@julian")
+            .expect("Key should be recognised");
+        assert_eq!(
+            question.prompt(),
+            "What is the weekday of 1582-10-04 (Julian)?"
+        );
+        assert_eq!(question.correct_answer(), "thursday");
+        assert!(doomsday.question_from_key("doomsday:1582-10-04").is_none());
+        assert!(doomsday
+            .question_from_key("doomsday:1582-10-04@1752-09-14")
+            .is_none());
+        assert!(doomsday
+            .generate_questions(10, &mut rand::thread_rng())
+            .iter()
+            .all(|question| question.prompt().ends_with("(Julian)?")));
+
+        let args = [
+            "--calendar=historical".to_string(),
+            "-l".to_string(),
+            "1700".to_string(),
+            "-u".to_string(),
+            "1800".to_string(),
+        ];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:1752-09-02@1752-09-14")
+            .expect("Key should be recognised");
+        assert_eq!(
+            question.prompt(),
+            "What is the weekday of 1752-09-02 (Julian)?"
+        );
+        assert_eq!(question.correct_answer(), "wednesday");
+        let question = doomsday
+            .question_from_key("doomsday:1752-09-14@1752-09-14")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "What is the weekday of 1752-09-14?");
+        assert_eq!(question.correct_answer(), "thursday");
+        assert!(doomsday
+            .question_from_key("doomsday:1752-09-10@1752-09-14")
+            .is_none());
+
+        let args = [
+            "--calendar=historical".to_string(),
+            "--adoption-date=1918-02-14".to_string(),
+            "--mode=year".to_string(),
+        ];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:year-1900@1918-02-14")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "What is the doomsday of 1900 (Julian)?");
+        assert_eq!(question.correct_answer(), "tuesday");
+        let question = doomsday
+            .question_from_key("doomsday:year-2024@1918-02-14")
+            .expect("Key should be recognised");
+        assert_eq!(question.correct_answer(), "thursday");
+        assert!(doomsday.question_from_key("doomsday:year-2024").is_none());
+    }
+
+    #[test]
+    fn localised_answers() {
+        let args = [
+            "--language=pl".to_string(),
+            "--weekday-numbers=sunday-1".to_string(),
+        ];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:2024-03-13")
+            .expect("Key should be recognised");
+        assert_eq!(question.correct_answer(), "środa");
+        assert!(question.is_answer_correct("Środa"));
+        assert!(question.is_answer_correct("sr"));
+        assert!(question.is_answer_correct("4"));
+        assert!(!question.is_answer_correct("3"));
+        assert!(!question.is_answer_correct("wednesday"));
+
+        let args = [
+            "--language=de".to_string(),
+            "-m".to_string(),
+            "year".to_string(),
+        ];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:year-2024")
+            .expect("Key should be recognised");
+        assert_eq!(question.correct_answer(), "donnerstag");
+
+        let args = ["--language=la".to_string()];
+        let err = Doomsday::build(&args).unwrap_err();
+        assert!(err.contains("invalid option argument"));
+    }
+
+    #[test]
+    fn date_formats() {
+        let args = ["-f".to_string(), "long".to_string()];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:2024-03-14")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "What is the weekday of 14 March 2024?");
+        assert_eq!(question.key(), Some(&"doomsday:2024-03-14".to_string()));
+
+        let args = [
+            "--date-format=%d/%m/%Y".to_string(),
+            "-c".to_string(),
+            "julian".to_string(),
+        ];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:2024-03-01@julian")
+            .expect("Key should be recognised");
+        assert_eq!(
+            question.prompt(),
+            "What is the weekday of 01/03/2024 (Julian)?"
+        );
+
+        let args = ["-f".to_string(), "mixed".to_string()];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:2024-03-14/long")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "What is the weekday of 14 March 2024?");
+        assert_eq!(
+            question.key(),
+            Some(&"doomsday:2024-03-14/long".to_string())
+        );
+        let question = doomsday
+            .question_from_key("doomsday:2024-03-14")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "What is the weekday of 2024-03-14?");
+        assert_eq!(question.key(), Some(&"doomsday:2024-03-14".to_string()));
+        let mut rng = StdRng::seed_from_u64(7);
+        let prompts: Vec<_> = doomsday
+            .generate_questions(20, &mut rng)
+            .iter()
+            .map(|question| {
+                // The chosen preset is recorded, so the question can be recreated as asked
+                let recreated = doomsday.question_from_key(question.key().unwrap()).unwrap();
+                assert_eq!(recreated.prompt(), question.prompt());
+                question.prompt().clone()
+            })
+            .collect();
+        assert!(prompts.iter().any(|prompt| prompt.contains('/')));
+        assert!(prompts.iter().any(|prompt| prompt.contains('.')));
+        // The preset is ignored if the format is not mixed
+        let doomsday = Doomsday::build(&[]).expect("Should build correctly");
+        let question = doomsday
+            .question_from_key("doomsday:2024-03-14/long")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "What is the weekday of 2024-03-14?");
+        assert_eq!(question.key(), Some(&"doomsday:2024-03-14".to_string()));
+
+        let args = ["--date-format=%A %d/%m/%Y".to_string()];
+        let err = Doomsday::build(&args).unwrap_err();
+        assert!(err.contains("can contain only the year, the month and the day"));
+        assert!(err.contains("Usage"));
+    }
+
+    #[test]
+    fn build_doomsday_incorrect_calendar() {
+        let build = |args: &[&str]| {
+            let args: Vec<_> = args.iter().map(ToString::to_string).collect();
+            Doomsday::build(&args)
+        };
+        let err = build(&["-c", "julian", "-l", "0"]).unwrap_err();
+        assert!(err.contains("years before 1 are not supported"));
+        let err = build(&["-c", "historical", "--adoption-date=1752"]).unwrap_err();
+        assert!(err.contains("invalid adoption date '1752'"));
+        let err = build(&["-c", "historical", "--adoption-date=1582-10-14"]).unwrap_err();
+        assert!(err.contains("cannot be before the Gregorian reform (1582-10-15)"));
+        let err = build(&["-c", "hebrew"]).unwrap_err();
+        assert!(err.contains("invalid option argument"));
+        // The adoption date is ignored by the other calendars
+        assert!(build(&["--adoption-date=1752"]).is_ok());
+    }
+
+    #[test]
+    fn julian_anchor_days() {
+        let question = Question::from_century(15, Calendar::Julian, WeekdayAnswers::default());
+        assert_eq!(
+            question.prompt(),
+            "What is the anchor day of the 1500s (Julian)?"
+        );
+        assert_eq!(question.correct_answer(), "saturday");
+        assert_eq!(
+            question.key(),
+            Some(&"doomsday:century-15@julian".to_string())
+        );
+        let explanation = question.explanation().unwrap().clone();
+        assert!(explanation.contains("Sunday (0s, 700s, 1400s)"));
+        assert!(explanation.ends_with("15 mod 7 = 1: Sunday - 1 = Saturday"));
+
+        for century in 0..30 {
+            let question =
+                Question::from_century(century, Calendar::Julian, WeekdayAnswers::default());
+            let explanation = question.explanation().unwrap().clone();
+            let anchor = Question::weekday_name(Question::doomsday(century * 100, System::Julian));
+            assert!(explanation.ends_with(anchor));
+        }
+        for year in 1500..1600 {
+            let explanation = Question::explain_year(year, System::Julian);
+            let doomsday = Question::weekday_name(Question::doomsday(year, System::Julian));
+            assert!(explanation[0].starts_with("Anchor day of the 1500s (Julian): Saturday"));
+            assert!(explanation[3].ends_with(&format!("= {doomsday}")));
+        }
+    }
+
+    #[test]
+    fn date_explanation() {
+        let iso = DateFormat::build(FORMAT_ISO).unwrap();
+        let date = Calendar::Gregorian.date(2024, 3, 12).unwrap();
+        let question = Question::from_date(
+            date,
+            Calendar::Gregorian,
+            &iso,
+            None,
+            WeekdayAnswers::default(),
+        );
+        let explanation = question.explanation().expect("Should be explained");
+        assert_eq!(
+            explanation,
+            "Anchor day of the 2000s: Tuesday\n\
+             Year 24 (12s method): 24 / 12 = 2 remainder 0, 0 / 4 = 0, 2 + 0 + 0 = 2\n\
+             Year 24 (odd + 11 method): 24 / 2 = 12, 7 - 12 mod 7 = 2\n\
+             Doomsday of 2024: Tuesday + 2 = Thursday\n\
+             Closest doomsday: March 14\n\
+             March 12 is 2 days before: Thursday - 2 = Tuesday"
+        );
+
+        let date = Calendar::Gregorian.date(1999, 2, 1).unwrap();
+        let explanation = Question::from_date(
+            date,
+            Calendar::Gregorian,
+            &iso,
+            None,
+            WeekdayAnswers::default(),
+        )
+        .explanation()
+        .unwrap()
+        .clone();
+        assert!(explanation.contains("Year 99 (odd + 11 method): 99 + 11 = 110, 110 / 2 = 55, 55 + 11 = 66, 7 - 66 mod 7 = 4"));
+        assert!(explanation.contains("Doomsday of 1999: Wednesday + 11 = Sunday"));
+        assert!(explanation.contains("Closest doomsday: February 7 (common year)"));
+        assert!(explanation.contains("February 1 is 6 days before: Sunday - 6 = Monday"));
+    }
+
+    #[test]
+    fn explained_steps_match_weekdays() {
+        for year in 1900..2100 {
+            let explanation = Question::explain_year(year, System::Gregorian);
+            let doomsday = Question::weekday_name(Question::doomsday(year, System::Gregorian));
+            assert!(explanation[3].ends_with(&format!("= {doomsday}")));
+        }
+        for calendar in [Calendar::Gregorian, Calendar::Julian] {
+            let start = calendar.year_start(2023);
+            for date in (start..start + 731).map(|days| calendar.date_from_days(days)) {
+                let explanation = Question::explain_day(date);
+                let weekday = Question::weekday_name(date.weekday());
+                assert!(explanation[1].ends_with(weekday), "{date}: {explanation:?}");
+                assert!(!explanation[0].contains(" 0") && !explanation[0].contains("-"));
+            }
+        }
+    }
+
+    #[test]
+    fn sub_drill_explanations() {
+        let explanation =
+            Question::from_century(19, Calendar::Gregorian, WeekdayAnswers::default())
+                .explanation()
+                .unwrap()
+                .clone();
+        assert!(explanation.contains("Tuesday (2000s), Sunday (2100s), Friday (2200s)"));
+        assert!(explanation.ends_with("19 mod 4 = 3: Wednesday"));
+        let explanation = Question::from_year(2024, Calendar::Gregorian, WeekdayAnswers::default())
+            .explanation()
+            .unwrap()
+            .clone();
+        assert!(explanation.ends_with("Doomsday of 2024: Tuesday + 2 = Thursday"));
+        let explanation = Question::from_month(3, false)
+            .explanation()
+            .unwrap()
+            .clone();
+        assert!(explanation.contains("3/14 (Pi Day)"));
+    }
+
+    #[test]
+    fn adaptive_range() {
+        let args = [
+            "-l".to_string(),
+            "1900".to_string(),
+            "-u".to_string(),
+            "2000".to_string(),
+        ];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        assert_eq!(doomsday.adaptive_centre, 2000);
+        assert_eq!(
+            doomsday.current_range(),
+            Some("years 1900-2000".to_string())
+        );
+
+        assert!(doomsday.adapt(Adjustment::Start));
+        assert_eq!(
+            doomsday.current_range(),
+            Some("years 1990-2000".to_string())
+        );
+        assert!(doomsday
+            .generate_questions(20, &mut rand::thread_rng())
+            .iter()
+            .all(|question| question.prompt().contains(" 199")
+                || question.prompt().contains(" 2000")));
+
+        assert!(doomsday.adapt(Adjustment::Harder));
+        assert_eq!(
+            doomsday.current_range(),
+            Some("years 1970-2000".to_string())
+        );
+        for _ in 0..4 {
+            assert!(doomsday.adapt(Adjustment::Harder));
+        }
+        assert!(!doomsday.adapt(Adjustment::Harder));
+        assert_eq!(
+            doomsday.current_range(),
+            Some("years 1900-2000".to_string())
+        );
+
+        // Narrow range is not adapted at all
+        let args = [
+            "-l".to_string(),
+            "2009".to_string(),
+            "-u".to_string(),
+            "2009".to_string(),
+        ];
+        let doomsday = Doomsday::build(&args).expect("Should build correctly");
+        assert!(!doomsday.adapt(Adjustment::Start));
     }
 
     #[test]
@@ -394,7 +1547,7 @@ mod tests {
             "2009".to_string(),
         ];
         let doomsday = Doomsday::build(&args).expect("Should build correctly");
-        let questions = doomsday.generate_questions(10);
+        let questions = doomsday.generate_questions(10, &mut rand::thread_rng());
         assert_eq!(questions.len(), 10);
         assert!(questions
             .iter()
@@ -429,5 +1582,11 @@ mod tests {
         assert!(help_text.contains("-h, --help"));
         assert!(help_text.contains("-l, --lower-boundary"));
         assert!(help_text.contains("-u, --upper-boundary"));
+        assert!(help_text.contains("-m, --mode"));
+        assert!(help_text.contains("-c, --calendar"));
+        assert!(help_text.contains("--adoption-date"));
+        assert!(help_text.contains("-f, --date-format"));
+        assert!(help_text.contains("--language"));
+        assert!(help_text.contains("--weekday-numbers"));
     }
 }
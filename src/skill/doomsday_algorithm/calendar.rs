@@ -0,0 +1,384 @@
+//! Dates in the Gregorian calendar, the Julian calendar and a historical calendar (Julian
+//! until the Gregorian one was adopted). Days are numbered like `NaiveDate::num_days_from_ce`,
+//! so the same day has the same number in every calendar.
+
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// The first day of the Gregorian calendar
+pub const GREGORIAN_REFORM: (i32, u32, u32) = (1582, 10, 15);
+
+/// Number of a day in `System::days_since_epoch` for 0001-01-01 (Gregorian), which
+/// `NaiveDate::num_days_from_ce` numbers 1
+const CE_OFFSET: i64 = 305;
+/// Epochs of both systems are 0000-03-01, which is 2 days later in the Julian calendar
+const JULIAN_OFFSET: i64 = 2;
+
+/// The calendar a date is written in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum System {
+    Gregorian,
+    Julian,
+}
+
+impl System {
+    pub fn is_leap_year(self, year: i32) -> bool {
+        match self {
+            System::Gregorian => {
+                year.rem_euclid(4) == 0 && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0)
+            }
+            System::Julian => year.rem_euclid(4) == 0,
+        }
+    }
+
+    fn days_in_month(self, year: i32, month: u32) -> u32 {
+        match month {
+            2 if self.is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Returns `None` if the date doesn't exist in the system
+    pub fn date(self, year: i32, month: u32, day: u32) -> Option<Date> {
+        ((1..=12).contains(&month) && (1..=self.days_in_month(year, month)).contains(&day))
+            .then(|| Date::new(self, year, month, day))
+    }
+
+    /// Day number of a date, which must exist
+    fn days(self, year: i32, month: u32, day: u32) -> i32 {
+        // Years start in March, so that the leap day is the last day of the year
+        let year = i64::from(year) - i64::from(month <= 2);
+        let day_of_year = (153 * i64::from((month + 9) % 12) + 2) / 5 + i64::from(day) - 1;
+        let days_since_epoch = match self {
+            System::Gregorian => {
+                let era = year.div_euclid(400);
+                let year_of_era = year - era * 400;
+                era * 146_097 + year_of_era * 365 + year_of_era / 4 - year_of_era / 100
+                    + day_of_year
+            }
+            System::Julian => {
+                let era = year.div_euclid(4);
+                era * 1461 + (year - era * 4) * 365 + day_of_year - JULIAN_OFFSET
+            }
+        };
+        i32::try_from(days_since_epoch - CE_OFFSET).expect("year range is limited")
+    }
+
+    /// Date of a day number as (year, month, day)
+    fn year_month_day(self, days: i32) -> (i32, u32, u32) {
+        let days = i64::from(days) + CE_OFFSET;
+        let (year_of_era, day_of_year, era_start) = match self {
+            System::Gregorian => {
+                let era = days.div_euclid(146_097);
+                let day_of_era = days - era * 146_097;
+                let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+                    - day_of_era / 146_096)
+                    / 365;
+                let day_of_year =
+                    day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+                (year_of_era, day_of_year, era * 400)
+            }
+            System::Julian => {
+                let days = days + JULIAN_OFFSET;
+                let era = days.div_euclid(1461);
+                let day_of_era = days - era * 1461;
+                let year_of_era = (day_of_era - day_of_era / 1460) / 365;
+                (year_of_era, day_of_era - 365 * year_of_era, era * 4)
+            }
+        };
+        let month_index = (5 * day_of_year + 2) / 153; // March is 0
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = era_start + year_of_era + i64::from(month <= 2);
+        (
+            i32::try_from(year).expect("year range is limited"),
+            u32::try_from(month).expect("month is between 1 and 12"),
+            u32::try_from(day).expect("day is between 1 and 31"),
+        )
+    }
+}
+
+/// A day written in one of the systems
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+    system: System,
+    days: i32,
+}
+
+impl Date {
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn system(&self) -> System {
+        self.system
+    }
+
+    pub fn leap_year(&self) -> bool {
+        self.system.is_leap_year(self.year)
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        // Day 1 (0001-01-01) is a Monday
+        (0..(self.days - 1).rem_euclid(7)).fold(Weekday::Mon, |weekday, _| weekday.succ())
+    }
+
+    /// The same month in the same system, regardless of the calendar it belongs to
+    pub fn with_day(&self, day: u32) -> Option<Date> {
+        self.system.date(self.year, self.month, day)
+    }
+
+    fn new(system: System, year: i32, month: u32, day: u32) -> Date {
+        Date {
+            year,
+            month,
+            day,
+            system,
+            days: system.days(year, month, day),
+        }
+    }
+
+    fn from_days(system: System, days: i32) -> Date {
+        let (year, month, day) = system.year_month_day(days);
+        Date {
+            year,
+            month,
+            day,
+            system,
+            days,
+        }
+    }
+}
+
+/// Formatted like `NaiveDate` with "%Y-%m-%d"
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.year > 9999 {
+            write!(f, "+")?;
+        }
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Calendar {
+    Gregorian,
+    Julian,
+    /// Julian until the day before `adoption`, Gregorian since `adoption` (day number)
+    Historical {
+        adoption: i32,
+    },
+}
+
+impl Calendar {
+    /// Historical calendar with the Gregorian calendar adopted on `adoption`.
+    /// Returns `None` if `adoption` is before the Gregorian reform.
+    pub fn historical(adoption: NaiveDate) -> Option<Calendar> {
+        let (year, month, day) = GREGORIAN_REFORM;
+        let reform = NaiveDate::from_ymd_opt(year, month, day).expect("valid date");
+        (adoption >= reform).then(|| Calendar::Historical {
+            adoption: adoption.num_days_from_ce(),
+        })
+    }
+
+    pub fn system_on(&self, days: i32) -> System {
+        match self {
+            Calendar::Gregorian => System::Gregorian,
+            Calendar::Julian => System::Julian,
+            Calendar::Historical { adoption } if days < *adoption => System::Julian,
+            Calendar::Historical { .. } => System::Gregorian,
+        }
+    }
+
+    pub fn date_from_days(&self, days: i32) -> Date {
+        Date::from_days(self.system_on(days), days)
+    }
+
+    /// Returns `None` if the date doesn't exist in the calendar (e.g. days skipped when
+    /// the Gregorian calendar was adopted)
+    pub fn date(&self, year: i32, month: u32, day: u32) -> Option<Date> {
+        [System::Julian, System::Gregorian]
+            .into_iter()
+            .filter_map(|system| system.date(year, month, day))
+            .find(|date| self.system_on(date.days) == date.system)
+    }
+
+    /// Parses a date formatted as "%Y-%m-%d"
+    pub fn parse(&self, text: &str) -> Option<Date> {
+        let mut parts = text.strip_prefix('+').unwrap_or(text).split('-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        self.date(year, month, day)
+    }
+
+    /// Day number of the first day of `year`
+    pub fn year_start(&self, year: i32) -> i32 {
+        match self {
+            Calendar::Gregorian => System::Gregorian.days(year, 1, 1),
+            Calendar::Julian => System::Julian.days(year, 1, 1),
+            Calendar::Historical { adoption } => {
+                let julian = System::Julian.days(year, 1, 1);
+                if julian < *adoption {
+                    julian
+                } else {
+                    // 1 January might have been skipped
+                    System::Gregorian.days(year, 1, 1).max(*adoption)
+                }
+            }
+        }
+    }
+
+    /// Day number of the last day of `year`
+    pub fn year_end(&self, year: i32) -> i32 {
+        self.year_start(year + 1) - 1
+    }
+
+    /// The system the doomsdays of `year` are calculated in, i.e. the one in use on 4/4
+    pub fn doomsday_system(&self, year: i32) -> System {
+        self.system_on(System::Julian.days(year, 4, 4))
+    }
+
+    /// Distinguishes question keys of different calendars (empty for Gregorian)
+    pub fn key_suffix(&self) -> String {
+        match self {
+            Calendar::Gregorian => String::new(),
+            Calendar::Julian => "@julian".to_string(),
+            Calendar::Historical { adoption } => {
+                format!("@{}", Date::from_days(System::Gregorian, *adoption))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gregorian_days_match_chrono() {
+        for days in (1..800_000).step_by(97) {
+            let date = Calendar::Gregorian.date_from_days(days);
+            let naive = NaiveDate::from_num_days_from_ce_opt(days).unwrap();
+            assert_eq!(
+                (date.year(), date.month(), date.day()),
+                (naive.year(), naive.month(), naive.day())
+            );
+            assert_eq!(date.weekday(), naive.weekday());
+            assert_eq!(date.to_string(), naive.format("%Y-%m-%d").to_string());
+            assert_eq!(
+                Calendar::Gregorian.date(date.year(), date.month(), date.day()),
+                Some(date)
+            );
+        }
+    }
+
+    #[test]
+    fn julian_dates() {
+        // The last Julian day in Catholic countries was followed by 1582-10-15 (Gregorian)
+        let last_julian = Calendar::Julian.date(1582, 10, 4).unwrap();
+        assert_eq!(last_julian.weekday(), Weekday::Thu);
+        assert_eq!(
+            last_julian.days + 1,
+            NaiveDate::from_ymd_opt(1582, 10, 15)
+                .unwrap()
+                .num_days_from_ce()
+        );
+        // Both calendars agree in the 3rd century
+        assert_eq!(
+            Calendar::Julian.date(250, 6, 1).unwrap().days,
+            Calendar::Gregorian.date(250, 6, 1).unwrap().days
+        );
+        // Leap day that doesn't exist in the Gregorian calendar
+        assert!(Calendar::Julian.date(1700, 2, 29).is_some());
+        assert!(Calendar::Gregorian.date(1700, 2, 29).is_none());
+        assert!(Calendar::Julian.date(1700, 2, 30).is_none());
+        assert!(Calendar::Julian.date(1700, 13, 1).is_none());
+
+        for days in (0..800_000).step_by(89) {
+            let date = Calendar::Julian.date_from_days(days);
+            assert_eq!(date.system(), System::Julian);
+            assert_eq!(
+                Calendar::Julian.date(date.year(), date.month(), date.day()),
+                Some(date)
+            );
+        }
+    }
+
+    #[test]
+    fn historical_calendar() {
+        let britain = Calendar::historical(NaiveDate::from_ymd_opt(1752, 9, 14).unwrap())
+            .expect("adoption after the reform");
+        let last_julian = britain.date(1752, 9, 2).unwrap();
+        assert_eq!(last_julian.system(), System::Julian);
+        assert_eq!(last_julian.weekday(), Weekday::Wed);
+        let first_gregorian = britain.date_from_days(last_julian.days + 1);
+        assert_eq!(first_gregorian.to_string(), "1752-09-14");
+        assert_eq!(first_gregorian.system(), System::Gregorian);
+        assert_eq!(first_gregorian.weekday(), Weekday::Thu);
+        assert!(britain.date(1752, 9, 10).is_none());
+        assert_eq!(britain.year_end(1752) - britain.year_start(1752) + 1, 355);
+        assert_eq!(britain.doomsday_system(1752), System::Julian);
+        assert_eq!(britain.doomsday_system(1753), System::Gregorian);
+        assert_eq!(britain.key_suffix(), "@1752-09-14");
+
+        // Russia skipped 1918-02-01 to 1918-02-13
+        let russia = Calendar::historical(NaiveDate::from_ymd_opt(1918, 2, 14).unwrap()).unwrap();
+        assert_eq!(russia.date(1918, 1, 31).unwrap().weekday(), Weekday::Wed);
+        assert!(russia.date(1918, 2, 1).is_none());
+
+        // 1 January skipped
+        let calendar = Calendar::historical(NaiveDate::from_ymd_opt(1700, 1, 5).unwrap()).unwrap();
+        assert_eq!(
+            calendar
+                .date_from_days(calendar.year_start(1700))
+                .to_string(),
+            "1700-01-05"
+        );
+        assert_eq!(
+            calendar.date_from_days(calendar.year_end(1699)).to_string(),
+            "1699-12-25"
+        );
+
+        assert!(Calendar::historical(NaiveDate::from_ymd_opt(1582, 10, 14).unwrap()).is_none());
+    }
+
+    #[test]
+    fn parse_dates() {
+        assert_eq!(
+            Calendar::Julian.parse("1500-03-01"),
+            Calendar::Julian.date(1500, 3, 1)
+        );
+        assert_eq!(
+            Calendar::Gregorian
+                .parse("+10000-01-01")
+                .unwrap()
+                .to_string(),
+            "+10000-01-01"
+        );
+        assert!(Calendar::Gregorian.parse("2024-02-30").is_none());
+        assert!(Calendar::Gregorian.parse("2024-02-03-01").is_none());
+        assert!(Calendar::Gregorian.parse("2024-02-03@julian").is_none());
+    }
+}
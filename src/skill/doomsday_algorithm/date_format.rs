@@ -0,0 +1,222 @@
+//! Formats of dates in questions: presets or strftime patterns of the year, the month and
+//! the day. Dates are formatted without chrono, so that Julian dates are supported too.
+
+use chrono::format::{Fixed, Item, Numeric, Pad, StrftimeItems};
+use chrono::NaiveDate;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+use super::calendar::Date;
+
+pub const FORMAT_ISO: &str = "iso";
+pub const FORMAT_MIXED: &str = "mixed";
+/// Preset names and their patterns, e.g. 2024-03-14, 14.03.2024, 03/14/2024, 14 March 2024
+const PRESETS: [(&str, &str); 4] = [
+    (FORMAT_ISO, "%Y-%m-%d"),
+    ("dmy", "%d.%m.%Y"),
+    ("mdy", "%m/%d/%Y"),
+    ("long", "%-d %B %Y"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateFormat {
+    /// strftime pattern
+    Pattern(String),
+    /// One of the presets, chosen randomly for each question
+    Mixed,
+}
+
+impl DateFormat {
+    /// Accepts a preset name, "mixed" or a strftime pattern. Patterns may contain only
+    /// the year, the month and the day (e.g. not the weekday) and must contain all of them.
+    pub fn build(value: &str) -> Result<DateFormat, String> {
+        if value == FORMAT_MIXED {
+            return Ok(DateFormat::Mixed);
+        }
+        if let Some((_, pattern)) = PRESETS.iter().find(|(name, _)| *name == value) {
+            return Ok(DateFormat::Pattern((*pattern).to_string()));
+        }
+
+        let (mut year, mut century, mut year_of_century, mut month, mut day) =
+            (false, false, false, false, false);
+        for item in StrftimeItems::new(value) {
+            match item {
+                Item::Literal(_) | Item::OwnedLiteral(_) | Item::Space(_) | Item::OwnedSpace(_) => {
+                }
+                Item::Numeric(Numeric::Year, _) => year = true,
+                Item::Numeric(Numeric::YearDiv100, _) => century = true,
+                Item::Numeric(Numeric::YearMod100, _) => year_of_century = true,
+                Item::Numeric(Numeric::Month, _)
+                | Item::Fixed(Fixed::ShortMonthName | Fixed::LongMonthName) => month = true,
+                Item::Numeric(Numeric::Day, _) => day = true,
+                Item::Error => return Err(format!("invalid date format '{value}'")),
+                _ => {
+                    return Err(format!(
+                        "date format '{value}' can contain only the year, the month and the day"
+                    ))
+                }
+            }
+        }
+        if (year || century && year_of_century) && month && day {
+            Ok(DateFormat::Pattern(value.to_string()))
+        } else {
+            Err(format!(
+                "date format '{value}' must contain the year, the month and the day"
+            ))
+        }
+    }
+
+    /// Chooses the preset of a question in mixed format. Returns `None` for patterns.
+    pub fn choose_preset(&self, rng: &mut dyn RngCore) -> Option<&'static str> {
+        match self {
+            DateFormat::Pattern(_) => None,
+            DateFormat::Mixed => Some(PRESETS.choose(rng).expect("not empty").0),
+        }
+    }
+
+    /// Returns the preset called `name` if it can be used in this format, i.e. in mixed
+    /// format only
+    pub fn find_preset(&self, name: &str) -> Option<&'static str> {
+        match self {
+            DateFormat::Pattern(_) => None,
+            DateFormat::Mixed => PRESETS
+                .iter()
+                .map(|(preset, _)| *preset)
+                .find(|preset| *preset == name),
+        }
+    }
+
+    /// Formats `date` with the pattern, or in mixed format with `preset` (see
+    /// `choose_preset`). Mixed format falls back to ISO if no preset is given.
+    pub fn format(&self, date: &Date, preset: Option<&str>) -> String {
+        let pattern = match self {
+            DateFormat::Pattern(pattern) => pattern,
+            DateFormat::Mixed => {
+                PRESETS
+                    .iter()
+                    .find(|(name, _)| Some(*name) == preset)
+                    .unwrap_or(&PRESETS[0])
+                    .1
+            }
+        };
+        let mut text = String::new();
+        for item in StrftimeItems::new(pattern) {
+            match item {
+                Item::Literal(literal) | Item::Space(literal) => text.push_str(literal),
+                Item::OwnedLiteral(literal) | Item::OwnedSpace(literal) => text.push_str(&literal),
+                Item::Numeric(numeric, pad) => {
+                    let (value, width) = match numeric {
+                        Numeric::Year if date.year() > 9999 => {
+                            // Like chrono, to tell the year apart from the following digits
+                            text.push('+');
+                            (date.year(), 4)
+                        }
+                        Numeric::Year => (date.year(), 4),
+                        Numeric::YearDiv100 => (date.year().div_euclid(100), 2),
+                        Numeric::YearMod100 => (date.year().rem_euclid(100), 2),
+                        Numeric::Month => (date.month() as i32, 2),
+                        _ => (date.day() as i32, 2), // only the day is left, checked in build()
+                    };
+                    match pad {
+                        Pad::None => text.push_str(&value.to_string()),
+                        Pad::Zero => text.push_str(&format!("{value:0width$}")),
+                        Pad::Space => text.push_str(&format!("{value:width$}")),
+                    }
+                }
+                Item::Fixed(fixed) => {
+                    let name = NaiveDate::from_ymd_opt(2000, date.month(), 1)
+                        .expect("month is between 1 and 12")
+                        .format("%B")
+                        .to_string();
+                    match fixed {
+                        Fixed::ShortMonthName => text.push_str(&name[..3]),
+                        _ => text.push_str(&name), // checked in build()
+                    }
+                }
+                Item::Error => {}
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::iter;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::super::calendar::Calendar;
+    use super::*;
+
+    #[test]
+    fn presets() {
+        let date = Calendar::Gregorian.date(2024, 3, 4).unwrap();
+        let format = |value| DateFormat::build(value).unwrap().format(&date, None);
+        assert_eq!(format("iso"), "2024-03-04");
+        assert_eq!(format("dmy"), "04.03.2024");
+        assert_eq!(format("mdy"), "03/04/2024");
+        assert_eq!(format("long"), "4 March 2024");
+
+        assert_eq!(DateFormat::Mixed.format(&date, Some("mdy")), "03/04/2024");
+        assert_eq!(DateFormat::Mixed.format(&date, None), "2024-03-04");
+        // Presets only apply in mixed format
+        assert_eq!(
+            DateFormat::build("long")
+                .unwrap()
+                .format(&date, Some("mdy")),
+            "4 March 2024"
+        );
+    }
+
+    #[test]
+    fn mixed_presets() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let presets: HashSet<_> = iter::repeat_with(|| DateFormat::Mixed.choose_preset(&mut rng))
+            .take(100)
+            .collect();
+        assert_eq!(presets.len(), PRESETS.len());
+        assert!(presets.iter().all(|preset| preset.is_some()));
+
+        let pattern = DateFormat::build("iso").unwrap();
+        assert_eq!(pattern.choose_preset(&mut rng), None);
+        assert_eq!(DateFormat::Mixed.find_preset("dmy"), Some("dmy"));
+        assert_eq!(DateFormat::Mixed.find_preset("%d.%m.%Y"), None);
+        assert_eq!(pattern.find_preset("dmy"), None);
+    }
+
+    #[test]
+    fn patterns() {
+        let date = Calendar::Julian.date(1700, 2, 29).unwrap();
+        let format = |value| DateFormat::build(value).unwrap().format(&date, None);
+        assert_eq!(format("%d/%m/%Y"), "29/02/1700");
+        assert_eq!(format("%b %e, %Y"), "Feb 29, 1700");
+        assert_eq!(format("%F"), "1700-02-29");
+        assert_eq!(format("%C%y%m%d"), "17000229");
+        assert_eq!(format("%Y-%m-%d (100%%)"), "1700-02-29 (100%)");
+
+        let date = Calendar::Gregorian.date(12024, 3, 14).unwrap();
+        assert_eq!(
+            DateFormat::build("iso").unwrap().format(&date, None),
+            "+12024-03-14"
+        );
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        let err = DateFormat::build("%A, %Y-%m-%d").unwrap_err();
+        assert!(err.contains("can contain only the year, the month and the day"));
+        let err = DateFormat::build("%Y-%m-%d %H:%M").unwrap_err();
+        assert!(err.contains("can contain only the year, the month and the day"));
+        let err = DateFormat::build("%m/%d").unwrap_err();
+        assert!(err.contains("must contain the year, the month and the day"));
+        let err = DateFormat::build("%y-%m-%d").unwrap_err();
+        assert!(err.contains("must contain the year, the month and the day"));
+        let err = DateFormat::build("european").unwrap_err();
+        assert!(err.contains("must contain the year, the month and the day"));
+        let err = DateFormat::build("%Y-%m-%d %Q").unwrap_err();
+        assert!(err.contains("invalid date format"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Weekday names and numbers accepted as answers
+
+use chrono::Weekday;
+
+pub const LANGUAGE_ENGLISH: &str = "en";
+pub const LANGUAGE_POLISH: &str = "pl";
+pub const LANGUAGE_GERMAN: &str = "de";
+pub const LANGUAGE_FRENCH: &str = "fr";
+pub const LANGUAGE_SPANISH: &str = "es";
+
+pub const NUMBERS_MIXED: &str = "mixed";
+pub const NUMBERS_MONDAY_1: &str = "monday-1";
+pub const NUMBERS_SUNDAY_0: &str = "sunday-0";
+pub const NUMBERS_SUNDAY_1: &str = "sunday-1";
+
+/// Names of weekdays from Monday, the full name first (shown as the correct answer), then
+/// abbreviations and spellings without diacritics
+type Names = [&'static [&'static str]; 7];
+
+const ENGLISH: Names = [
+    &["monday", "mo", "mon"],
+    &["tuesday", "tu", "tue"],
+    &["wednesday", "we", "wed"],
+    &["thursday", "th", "thu"],
+    &["friday", "fr", "fri"],
+    &["saturday", "sa", "sat"],
+    &["sunday", "su", "sun"],
+];
+const POLISH: Names = [
+    &["poniedziałek", "pn", "pon", "poniedzialek"],
+    &["wtorek", "wt", "wto"],
+    &["środa", "śr", "śro", "sr", "sro", "sroda"],
+    &["czwartek", "cz", "czw"],
+    &["piątek", "pt", "pią", "pia", "piatek"],
+    &["sobota", "sb", "sob"],
+    &["niedziela", "nd", "nie", "ndz"],
+];
+const GERMAN: Names = [
+    &["montag", "mo", "mon"],
+    &["dienstag", "di", "die"],
+    &["mittwoch", "mi", "mit"],
+    &["donnerstag", "do", "don"],
+    &["freitag", "fr", "fre"],
+    &["samstag", "sa", "sam", "sonnabend"],
+    &["sonntag", "so", "son"],
+];
+const FRENCH: Names = [
+    &["lundi", "lu", "lun"],
+    &["mardi", "ma", "mar"],
+    &["mercredi", "me", "mer"],
+    &["jeudi", "je", "jeu"],
+    &["vendredi", "ve", "ven"],
+    &["samedi", "sa", "sam"],
+    &["dimanche", "di", "dim"],
+];
+const SPANISH: Names = [
+    &["lunes", "lu", "lun"],
+    &["martes", "ma", "mar"],
+    &["miércoles", "mi", "mié", "mie", "miercoles"],
+    &["jueves", "ju", "jue"],
+    &["viernes", "vi", "vie"],
+    &["sábado", "sá", "sáb", "sa", "sab", "sabado"],
+    &["domingo", "do", "dom"],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Polish,
+    German,
+    French,
+    Spanish,
+}
+
+impl Language {
+    pub fn from_string(value: &str) -> Language {
+        match value {
+            LANGUAGE_ENGLISH => Language::English,
+            LANGUAGE_POLISH => Language::Polish,
+            LANGUAGE_GERMAN => Language::German,
+            LANGUAGE_FRENCH => Language::French,
+            LANGUAGE_SPANISH => Language::Spanish,
+            _ => panic!("incorrect value for Language"),
+        }
+    }
+
+    fn names(self, weekday: Weekday) -> &'static [&'static str] {
+        let names = match self {
+            Language::English => &ENGLISH,
+            Language::Polish => &POLISH,
+            Language::German => &GERMAN,
+            Language::French => &FRENCH,
+            Language::Spanish => &SPANISH,
+        };
+        names[weekday.num_days_from_monday() as usize]
+    }
+}
+
+/// How weekdays are numbered
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WeekdayNumbers {
+    /// Monday is 1, Saturday is 6 and Sunday is either 0 or 7
+    #[default]
+    Mixed,
+    /// Monday is 1 and Sunday is 7 (ISO 8601)
+    MondayOne,
+    /// Sunday is 0 and Saturday is 6
+    SundayZero,
+    /// Sunday is 1 and Saturday is 7
+    SundayOne,
+}
+
+impl WeekdayNumbers {
+    pub fn from_string(value: &str) -> WeekdayNumbers {
+        match value {
+            NUMBERS_MIXED => WeekdayNumbers::Mixed,
+            NUMBERS_MONDAY_1 => WeekdayNumbers::MondayOne,
+            NUMBERS_SUNDAY_0 => WeekdayNumbers::SundayZero,
+            NUMBERS_SUNDAY_1 => WeekdayNumbers::SundayOne,
+            _ => panic!("incorrect value for WeekdayNumbers"),
+        }
+    }
+
+    fn numbers(self, weekday: Weekday) -> Vec<u32> {
+        match self {
+            WeekdayNumbers::Mixed if weekday == Weekday::Sun => vec![0, 7],
+            WeekdayNumbers::Mixed | WeekdayNumbers::MondayOne => {
+                vec![weekday.number_from_monday()]
+            }
+            WeekdayNumbers::SundayZero => vec![weekday.num_days_from_sunday()],
+            WeekdayNumbers::SundayOne => vec![weekday.number_from_sunday()],
+        }
+    }
+}
+
+/// Answers accepted for questions answered with a weekday
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WeekdayAnswers {
+    language: Language,
+    numbers: WeekdayNumbers,
+}
+
+impl WeekdayAnswers {
+    pub fn new(language: Language, numbers: WeekdayNumbers) -> WeekdayAnswers {
+        WeekdayAnswers { language, numbers }
+    }
+
+    /// The weekday expected as the answer and its accepted alternatives
+    pub fn answers(self, weekday: Weekday) -> (String, Vec<String>) {
+        let (answer, abbreviations) = self
+            .language
+            .names(weekday)
+            .split_first()
+            .expect("every weekday has a name");
+        let alternative_answers = abbreviations
+            .iter()
+            .map(ToString::to_string)
+            .chain(
+                self.numbers
+                    .numbers(weekday)
+                    .iter()
+                    .map(ToString::to_string),
+            )
+            .collect();
+        (answer.to_string(), alternative_answers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use super::*;
+
+    #[test]
+    fn names() {
+        let answers = WeekdayAnswers::new(Language::Polish, WeekdayNumbers::Mixed);
+        let (answer, alternative_answers) = answers.answers(Weekday::Wed);
+        assert_eq!(answer, "środa");
+        assert!(alternative_answers.contains(&"sroda".to_string()));
+        assert!(alternative_answers.contains(&"3".to_string()));
+
+        let (answer, _) = WeekdayAnswers::default().answers(Weekday::Wed);
+        assert_eq!(answer, "wednesday");
+        let answers = WeekdayAnswers::new(Language::German, WeekdayNumbers::Mixed);
+        assert_eq!(answers.answers(Weekday::Wed).0, "mittwoch");
+        let answers = WeekdayAnswers::new(Language::French, WeekdayNumbers::Mixed);
+        assert_eq!(answers.answers(Weekday::Wed).0, "mercredi");
+        let answers = WeekdayAnswers::new(Language::Spanish, WeekdayNumbers::Mixed);
+        assert_eq!(answers.answers(Weekday::Wed).0, "miércoles");
+
+        // Abbreviations don't collide within a language
+        for language in [
+            Language::English,
+            Language::Polish,
+            Language::German,
+            Language::French,
+            Language::Spanish,
+        ] {
+            let mut names: Vec<_> = iter::successors(Some(Weekday::Mon), |day| Some(day.succ()))
+                .take(7)
+                .flat_map(|day| language.names(day))
+                .collect();
+            let count = names.len();
+            names.sort_unstable();
+            names.dedup();
+            assert_eq!(names.len(), count, "{language:?}");
+        }
+    }
+
+    #[test]
+    fn numbers() {
+        let numbers = |numbers: WeekdayNumbers, weekday| {
+            WeekdayAnswers::new(Language::English, numbers)
+                .answers(weekday)
+                .1[2..]
+                .to_vec()
+        };
+        assert_eq!(numbers(WeekdayNumbers::Mixed, Weekday::Mon), ["1"]);
+        assert_eq!(numbers(WeekdayNumbers::Mixed, Weekday::Sun), ["0", "7"]);
+        assert_eq!(numbers(WeekdayNumbers::MondayOne, Weekday::Sun), ["7"]);
+        assert_eq!(numbers(WeekdayNumbers::SundayZero, Weekday::Sun), ["0"]);
+        assert_eq!(numbers(WeekdayNumbers::SundayZero, Weekday::Sat), ["6"]);
+        assert_eq!(numbers(WeekdayNumbers::SundayOne, Weekday::Sun), ["1"]);
+        assert_eq!(numbers(WeekdayNumbers::SundayOne, Weekday::Sat), ["7"]);
+    }
+}
@@ -0,0 +1,577 @@
+use std::iter;
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use super::{question_id, question_key, Base};
+use crate::application::APP_NAME;
+use crate::args::prelude::*;
+use crate::question::{Category, Question};
+
+pub const CMD: &str = "gcd";
+
+const ARG_ID_HELP: &str = "help";
+const ARG_ID_MODE: &str = "mode";
+const ARG_ID_NUMBERS: &str = "numbers";
+const ARG_ID_LOWER_BOUNDARY_1: &str = "lower_boundary_1";
+const ARG_ID_UPPER_BOUNDARY_1: &str = "upper_boundary_1";
+const ARG_ID_LOWER_BOUNDARY_2: &str = "lower_boundary_2";
+const ARG_ID_UPPER_BOUNDARY_2: &str = "upper_boundary_2";
+const ARG_ID_LOWER_BOUNDARY_3: &str = "lower_boundary_3";
+const ARG_ID_UPPER_BOUNDARY_3: &str = "upper_boundary_3";
+
+const MODE_GCD: &str = "gcd";
+const MODE_LCM: &str = "lcm";
+const MODE_MIXED: &str = "mixed";
+
+const NUMBERS_TWO: &str = "2";
+const NUMBERS_THREE: &str = "3";
+const NUMBERS_MIXED: &str = "mixed";
+
+const DEFAULT_LOWER_BOUNDARY: u32 = 1;
+const DEFAULT_UPPER_BOUNDARY: u32 = 100;
+const CATEGORY_QUESTION: &str = "question";
+
+/// Share of questions about numbers with a common factor; random numbers are usually coprime
+const COMMON_FACTOR_PROBABILITY: f64 = 0.75;
+/// Number of draws after which any numbers are asked about, if numbers with (or without)
+/// a common factor are rare in the ranges
+const MAX_DRAWS: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    /// Greatest common divisor
+    Gcd,
+    /// Least common multiple
+    Lcm,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Gcd => MODE_GCD,
+            Kind::Lcm => MODE_LCM,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Only(Kind),
+    /// Either of them, chosen randomly
+    Mixed,
+}
+
+impl Mode {
+    fn from_string(value: &str) -> Mode {
+        match value {
+            MODE_GCD => Mode::Only(Kind::Gcd),
+            MODE_LCM => Mode::Only(Kind::Lcm),
+            MODE_MIXED => Mode::Mixed,
+            _ => panic!("incorrect value for Mode"),
+        }
+    }
+
+    fn allows(self, kind: Kind) -> bool {
+        match self {
+            Mode::Only(only) => only == kind,
+            Mode::Mixed => true,
+        }
+    }
+}
+
+/// How many numbers questions are about
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Numbers {
+    Two,
+    Three,
+    /// Either of them, chosen randomly
+    Mixed,
+}
+
+impl Numbers {
+    fn from_string(value: &str) -> Numbers {
+        match value {
+            NUMBERS_TWO => Numbers::Two,
+            NUMBERS_THREE => Numbers::Three,
+            NUMBERS_MIXED => Numbers::Mixed,
+            _ => panic!("incorrect value for Numbers"),
+        }
+    }
+
+    fn allows(self, count: usize) -> bool {
+        match self {
+            Numbers::Two => count == 2,
+            Numbers::Three => count == 3,
+            Numbers::Mixed => count == 2 || count == 3,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Gcd {
+    arg_definitions: Vec<Arg>,
+    show_help: bool,
+
+    mode: Mode,
+    numbers: Numbers,
+    /// Lower and upper boundaries of the first, second and third number
+    boundaries: [(u32, u32); 3],
+}
+
+impl Gcd {
+    pub fn build(args: &[String]) -> Result<Self, String> {
+        let arg_definitions = Self::build_arg_definitions();
+        let parsed_args = parser::parse_and_validate_arg_list(args, &arg_definitions)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        let show_help =
+            bool::set_value_from_arg_or_default(ARG_ID_HELP, &parsed_args, &arg_definitions);
+        let mode =
+            String::set_value_from_arg_or_default(ARG_ID_MODE, &parsed_args, &arg_definitions);
+        let numbers =
+            String::set_value_from_arg_or_default(ARG_ID_NUMBERS, &parsed_args, &arg_definitions);
+        let boundaries = [
+            (ARG_ID_LOWER_BOUNDARY_1, ARG_ID_UPPER_BOUNDARY_1),
+            (ARG_ID_LOWER_BOUNDARY_2, ARG_ID_UPPER_BOUNDARY_2),
+            (ARG_ID_LOWER_BOUNDARY_3, ARG_ID_UPPER_BOUNDARY_3),
+        ]
+        .map(|(lower_id, upper_id)| {
+            (
+                u32::set_value_from_arg_or_default(lower_id, &parsed_args, &arg_definitions),
+                u32::set_value_from_arg_or_default(upper_id, &parsed_args, &arg_definitions),
+            )
+        });
+
+        if boundaries.iter().any(|(lower, upper)| lower > upper) {
+            return Err(Self::build_err_message(Some(
+                "lower boundary must be less than or equal to upper boundary".to_string(),
+            )));
+        }
+
+        // GCD(0, 0) and LCM(0, n) are of no use
+        if boundaries.iter().any(|&(lower, _)| lower == 0) {
+            return Err(Self::build_err_message(Some(
+                "lower boundary must be at least 1".to_string(),
+            )));
+        }
+
+        Ok(Self {
+            arg_definitions,
+            show_help,
+            mode: Mode::from_string(&mode),
+            numbers: Numbers::from_string(&numbers),
+            boundaries,
+        })
+    }
+
+    fn build_arg_definitions() -> Vec<Arg> {
+        let mut definitions = vec![
+            Arg::builder()
+                .id(ARG_ID_HELP)
+                .short_name('h')
+                .long_name("help")
+                .description(vec!["Display help for gcd command.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(true)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_MODE)
+                .short_name('m')
+                .long_name("mode")
+                .description(vec![
+                    "Set the kind of questions: gcd (greatest".to_string(),
+                    "common divisor), lcm (least common multiple)".to_string(),
+                    "or mixed (either of them, chosen randomly)".to_string(),
+                    "(default: mixed).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    MODE_GCD.to_string(),
+                    MODE_LCM.to_string(),
+                    MODE_MIXED.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(MODE_MIXED.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_NUMBERS)
+                .long_name("numbers")
+                .description(vec![
+                    "Set how many numbers questions are about:".to_string(),
+                    "2, 3 or mixed (default: 2).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    NUMBERS_TWO.to_string(),
+                    NUMBERS_THREE.to_string(),
+                    NUMBERS_MIXED.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(NUMBERS_TWO.to_string()))
+                .build(),
+        ];
+        let boundaries = [
+            (ARG_ID_LOWER_BOUNDARY_1, ARG_ID_UPPER_BOUNDARY_1, "first"),
+            (ARG_ID_LOWER_BOUNDARY_2, ARG_ID_UPPER_BOUNDARY_2, "second"),
+            (ARG_ID_LOWER_BOUNDARY_3, ARG_ID_UPPER_BOUNDARY_3, "third"),
+        ];
+        for (index, (lower_id, upper_id, ordinal)) in boundaries.into_iter().enumerate() {
+            definitions.push(
+                Arg::builder()
+                    .id(lower_id)
+                    .long_name(&format!("lower-boundary-{}", index + 1))
+                    .description(vec![
+                        "Set the minimum value".to_string(),
+                        format!("for the {ordinal} number (default: {DEFAULT_LOWER_BOUNDARY})."),
+                    ])
+                    .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                    .stop_parsing(false)
+                    .default_value(ArgValue::UnsignedInt(DEFAULT_LOWER_BOUNDARY))
+                    .build(),
+            );
+            definitions.push(
+                Arg::builder()
+                    .id(upper_id)
+                    .long_name(&format!("upper-boundary-{}", index + 1))
+                    .description(vec![
+                        "Set the maximum value".to_string(),
+                        format!("for the {ordinal} number (default: {DEFAULT_UPPER_BOUNDARY})."),
+                    ])
+                    .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                    .stop_parsing(false)
+                    .default_value(ArgValue::UnsignedInt(DEFAULT_UPPER_BOUNDARY))
+                    .build(),
+            );
+        }
+        definitions
+    }
+
+    fn usage() -> String {
+        format!("Usage: {APP_NAME} [option]... gcd [gcd_option]...")
+    }
+
+    fn help_prompt() -> String {
+        format!("Try '{APP_NAME} gcd --help' for more information.")
+    }
+
+    fn additional_info() -> String {
+        let mut text = String::new();
+        text.push_str("Practise the greatest common divisor and the least common multiple\n");
+        text.push_str("of two or three numbers. The range is set separately for each number.\n");
+        text.push_str("Most questions are about numbers with a common factor. With --explain,\n");
+        text.push_str("the steps of the Euclidean algorithm are shown after wrong answers.");
+        text
+    }
+
+    fn build_err_message(msg: Option<String>) -> String {
+        if let Some(msg) = msg {
+            format!(
+                "{}: {}: {}\n{}\n{}",
+                APP_NAME,
+                CMD,
+                msg,
+                Self::usage(),
+                Self::help_prompt()
+            )
+        } else {
+            format!("{}\n{}", Self::usage(), Self::help_prompt())
+        }
+    }
+
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let kind = match self.mode {
+            Mode::Only(kind) => kind,
+            Mode::Mixed => *[Kind::Gcd, Kind::Lcm].choose(rng).expect("not empty"),
+        };
+        let count = match self.numbers {
+            Numbers::Two => 2,
+            Numbers::Three => 3,
+            Numbers::Mixed => *[2, 3].choose(rng).expect("not empty"),
+        };
+        let common_factor = rng.gen_bool(COMMON_FACTOR_PROBABILITY);
+        let mut draw = || -> Vec<u32> {
+            self.boundaries[..count]
+                .iter()
+                .map(|&(lower, upper)| rng.gen_range(lower..=upper))
+                .collect()
+        };
+        let mut numbers = draw();
+        for _ in 1..MAX_DRAWS {
+            if (Self::gcd_of(&numbers) > 1) == common_factor {
+                break;
+            }
+            numbers = draw();
+        }
+        Self::question(&numbers, kind)
+    }
+
+    fn gcd(a: u128, b: u128) -> u128 {
+        if b == 0 {
+            a
+        } else {
+            Self::gcd(b, a % b)
+        }
+    }
+
+    fn gcd_of(numbers: &[u32]) -> u128 {
+        numbers
+            .iter()
+            .fold(0, |gcd, &number| Self::gcd(gcd, number.into()))
+    }
+
+    /// Steps of the Euclidean algorithm, e.g. "252 = 2 * 105 + 42"
+    fn euclidean_steps(a: u128, b: u128) -> Vec<String> {
+        let (a, b) = (a.max(b), a.min(b));
+        iter::successors(Some((a, b)), |&(a, b)| (b > 0).then(|| (b, a % b)))
+            .take_while(|&(_, b)| b > 0)
+            .map(|(a, b)| format!("{a} = {} * {b} + {}", a / b, a % b))
+            .collect()
+    }
+
+    fn question(numbers: &[u32], kind: Kind) -> Question {
+        let list: Vec<_> = numbers.iter().map(ToString::to_string).collect();
+        let name = kind.name().to_uppercase();
+
+        // Pairwise, the third number with the result of the first two
+        let mut explanation = vec![];
+        let mut result = u128::from(numbers[0]);
+        for (index, &number) in numbers.iter().enumerate().skip(1) {
+            let number = u128::from(number);
+            let gcd = Self::gcd(result, number);
+            explanation.extend(Self::euclidean_steps(result, number));
+            let operands = if index == 1 {
+                format!("{}, {}", numbers[0], numbers[1])
+            } else {
+                format!("{result}, {number}")
+            };
+            explanation.push(format!("GCD({operands}) = {gcd}"));
+            result = match kind {
+                Kind::Gcd => gcd,
+                Kind::Lcm => {
+                    let lcm = result / gcd * number;
+                    explanation.push(format!(
+                        "LCM({operands}) = {result} * {number} / {gcd} = {lcm}"
+                    ));
+                    lcm
+                }
+            };
+        }
+
+        Question::builder()
+            .question(&format!("{name}({})", list.join(", ")))
+            .answer(&result.to_string())
+            .key(&question_key(
+                CMD,
+                &format!("{}-{}", kind.name(), list.join("-")),
+            ))
+            .categories(&[Category::new(
+                CATEGORY_QUESTION,
+                kind.name(),
+                i64::from(kind == Kind::Lcm),
+            )])
+            .explanation(&explanation.join("\n"))
+            .build()
+    }
+}
+
+impl Base for Gcd {
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
+            .take(count as usize)
+            .collect()
+    }
+
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let mut parts = question_id(CMD, key)?.split('-');
+        let kind = match parts.next()? {
+            MODE_GCD => Kind::Gcd,
+            MODE_LCM => Kind::Lcm,
+            _ => return None,
+        };
+        let numbers = parts
+            .map(|number| number.parse::<u32>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let in_range = numbers
+            .iter()
+            .zip(&self.boundaries)
+            .all(|(number, (lower, upper))| (lower..=upper).contains(&number));
+        (self.mode.allows(kind) && self.numbers.allows(numbers.len()) && in_range)
+            .then(|| Self::question(&numbers, kind))
+    }
+
+    fn wants_to_print_help(&self) -> bool {
+        self.show_help
+    }
+
+    fn get_help_text(&self) -> String {
+        let definitions = &self.arg_definitions;
+        let options = help::Options::new("Gcd options", definitions);
+        help::build(
+            &Self::usage(),
+            Some(&Self::additional_info()),
+            &options,
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::{assert_err_message, to_args};
+
+    #[test]
+    fn build_gcd_defaults() {
+        let gcd = Gcd::build(&to_args(&[])).expect("Should build correctly with no args");
+        assert!(!gcd.show_help);
+        assert_eq!(gcd.mode, Mode::Mixed);
+        assert_eq!(gcd.numbers, Numbers::Two);
+        assert_eq!(gcd.boundaries, [(1, 100); 3]);
+
+        let gcd = Gcd::build(&to_args(&[
+            "-m",
+            "lcm",
+            "--numbers=3",
+            "--lower-boundary-1=10",
+            "--upper-boundary-3=20",
+        ]))
+        .expect("Should build correctly");
+        assert_eq!(gcd.mode, Mode::Only(Kind::Lcm));
+        assert_eq!(gcd.numbers, Numbers::Three);
+        assert_eq!(gcd.boundaries, [(10, 100), (1, 100), (1, 20)]);
+    }
+
+    #[test]
+    fn build_gcd_incorrect_args() {
+        let err = Gcd::build(&to_args(&["--lower-boundary-2=0"])).unwrap_err();
+        assert!(err.contains("lower boundary must be at least 1"));
+        let err = Gcd::build(&to_args(&[
+            "--lower-boundary-3=20",
+            "--upper-boundary-3=10",
+        ]))
+        .unwrap_err();
+        assert!(err.contains("lower boundary must be less than or equal to upper boundary"));
+        let err = Gcd::build(&to_args(&["--numbers=4"])).unwrap_err();
+        assert!(err.contains("invalid option argument"));
+    }
+
+    #[test]
+    fn error_message() {
+        let message = Gcd::build_err_message(Some("something wrong".to_string()));
+        assert_err_message(&message, CMD, "something wrong");
+    }
+
+    #[test]
+    fn gcd_questions() {
+        let question = Gcd::question(&[252, 105], Kind::Gcd);
+        assert_eq!(question.prompt(), "GCD(252, 105)");
+        assert_eq!(question.correct_answer(), "21");
+        assert_eq!(question.key(), Some(&"gcd:gcd-252-105".to_string()));
+        assert_eq!(question.categories(), [Category::new("question", "gcd", 0)]);
+        assert_eq!(
+            question.explanation(),
+            Some(
+                &"252 = 2 * 105 + 42\n105 = 2 * 42 + 21\n42 = 2 * 21 + 0\nGCD(252, 105) = 21"
+                    .to_string()
+            )
+        );
+
+        let question = Gcd::question(&[12, 18, 8], Kind::Gcd);
+        assert_eq!(question.prompt(), "GCD(12, 18, 8)");
+        assert_eq!(question.correct_answer(), "2");
+        assert_eq!(
+            question.explanation(),
+            Some(
+                &"18 = 1 * 12 + 6\n12 = 2 * 6 + 0\nGCD(12, 18) = 6\n8 = 1 * 6 + 2\n6 = 3 * 2 + 0\nGCD(6, 8) = 2"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn lcm_questions() {
+        let question = Gcd::question(&[4, 6], Kind::Lcm);
+        assert_eq!(question.prompt(), "LCM(4, 6)");
+        assert_eq!(question.correct_answer(), "12");
+        assert_eq!(question.key(), Some(&"gcd:lcm-4-6".to_string()));
+        assert_eq!(
+            question.explanation(),
+            Some(
+                &"6 = 1 * 4 + 2\n4 = 2 * 2 + 0\nGCD(4, 6) = 2\nLCM(4, 6) = 4 * 6 / 2 = 12"
+                    .to_string()
+            )
+        );
+
+        assert_eq!(Gcd::question(&[4, 6, 10], Kind::Lcm).correct_answer(), "60");
+        assert_eq!(Gcd::question(&[7, 7], Kind::Lcm).correct_answer(), "7");
+        // Pairwise coprime, the product doesn't fit in u64
+        let question = Gcd::question(&[u32::MAX, u32::MAX - 1, u32::MAX - 2], Kind::Lcm);
+        assert_eq!(
+            question.correct_answer().parse::<u128>().unwrap(),
+            u128::from(u32::MAX) * u128::from(u32::MAX - 1) * u128::from(u32::MAX - 2)
+        );
+    }
+
+    #[test]
+    fn generated_questions() {
+        let gcd = Gcd::build(&to_args(&[
+            "--numbers=mixed",
+            "--lower-boundary-1=10",
+            "--upper-boundary-1=20",
+            "--upper-boundary-2=5",
+            "--lower-boundary-3=50",
+        ]))
+        .expect("Should build correctly");
+        let questions = gcd.generate_questions(200, &mut rand::thread_rng());
+        for question in &questions {
+            let key = question.key().unwrap();
+            let numbers: Vec<u32> = key.split('-').skip(1).map(|n| n.parse().unwrap()).collect();
+            assert!((10..=20).contains(&numbers[0]));
+            assert!((1..=5).contains(&numbers[1]));
+            assert!(numbers.len() == 2 || numbers[2] >= 50);
+            assert!(gcd.question_from_key(key).is_some());
+        }
+        let common_factor = questions
+            .iter()
+            .filter(|question| {
+                let numbers: Vec<u32> = question
+                    .key()
+                    .unwrap()
+                    .split('-')
+                    .skip(1)
+                    .map(|n| n.parse().unwrap())
+                    .collect();
+                Gcd::gcd_of(&numbers) > 1
+            })
+            .count();
+        assert!((100..=190).contains(&common_factor), "{common_factor}");
+    }
+
+    #[test]
+    fn question_from_key() {
+        let gcd = Gcd::build(&to_args(&["-m", "gcd"])).expect("Should build correctly");
+        assert!(gcd.question_from_key("gcd:gcd-12-18").is_some());
+        assert!(gcd.question_from_key("gcd:lcm-12-18").is_none());
+        assert!(gcd.question_from_key("gcd:gcd-12-18-8").is_none());
+        assert!(gcd.question_from_key("gcd:gcd-12-101").is_none());
+        assert!(gcd.question_from_key("gcd:gcd-12").is_none());
+        assert!(gcd.question_from_key("gcd:gcd-12-x").is_none());
+
+        let gcd = Gcd::build(&to_args(&["--numbers=3"])).expect("Should build correctly");
+        assert!(gcd.question_from_key("gcd:lcm-12-18-8").is_some());
+        assert!(gcd.question_from_key("gcd:lcm-12-18").is_none());
+    }
+
+    #[test]
+    fn help_text() {
+        let gcd = Gcd::build(&to_args(&["-h"])).expect("Should build correctly");
+        assert!(gcd.wants_to_print_help());
+        let help_text = gcd.get_help_text();
+        assert!(help_text.contains("Gcd options"));
+        assert!(help_text.contains("Usage"));
+        assert!(help_text.contains("-m, --mode"));
+        assert!(help_text.contains("--numbers"));
+        assert!(help_text.contains("--lower-boundary-1"));
+        assert!(help_text.contains("--upper-boundary-3"));
+    }
+}
@@ -1,11 +1,12 @@
 use std::iter;
+use std::sync::atomic::{AtomicU32, Ordering};
 
-use rand::Rng;
+use rand::{Rng, RngCore};
 
-use super::Base;
+use super::{question_id, question_key, Adjustment, Base, Level};
 use crate::application::APP_NAME;
 use crate::args::prelude::*;
-use crate::question::Question;
+use crate::question::{Category, Question};
 
 pub const CMD: &str = "powers";
 
@@ -13,6 +14,45 @@ const ARG_ID_HELP: &str = "help";
 const ARG_ID_BASE: &str = "base";
 const ARG_ID_LOWER_BOUNDARY: &str = "lower_boundary";
 const ARG_ID_UPPER_BOUNDARY: &str = "upper_boundary";
+const ARG_ID_MODE: &str = "mode";
+
+const MODE_FORWARD: &str = "forward";
+const MODE_INVERSE: &str = "inverse";
+const MODE_MIXED: &str = "mixed";
+
+const CATEGORY_EXPONENT: &str = "exponent";
+
+/// Number of exponents added to the lower boundary in the initial range of adaptive mode
+const ADAPTIVE_INITIAL_SPAN: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// Find the value of the power, e.g. "2^13"
+    Forward,
+    /// Find the exponent (logarithm), e.g. "2^? = 8192"
+    Inverse,
+    /// Both of them, alternating (starting with the power)
+    Mixed,
+}
+
+impl Mode {
+    fn from_string(value: &str) -> Mode {
+        match value {
+            MODE_FORWARD => Mode::Forward,
+            MODE_INVERSE => Mode::Inverse,
+            MODE_MIXED => Mode::Mixed,
+            _ => panic!("incorrect value for Mode"),
+        }
+    }
+
+    fn allows(self, inverse: bool) -> bool {
+        match self {
+            Mode::Forward => !inverse,
+            Mode::Inverse => inverse,
+            Mode::Mixed => true,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Powers {
@@ -22,6 +62,11 @@ pub struct Powers {
     base: u32,
     lower_boundary: u32,
     upper_boundary: u32,
+    mode: Mode,
+    /// Number of questions generated so far, used to alternate directions in mixed mode
+    generated: AtomicU32,
+
+    level: Level,
 }
 
 impl Powers {
@@ -43,6 +88,9 @@ impl Powers {
             &parsed_args,
             &arg_definitions,
         );
+        let mode =
+            String::set_value_from_arg_or_default(ARG_ID_MODE, &parsed_args, &arg_definitions);
+        let mode = Mode::from_string(&mode);
 
         if lower_boundary > upper_boundary {
             return Err(Self::build_err_message(Some(
@@ -59,12 +107,26 @@ impl Powers {
             )));
         }
 
+        // every power of 0 (except 0^0) and 1 is the same, so the exponent can't be found
+        if mode != Mode::Forward && base < 2 {
+            return Err(Self::build_err_message(Some(
+                "inverse questions need a base of at least 2".to_string(),
+            )));
+        }
+
+        let level = Level::new(
+            upper_boundary.saturating_sub(lower_boundary.saturating_add(ADAPTIVE_INITIAL_SPAN)),
+        );
+
         Ok(Self {
             arg_definitions,
             show_help,
             base,
             lower_boundary,
             upper_boundary,
+            mode,
+            generated: AtomicU32::new(0),
+            level,
         })
     }
 
@@ -96,7 +158,7 @@ impl Powers {
             .default_value()
             .to_string();
 
-        format!("Practise powers with a customisable base and exponent range.\nBy default, the base is {default_base}, with exponents ranging from {default_lower_boundary} to {default_upper_boundary}.")
+        format!("Practise powers with a customisable base and exponent range.\nBy default, the base is {default_base}, with exponents ranging from {default_lower_boundary} to {default_upper_boundary}.\nIn inverse mode, the power is given and the exponent has to be found.")
     }
 
     fn build_arg_definitions() -> Vec<Arg> {
@@ -141,6 +203,24 @@ impl Powers {
                 .stop_parsing(false)
                 .default_value(ArgValue::UnsignedInt(16))
                 .build(),
+            Arg::builder()
+                .id(ARG_ID_MODE)
+                .short_name('m')
+                .long_name("mode")
+                .description(vec![
+                    "Set the kind of questions: forward (find".to_string(),
+                    "the power, e.g. 2^13), inverse (find the".to_string(),
+                    "exponent, e.g. 2^? = 8192) or mixed (both".to_string(),
+                    "of them, alternating) (default: forward).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    MODE_FORWARD.to_string(),
+                    MODE_INVERSE.to_string(),
+                    MODE_MIXED.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(MODE_FORWARD.to_string()))
+                .build(),
         ]
     }
 
@@ -159,16 +239,59 @@ impl Powers {
         }
     }
 
-    fn generate_question(&self) -> Question {
-        let mut rng = rand::thread_rng();
-        let exp = rng.gen_range(self.lower_boundary..=self.upper_boundary);
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let exp = rng.gen_range(self.lower_boundary..=self.adaptive_upper_boundary());
+        let inverse = match self.mode {
+            Mode::Forward => false,
+            Mode::Inverse => true,
+            Mode::Mixed => self.generated.fetch_add(1, Ordering::Relaxed) % 2 == 1,
+        };
+        self.question(exp, inverse)
+    }
+
+    /// Maximum exponent at the current difficulty level
+    fn adaptive_upper_boundary(&self) -> u32 {
+        self.upper_boundary - (self.level.max - self.level.get())
+    }
+
+    /// Asks for the exponent instead of the power if `inverse` is set
+    fn question(&self, exp: u32, inverse: bool) -> Question {
         let result = u64::from(self.base).pow(exp); // Won't overflow, checked during Powers construction
+        let base = self.base;
+        let (prompt, answer, id) = if inverse {
+            (
+                format!("{base}^? = {result}"),
+                exp.to_string(),
+                format!("log{base}({result})"),
+            )
+        } else {
+            let id = format!("{base}^{exp}");
+            (id.clone(), result.to_string(), id)
+        };
         Question::builder()
-            .question(&format!("{base}^{exp}", base = self.base))
-            .answer(&result.to_string())
+            .question(&prompt)
+            .answer(&answer)
+            .key(&question_key(CMD, &id))
+            .categories(&[Category::new(
+                CATEGORY_EXPONENT,
+                &exp.to_string(),
+                exp.into(),
+            )])
             .build()
     }
 
+    /// Returns the exponent of the power of `base` equal to `value`, if there is one
+    fn exact_exponent(base: u32, value: u64) -> Option<u32> {
+        let base = u64::from(base);
+        let mut power = 1;
+        let mut exp = 0;
+        while power < value {
+            power = power.checked_mul(base)?;
+            exp += 1;
+        }
+        (power == value).then_some(exp)
+    }
+
     fn calculate_max_exponent(base: u32, chosen_exponent: u32) -> u32 {
         let mut low = 0;
         let mut high = chosen_exponent;
@@ -188,12 +311,45 @@ impl Powers {
 }
 
 impl Base for Powers {
-    fn generate_questions(&self, count: u32) -> Vec<Question> {
-        iter::repeat_with(|| self.generate_question())
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
             .take(count as usize)
             .collect()
     }
 
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let id = question_id(CMD, key)?;
+        let inverse = id.starts_with("log");
+        let (base, exp) = if inverse {
+            let (base, value) = id.strip_prefix("log")?.strip_suffix(')')?.split_once('(')?;
+            let base = base.parse::<u32>().ok()?;
+            (base, Self::exact_exponent(base, value.parse().ok()?)?)
+        } else {
+            let (base, exp) = id.split_once('^')?;
+            (base.parse::<u32>().ok()?, exp.parse::<u32>().ok()?)
+        };
+        if base == self.base
+            && self.mode.allows(inverse)
+            && (self.lower_boundary..=self.upper_boundary).contains(&exp)
+        {
+            Some(self.question(exp, inverse))
+        } else {
+            None
+        }
+    }
+
+    fn adapt(&self, adjustment: Adjustment) -> bool {
+        self.level.adjust(adjustment)
+    }
+
+    fn current_range(&self) -> Option<String> {
+        Some(format!(
+            "exponents {}-{}",
+            self.lower_boundary,
+            self.adaptive_upper_boundary()
+        ))
+    }
+
     fn wants_to_print_help(&self) -> bool {
         self.show_help
     }
@@ -212,6 +368,9 @@ impl Base for Powers {
 
 #[cfg(test)]
 mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
     use super::*;
 
     #[test]
@@ -301,23 +460,152 @@ mod tests {
     fn question_generation() {
         let args = ["-u".to_string(), "1".to_string()];
         let powers = Powers::build(&args).expect("Should build correctly");
-        let question = powers.generate_question();
+        let question = powers.generate_question(&mut rand::thread_rng());
         assert_eq!(question.prompt(), "2^1");
         assert_eq!(question.correct_answer(), "2");
         assert!(question.is_answer_correct("2"));
+        assert_eq!(question.key(), Some(&"powers:2^1".to_string()));
+        assert_eq!(question.categories(), [Category::new("exponent", "1", 1)]);
+    }
+
+    #[test]
+    fn question_from_key() {
+        let args = [
+            "-l".to_string(),
+            "2".to_string(),
+            "-u".to_string(),
+            "10".to_string(),
+        ];
+        let powers = Powers::build(&args).expect("Should build correctly");
+
+        let question = powers
+            .question_from_key("powers:2^10")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "2^10");
+        assert_eq!(question.correct_answer(), "1024");
+
+        assert!(powers.question_from_key("powers:2^11").is_none());
+        assert!(powers.question_from_key("powers:2^1").is_none());
+        assert!(powers.question_from_key("powers:3^4").is_none());
+        assert!(powers.question_from_key("powers:2^x").is_none());
+        assert!(powers.question_from_key("times_table:2*3").is_none());
+    }
+
+    #[test]
+    fn inverse_questions() {
+        let args = ["--mode=inverse".to_string()];
+        let powers = Powers::build(&args).expect("Should build correctly");
+        let question = powers.question(13, true);
+        assert_eq!(question.prompt(), "2^? = 8192");
+        assert_eq!(question.correct_answer(), "13");
+        assert!(question.is_answer_correct("13"));
+        assert_eq!(question.key(), Some(&"powers:log2(8192)".to_string()));
+        assert_eq!(question.categories(), [Category::new("exponent", "13", 13)]);
+
+        let question = powers
+            .question_from_key("powers:log2(8192)")
+            .expect("Key should be recognised");
+        assert_eq!(question.correct_answer(), "13");
+        assert!(powers.question_from_key("powers:log2(8191)").is_none());
+        assert!(powers.question_from_key("powers:log3(81)").is_none());
+        assert!(powers.question_from_key("powers:log2(131072)").is_none());
+        assert!(powers.question_from_key("powers:2^13").is_none());
+
+        let args = ["-m".to_string(), "mixed".to_string()];
+        let powers = Powers::build(&args).expect("Should build correctly");
+        assert!(powers.question_from_key("powers:log2(8192)").is_some());
+        assert!(powers.question_from_key("powers:2^13").is_some());
+        let prompts: Vec<_> = powers
+            .generate_questions(100, &mut rand::thread_rng())
+            .iter()
+            .map(|question| question.prompt().clone())
+            .collect();
+        assert!(prompts
+            .iter()
+            .enumerate()
+            .all(|(i, prompt)| prompt.contains('?') == (i % 2 == 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "inverse questions need a base of at least 2")]
+    fn build_powers_inverse_base_one() {
+        let args = [
+            "-b".to_string(),
+            "1".to_string(),
+            "-m".to_string(),
+            "inverse".to_string(),
+        ];
+        Powers::build(&args).unwrap();
+    }
+
+    #[test]
+    fn exact_exponent() {
+        assert_eq!(Powers::exact_exponent(2, 1), Some(0));
+        assert_eq!(Powers::exact_exponent(2, 8192), Some(13));
+        assert_eq!(Powers::exact_exponent(2, 8191), None);
+        assert_eq!(Powers::exact_exponent(10, 1000), Some(3));
+        assert_eq!(Powers::exact_exponent(2, u64::MAX), None);
+    }
+
+    #[test]
+    fn adaptive_range() {
+        let args = [
+            "-l".to_string(),
+            "2".to_string(),
+            "-u".to_string(),
+            "7".to_string(),
+        ];
+        let powers = Powers::build(&args).expect("Should build correctly");
+        assert_eq!(powers.current_range(), Some("exponents 2-7".to_string()));
+
+        assert!(powers.adapt(Adjustment::Start));
+        assert_eq!(powers.current_range(), Some("exponents 2-5".to_string()));
+        let answers: Vec<u64> = powers
+            .generate_questions(20, &mut rand::thread_rng())
+            .iter()
+            .map(|question| question.correct_answer().parse().unwrap())
+            .collect();
+        assert!(answers.iter().all(|&answer| answer <= 32));
+
+        assert!(powers.adapt(Adjustment::Harder));
+        assert!(powers.adapt(Adjustment::Harder));
+        assert!(!powers.adapt(Adjustment::Harder));
+        assert_eq!(powers.current_range(), Some("exponents 2-7".to_string()));
+        assert!(powers.adapt(Adjustment::Easier));
+        assert_eq!(powers.current_range(), Some("exponents 2-6".to_string()));
+
+        // Range narrower than the initial span
+        let args = ["-u".to_string(), "2".to_string()];
+        let powers = Powers::build(&args).expect("Should build correctly");
+        assert!(!powers.adapt(Adjustment::Start));
+        assert_eq!(powers.current_range(), Some("exponents 1-2".to_string()));
     }
 
     #[test]
     fn multiple_question_generation() {
         let args = ["--base=3".to_string()];
         let powers = Powers::build(&args).expect("Should build correctly");
-        let questions = powers.generate_questions(10);
+        let questions = powers.generate_questions(10, &mut rand::thread_rng());
         assert_eq!(questions.len(), 10);
         assert!(questions
             .iter()
             .all(|question| question.prompt().starts_with("3^")));
     }
 
+    #[test]
+    fn seeded_question_generation() {
+        let powers = Powers::build(&[]).expect("Should build correctly");
+        let prompts = |seed| {
+            powers
+                .generate_questions(10, &mut StdRng::seed_from_u64(seed))
+                .iter()
+                .map(|question| question.prompt().clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(prompts(7), prompts(7));
+        assert_ne!(prompts(7), prompts(8));
+    }
+
     #[test]
     fn print_help_only() {
         let args = [];
@@ -347,5 +635,6 @@ mod tests {
         assert!(help_text.contains("-b, --base"));
         assert!(help_text.contains("-l, --lower-boundary"));
         assert!(help_text.contains("-u, --upper-boundary"));
+        assert!(help_text.contains("-m, --mode"));
     }
 }
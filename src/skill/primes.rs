@@ -0,0 +1,513 @@
+use std::iter;
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use super::{question_id, question_key, Base};
+use crate::application::APP_NAME;
+use crate::args::prelude::*;
+use crate::question::{Category, Matcher, Question};
+
+pub const CMD: &str = "primes";
+
+const ARG_ID_HELP: &str = "help";
+const ARG_ID_MODE: &str = "mode";
+const ARG_ID_LOWER_BOUNDARY: &str = "lower_boundary";
+const ARG_ID_UPPER_BOUNDARY: &str = "upper_boundary";
+
+const MODE_PRIMALITY: &str = "primality";
+const MODE_FACTORISATION: &str = "factorisation";
+const MODE_MIXED: &str = "mixed";
+
+const DEFAULT_LOWER_BOUNDARY: u32 = 2;
+const DEFAULT_UPPER_BOUNDARY: u32 = 1000;
+const CATEGORY_QUESTION: &str = "question";
+
+/// Number of draws after which a primality question is asked about any number, if there
+/// are no (or hardly any) primes or composite numbers in the range
+const MAX_DRAWS: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    /// "Is 221 prime?"
+    Primality,
+    /// "Factorise 360"
+    Factorisation,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Primality => MODE_PRIMALITY,
+            Kind::Factorisation => MODE_FACTORISATION,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Only(Kind),
+    /// Either of them, chosen randomly
+    Mixed,
+}
+
+impl Mode {
+    fn from_string(value: &str) -> Mode {
+        match value {
+            MODE_PRIMALITY => Mode::Only(Kind::Primality),
+            MODE_FACTORISATION => Mode::Only(Kind::Factorisation),
+            MODE_MIXED => Mode::Mixed,
+            _ => panic!("incorrect value for Mode"),
+        }
+    }
+
+    fn allows(self, kind: Kind) -> bool {
+        match self {
+            Mode::Only(only) => only == kind,
+            Mode::Mixed => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Primes {
+    arg_definitions: Vec<Arg>,
+    show_help: bool,
+
+    mode: Mode,
+    lower_boundary: u32,
+    upper_boundary: u32,
+}
+
+impl Primes {
+    pub fn build(args: &[String]) -> Result<Self, String> {
+        let arg_definitions = Self::build_arg_definitions();
+        let parsed_args = parser::parse_and_validate_arg_list(args, &arg_definitions)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        let show_help =
+            bool::set_value_from_arg_or_default(ARG_ID_HELP, &parsed_args, &arg_definitions);
+        let mode =
+            String::set_value_from_arg_or_default(ARG_ID_MODE, &parsed_args, &arg_definitions);
+        let lower_boundary = u32::set_value_from_arg_or_default(
+            ARG_ID_LOWER_BOUNDARY,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let upper_boundary = u32::set_value_from_arg_or_default(
+            ARG_ID_UPPER_BOUNDARY,
+            &parsed_args,
+            &arg_definitions,
+        );
+
+        if lower_boundary > upper_boundary {
+            return Err(Self::build_err_message(Some(
+                "lower boundary must be less than or equal to upper boundary".to_string(),
+            )));
+        }
+
+        // 0 and 1 are neither prime nor composite
+        if lower_boundary < 2 {
+            return Err(Self::build_err_message(Some(
+                "lower boundary must be at least 2".to_string(),
+            )));
+        }
+
+        Ok(Self {
+            arg_definitions,
+            show_help,
+            mode: Mode::from_string(&mode),
+            lower_boundary,
+            upper_boundary,
+        })
+    }
+
+    fn build_arg_definitions() -> Vec<Arg> {
+        vec![
+            Arg::builder()
+                .id(ARG_ID_HELP)
+                .short_name('h')
+                .long_name("help")
+                .description(vec!["Display help for primes command.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(true)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_MODE)
+                .short_name('m')
+                .long_name("mode")
+                .description(vec![
+                    "Set the kind of questions: primality (e.g.".to_string(),
+                    "Is 221 prime?), factorisation (e.g. Factorise".to_string(),
+                    "360) or mixed (either of them, chosen".to_string(),
+                    "randomly) (default: mixed).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    MODE_PRIMALITY.to_string(),
+                    MODE_FACTORISATION.to_string(),
+                    MODE_MIXED.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(MODE_MIXED.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_LOWER_BOUNDARY)
+                .long_name("lower-boundary")
+                .description(vec![
+                    "Set the minimum value".to_string(),
+                    format!("of the number (default: {DEFAULT_LOWER_BOUNDARY})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_LOWER_BOUNDARY))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_UPPER_BOUNDARY)
+                .long_name("upper-boundary")
+                .description(vec![
+                    "Set the maximum value".to_string(),
+                    format!("of the number (default: {DEFAULT_UPPER_BOUNDARY})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_UPPER_BOUNDARY))
+                .build(),
+        ]
+    }
+
+    fn usage() -> String {
+        format!("Usage: {APP_NAME} [option]... primes [primes_option]...")
+    }
+
+    fn help_prompt() -> String {
+        format!("Try '{APP_NAME} primes --help' for more information.")
+    }
+
+    fn additional_info() -> String {
+        let mut text = String::new();
+        text.push_str("Practise recognising primes and factorising numbers into primes.\n");
+        text.push_str("Primality questions are answered with yes or no, and about half of the\n");
+        text.push_str("numbers asked about are prime. Factors can be given in any order, either\n");
+        text.push_str("repeated or as powers, e.g. 2^3*3^2*5 or 2*2*2*3*3*5 for 360.");
+        text
+    }
+
+    fn build_err_message(msg: Option<String>) -> String {
+        if let Some(msg) = msg {
+            format!(
+                "{}: {}: {}\n{}\n{}",
+                APP_NAME,
+                CMD,
+                msg,
+                Self::usage(),
+                Self::help_prompt()
+            )
+        } else {
+            format!("{}\n{}", Self::usage(), Self::help_prompt())
+        }
+    }
+
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let kind = match self.mode {
+            Mode::Only(kind) => kind,
+            Mode::Mixed => *[Kind::Primality, Kind::Factorisation]
+                .choose(rng)
+                .expect("not empty"),
+        };
+        let range = self.lower_boundary..=self.upper_boundary;
+        let number = match kind {
+            // Composite numbers are much more common, so both answers are drawn equally
+            // often (as long as the range has both)
+            Kind::Primality => {
+                let prime = rng.gen_bool(0.5);
+                let mut number = rng.gen_range(range.clone());
+                for _ in 1..MAX_DRAWS {
+                    if Self::is_prime(number) == prime {
+                        break;
+                    }
+                    number = rng.gen_range(range.clone());
+                }
+                number
+            }
+            Kind::Factorisation => rng.gen_range(range),
+        };
+        Self::question(number, kind)
+    }
+
+    /// Smallest prime factor of `number` (at least 2)
+    fn smallest_factor(number: u32) -> u32 {
+        (2..)
+            .take_while(|factor| u64::from(*factor) * u64::from(*factor) <= u64::from(number))
+            .find(|factor| number.is_multiple_of(*factor))
+            .unwrap_or(number)
+    }
+
+    fn is_prime(number: u32) -> bool {
+        number >= 2 && Self::smallest_factor(number) == number
+    }
+
+    /// Prime factors of `number` (at least 2) in ascending order, with repetitions
+    fn factors(number: u32) -> Vec<u32> {
+        iter::successors(Some(number), |&rest| {
+            (rest > 1).then(|| rest / Self::smallest_factor(rest))
+        })
+        .take_while(|&rest| rest > 1)
+        .map(Self::smallest_factor)
+        .collect()
+    }
+
+    /// Factors written as powers, e.g. "2^3*3^2*5"
+    fn format_factors(factors: &[u32]) -> String {
+        factors
+            .chunk_by(|a, b| a == b)
+            .map(|powers| match powers.len() {
+                1 => powers[0].to_string(),
+                exponent => format!("{}^{exponent}", powers[0]),
+            })
+            .collect::<Vec<_>>()
+            .join("*")
+    }
+
+    fn question(number: u32, kind: Kind) -> Question {
+        let factors = Self::factors(number);
+        let builder = match kind {
+            Kind::Primality => {
+                let prime = factors.len() == 1;
+                let (answer, alternative_answer) = if prime { ("yes", "y") } else { ("no", "n") };
+                let explanation = if prime {
+                    format!(
+                        "{number} is prime: no prime up to √{number} ≈ {:.1} divides it",
+                        f64::from(number).sqrt()
+                    )
+                } else {
+                    format!("{number} = {} * {}", factors[0], number / factors[0])
+                };
+                Question::builder()
+                    .question(&format!("Is {number} prime?"))
+                    .answer(answer)
+                    .alternative_answers(&[alternative_answer.to_string()])
+                    .allow_any_case(true)
+                    .explanation(&explanation)
+            }
+            Kind::Factorisation => {
+                // Dividing by the smallest prime factor until the prime is left
+                let mut rest = number;
+                let steps: Vec<_> = factors
+                    .iter()
+                    .take(factors.len() - 1)
+                    .map(|factor| {
+                        let quotient = rest / factor;
+                        let step = format!("{rest} / {factor} = {quotient}");
+                        rest = quotient;
+                        step
+                    })
+                    .collect();
+                let explanation = if steps.is_empty() {
+                    format!("{number} is prime")
+                } else {
+                    steps.join("\n")
+                };
+                Question::builder()
+                    .question(&format!("Factorise {number}"))
+                    .answer(&Self::format_factors(&factors))
+                    .matcher(Matcher::Factors)
+                    .explanation(&explanation)
+            }
+        };
+        builder
+            .key(&question_key(CMD, &format!("{}-{number}", kind.name())))
+            .categories(&[Category::new(
+                CATEGORY_QUESTION,
+                kind.name(),
+                i64::from(kind == Kind::Factorisation),
+            )])
+            .build()
+    }
+}
+
+impl Base for Primes {
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
+            .take(count as usize)
+            .collect()
+    }
+
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let (kind, number) = question_id(CMD, key)?.split_once('-')?;
+        let kind = match kind {
+            MODE_PRIMALITY => Kind::Primality,
+            MODE_FACTORISATION => Kind::Factorisation,
+            _ => return None,
+        };
+        let number = number.parse::<u32>().ok()?;
+        (self.mode.allows(kind) && (self.lower_boundary..=self.upper_boundary).contains(&number))
+            .then(|| Self::question(number, kind))
+    }
+
+    fn wants_to_print_help(&self) -> bool {
+        self.show_help
+    }
+
+    fn get_help_text(&self) -> String {
+        let definitions = &self.arg_definitions;
+        let options = help::Options::new("Primes options", definitions);
+        help::build(
+            &Self::usage(),
+            Some(&Self::additional_info()),
+            &options,
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::{assert_err_message, to_args};
+
+    #[test]
+    fn build_primes_defaults() {
+        let primes = Primes::build(&to_args(&[])).expect("Should build correctly with no args");
+        assert!(!primes.show_help);
+        assert_eq!(primes.mode, Mode::Mixed);
+        assert_eq!(primes.lower_boundary, 2);
+        assert_eq!(primes.upper_boundary, 1000);
+
+        let primes = Primes::build(&to_args(&["-m", "primality", "--upper-boundary=100"]))
+            .expect("Should build correctly");
+        assert_eq!(primes.mode, Mode::Only(Kind::Primality));
+        assert_eq!(primes.upper_boundary, 100);
+    }
+
+    #[test]
+    fn build_primes_incorrect_args() {
+        let err = Primes::build(&to_args(&["--lower-boundary=1"])).unwrap_err();
+        assert!(err.contains("lower boundary must be at least 2"));
+        let err =
+            Primes::build(&to_args(&["--lower-boundary=20", "--upper-boundary=10"])).unwrap_err();
+        assert!(err.contains("lower boundary must be less than or equal to upper boundary"));
+        let err = Primes::build(&to_args(&["--mode=forward"])).unwrap_err();
+        assert!(err.contains("invalid option argument"));
+    }
+
+    #[test]
+    fn error_message() {
+        let message = Primes::build_err_message(Some("something wrong".to_string()));
+        assert_err_message(&message, CMD, "something wrong");
+    }
+
+    #[test]
+    fn factors() {
+        assert_eq!(Primes::factors(2), [2]);
+        assert_eq!(Primes::factors(360), [2, 2, 2, 3, 3, 5]);
+        assert_eq!(Primes::factors(221), [13, 17]);
+        assert_eq!(Primes::factors(4_294_967_291), [4_294_967_291]);
+        assert_eq!(Primes::factors(u32::MAX), [3, 5, 17, 257, 65537]);
+        assert_eq!(Primes::format_factors(&Primes::factors(360)), "2^3*3^2*5");
+        assert_eq!(Primes::format_factors(&Primes::factors(1024)), "2^10");
+
+        let primes: Vec<_> = (0..30).filter(|&number| Primes::is_prime(number)).collect();
+        assert_eq!(primes, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    }
+
+    #[test]
+    fn questions() {
+        let question = Primes::question(221, Kind::Primality);
+        assert_eq!(question.prompt(), "Is 221 prime?");
+        assert_eq!(question.correct_answer(), "no");
+        assert!(question.is_answer_correct("N"));
+        assert!(!question.is_answer_correct("yes"));
+        assert_eq!(question.explanation(), Some(&"221 = 13 * 17".to_string()));
+        assert_eq!(question.key(), Some(&"primes:primality-221".to_string()));
+        assert_eq!(
+            question.categories(),
+            [Category::new("question", "primality", 0)]
+        );
+
+        let question = Primes::question(223, Kind::Primality);
+        assert_eq!(question.correct_answer(), "yes");
+        assert!(question.is_answer_correct("Y"));
+        assert_eq!(
+            question.explanation(),
+            Some(&"223 is prime: no prime up to √223 ≈ 14.9 divides it".to_string())
+        );
+
+        let question = Primes::question(360, Kind::Factorisation);
+        assert_eq!(question.prompt(), "Factorise 360");
+        assert_eq!(question.correct_answer(), "2^3*3^2*5");
+        assert!(question.is_answer_correct("2*2*2*3*3*5"));
+        assert!(question.is_answer_correct("5*3^2*2^3"));
+        assert!(!question.is_answer_correct("8*45"));
+        assert_eq!(
+            question.explanation(),
+            Some(&"360 / 2 = 180\n180 / 2 = 90\n90 / 2 = 45\n45 / 3 = 15\n15 / 3 = 5".to_string())
+        );
+        assert_eq!(
+            question.key(),
+            Some(&"primes:factorisation-360".to_string())
+        );
+
+        let question = Primes::question(13, Kind::Factorisation);
+        assert_eq!(question.correct_answer(), "13");
+        assert_eq!(question.explanation(), Some(&"13 is prime".to_string()));
+    }
+
+    #[test]
+    fn primality_questions_are_balanced() {
+        let primes = Primes::build(&to_args(&["-m", "primality"])).expect("Should build correctly");
+        let questions = primes.generate_questions(200, &mut rand::thread_rng());
+        let yes = questions
+            .iter()
+            .filter(|question| question.correct_answer() == "yes")
+            .count();
+        assert!((50..=150).contains(&yes), "{yes}");
+
+        // No primes in the range
+        let primes = Primes::build(&to_args(&[
+            "-m",
+            "primality",
+            "--lower-boundary=24",
+            "--upper-boundary=28",
+        ]))
+        .expect("Should build correctly");
+        for question in primes.generate_questions(10, &mut rand::thread_rng()) {
+            assert_eq!(question.correct_answer(), "no");
+        }
+    }
+
+    #[test]
+    fn question_from_key() {
+        let primes =
+            Primes::build(&to_args(&["--upper-boundary=500"])).expect("Should build correctly");
+        assert!(primes.question_from_key("primes:primality-221").is_some());
+        assert!(primes
+            .question_from_key("primes:factorisation-360")
+            .is_some());
+        assert!(primes
+            .question_from_key("primes:factorisation-501")
+            .is_none());
+        assert!(primes.question_from_key("primes:primality-1").is_none());
+        assert!(primes.question_from_key("primes:gcd-360").is_none());
+
+        let primes =
+            Primes::build(&to_args(&["-m", "factorisation"])).expect("Should build correctly");
+        assert!(primes.question_from_key("primes:primality-221").is_none());
+        for question in primes.generate_questions(10, &mut rand::thread_rng()) {
+            assert!(question.prompt().starts_with("Factorise "));
+            assert!(primes.question_from_key(question.key().unwrap()).is_some());
+        }
+    }
+
+    #[test]
+    fn help_text() {
+        let primes = Primes::build(&to_args(&["-h"])).expect("Should build correctly");
+        assert!(primes.wants_to_print_help());
+        let help_text = primes.get_help_text();
+        assert!(help_text.contains("Primes options"));
+        assert!(help_text.contains("Usage"));
+        assert!(help_text.contains("-m, --mode"));
+        assert!(help_text.contains("--lower-boundary"));
+        assert!(help_text.contains("--upper-boundary"));
+    }
+}
@@ -0,0 +1,449 @@
+use std::iter;
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use super::{question_id, question_key, Base};
+use crate::application::APP_NAME;
+use crate::args::prelude::*;
+use crate::question::{Category, Question};
+
+pub const CMD: &str = "roman";
+
+const ARG_ID_HELP: &str = "help";
+const ARG_ID_MODE: &str = "mode";
+const ARG_ID_LOWER_BOUNDARY: &str = "lower_boundary";
+const ARG_ID_UPPER_BOUNDARY: &str = "upper_boundary";
+
+const MODE_TO_ROMAN: &str = "to-roman";
+const MODE_FROM_ROMAN: &str = "from-roman";
+const MODE_MIXED: &str = "mixed";
+
+const MIN_NUMBER: u32 = 1;
+const MAX_NUMBER: u32 = 3999;
+const CATEGORY_DIRECTION: &str = "direction";
+
+/// Values of numerals in canonical subtractive notation, from the largest one
+const NUMERALS: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// Write the number in Roman numerals, e.g. "1994" is "MCMXCIV"
+    ToRoman,
+    /// Read the Roman numerals, e.g. "MCMXCIV" is "1994"
+    FromRoman,
+    /// Either of them, chosen randomly
+    Mixed,
+}
+
+impl Mode {
+    fn from_string(value: &str) -> Mode {
+        match value {
+            MODE_TO_ROMAN => Mode::ToRoman,
+            MODE_FROM_ROMAN => Mode::FromRoman,
+            MODE_MIXED => Mode::Mixed,
+            _ => panic!("incorrect value for Mode"),
+        }
+    }
+
+    fn allows(self, from_roman: bool) -> bool {
+        match self {
+            Mode::ToRoman => !from_roman,
+            Mode::FromRoman => from_roman,
+            Mode::Mixed => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Roman {
+    arg_definitions: Vec<Arg>,
+    show_help: bool,
+
+    mode: Mode,
+    lower_boundary: u32,
+    upper_boundary: u32,
+}
+
+impl Roman {
+    pub fn build(args: &[String]) -> Result<Self, String> {
+        let arg_definitions = Self::build_arg_definitions();
+        let parsed_args = parser::parse_and_validate_arg_list(args, &arg_definitions)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        let show_help =
+            bool::set_value_from_arg_or_default(ARG_ID_HELP, &parsed_args, &arg_definitions);
+        let mode =
+            String::set_value_from_arg_or_default(ARG_ID_MODE, &parsed_args, &arg_definitions);
+        let lower_boundary = u32::set_value_from_arg_or_default(
+            ARG_ID_LOWER_BOUNDARY,
+            &parsed_args,
+            &arg_definitions,
+        );
+        let upper_boundary = u32::set_value_from_arg_or_default(
+            ARG_ID_UPPER_BOUNDARY,
+            &parsed_args,
+            &arg_definitions,
+        );
+
+        if lower_boundary > upper_boundary {
+            return Err(Self::build_err_message(Some(
+                "lower boundary must be less than or equal to upper boundary".to_string(),
+            )));
+        }
+
+        if lower_boundary < MIN_NUMBER || upper_boundary > MAX_NUMBER {
+            return Err(Self::build_err_message(Some(format!(
+                "Roman numerals are supported for numbers from {MIN_NUMBER} to {MAX_NUMBER}"
+            ))));
+        }
+
+        Ok(Self {
+            arg_definitions,
+            show_help,
+            mode: Mode::from_string(&mode),
+            lower_boundary,
+            upper_boundary,
+        })
+    }
+
+    fn build_arg_definitions() -> Vec<Arg> {
+        vec![
+            Arg::builder()
+                .id(ARG_ID_HELP)
+                .short_name('h')
+                .long_name("help")
+                .description(vec!["Display help for roman command.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(true)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_MODE)
+                .short_name('m')
+                .long_name("mode")
+                .description(vec![
+                    "Set the kind of questions: to-roman (write".to_string(),
+                    "the number in Roman numerals), from-roman".to_string(),
+                    "(read Roman numerals) or mixed (either of".to_string(),
+                    "them, chosen randomly) (default: mixed).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    MODE_TO_ROMAN.to_string(),
+                    MODE_FROM_ROMAN.to_string(),
+                    MODE_MIXED.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(MODE_MIXED.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_LOWER_BOUNDARY)
+                .short_name('l')
+                .long_name("lower-boundary")
+                .description(vec![format!(
+                    "Set the minimum number (default: {MIN_NUMBER})."
+                )])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(MIN_NUMBER))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_UPPER_BOUNDARY)
+                .short_name('u')
+                .long_name("upper-boundary")
+                .description(vec![format!(
+                    "Set the maximum number (default: {MAX_NUMBER})."
+                )])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(MAX_NUMBER))
+                .build(),
+        ]
+    }
+
+    fn usage() -> String {
+        format!("Usage: {APP_NAME} [option]... roman [roman_option]...")
+    }
+
+    fn help_prompt() -> String {
+        format!("Try '{APP_NAME} roman --help' for more information.")
+    }
+
+    fn additional_info() -> String {
+        let mut text = String::new();
+        text.push_str("Practise converting numbers to and from Roman numerals.\n");
+        text.push_str("Numbers must be written in canonical subtractive notation, e.g. 1994 is\n");
+        text.push_str("MCMXCIV (not MDCCCCLXXXXIIII or MXMIV). Case doesn't matter.");
+        text
+    }
+
+    fn build_err_message(msg: Option<String>) -> String {
+        if let Some(msg) = msg {
+            format!(
+                "{}: {}: {}\n{}\n{}",
+                APP_NAME,
+                CMD,
+                msg,
+                Self::usage(),
+                Self::help_prompt()
+            )
+        } else {
+            format!("{}\n{}", Self::usage(), Self::help_prompt())
+        }
+    }
+
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let number = rng.gen_range(self.lower_boundary..=self.upper_boundary);
+        let from_roman = match self.mode {
+            Mode::ToRoman => false,
+            Mode::FromRoman => true,
+            Mode::Mixed => *[false, true].choose(rng).expect("not empty"),
+        };
+        Self::question(number, from_roman)
+    }
+
+    /// Numerals `number` is made of, e.g. "M", "CM", "XC" and "IV" for 1994
+    fn numerals(number: u32) -> Vec<(u32, &'static str)> {
+        let mut rest = number;
+        NUMERALS
+            .iter()
+            .flat_map(|&(value, numeral)| {
+                let count = rest / value;
+                rest %= value;
+                iter::repeat_n((value, numeral), count as usize)
+            })
+            .collect()
+    }
+
+    fn to_roman(number: u32) -> String {
+        Self::numerals(number)
+            .into_iter()
+            .map(|(_, numeral)| numeral)
+            .collect()
+    }
+
+    /// Reads Roman numerals in canonical notation (in uppercase). Returns `None` if
+    /// `roman` is anything else, e.g. "IIII" or "IM".
+    fn from_roman(roman: &str) -> Option<u32> {
+        let mut rest = roman;
+        let mut number = 0;
+        for &(value, numeral) in &NUMERALS {
+            while let Some(stripped) = rest.strip_prefix(numeral) {
+                number += value;
+                rest = stripped;
+            }
+        }
+        (rest.is_empty() && number > 0 && Self::to_roman(number) == roman).then_some(number)
+    }
+
+    fn question(number: u32, from_roman: bool) -> Question {
+        let roman = Self::to_roman(number);
+        // The key is the number or the numerals given in the question
+        let (prompt, answer, id, direction, order) = if from_roman {
+            (
+                format!("What is {roman}?"),
+                number.to_string(),
+                roman.clone(),
+                MODE_FROM_ROMAN,
+                1,
+            )
+        } else {
+            (
+                format!("What is {number} in Roman numerals?"),
+                roman.clone(),
+                number.to_string(),
+                MODE_TO_ROMAN,
+                0,
+            )
+        };
+        let numerals = Self::numerals(number);
+        let values: Vec<_> = numerals
+            .iter()
+            .map(|(value, _)| value.to_string())
+            .collect();
+        let letters: Vec<_> = numerals.iter().map(|(_, numeral)| *numeral).collect();
+        let explanation = format!(
+            "{number} = {} = {} = {roman}",
+            values.join(" + "),
+            letters.join(" + ")
+        );
+
+        Question::builder()
+            .question(&prompt)
+            .answer(&answer)
+            .allow_any_case(true)
+            .key(&question_key(CMD, &id))
+            .categories(&[Category::new(CATEGORY_DIRECTION, direction, order)])
+            .explanation(&explanation)
+            .build()
+    }
+}
+
+impl Base for Roman {
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
+            .take(count as usize)
+            .collect()
+    }
+
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let id = question_id(CMD, key)?;
+        let (number, from_roman) = match id.parse::<u32>() {
+            Ok(number) => (number, false),
+            Err(_) => (Self::from_roman(id)?, true),
+        };
+        (self.mode.allows(from_roman)
+            && (self.lower_boundary..=self.upper_boundary).contains(&number))
+        .then(|| Self::question(number, from_roman))
+    }
+
+    fn wants_to_print_help(&self) -> bool {
+        self.show_help
+    }
+
+    fn get_help_text(&self) -> String {
+        let definitions = &self.arg_definitions;
+        let options = help::Options::new("Roman options", definitions);
+        help::build(
+            &Self::usage(),
+            Some(&Self::additional_info()),
+            &options,
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::{assert_err_message, to_args};
+
+    #[test]
+    fn build_roman_defaults() {
+        let roman = Roman::build(&to_args(&[])).expect("Should build correctly with no args");
+        assert!(!roman.show_help);
+        assert_eq!(roman.mode, Mode::Mixed);
+        assert_eq!(roman.lower_boundary, 1);
+        assert_eq!(roman.upper_boundary, 3999);
+
+        let roman = Roman::build(&to_args(&["-m", "to-roman", "-u", "100"]))
+            .expect("Should build correctly");
+        assert_eq!(roman.mode, Mode::ToRoman);
+        assert_eq!(roman.upper_boundary, 100);
+    }
+
+    #[test]
+    fn build_roman_incorrect_args() {
+        let err = Roman::build(&to_args(&["-l", "0"])).unwrap_err();
+        assert!(err.contains("supported for numbers from 1 to 3999"));
+        let err = Roman::build(&to_args(&["-u", "4000"])).unwrap_err();
+        assert!(err.contains("supported for numbers from 1 to 3999"));
+        let err = Roman::build(&to_args(&["-l", "20", "-u", "10"])).unwrap_err();
+        assert!(err.contains("lower boundary must be less than or equal to upper boundary"));
+        let err = Roman::build(&to_args(&["--mode=forward"])).unwrap_err();
+        assert!(err.contains("invalid option argument"));
+    }
+
+    #[test]
+    fn error_message() {
+        let message = Roman::build_err_message(Some("something wrong".to_string()));
+        assert_err_message(&message, CMD, "something wrong");
+    }
+
+    #[test]
+    fn conversions() {
+        for (number, roman) in [
+            (1, "I"),
+            (4, "IV"),
+            (9, "IX"),
+            (14, "XIV"),
+            (40, "XL"),
+            (90, "XC"),
+            (400, "CD"),
+            (1994, "MCMXCIV"),
+            (2026, "MMXXVI"),
+            (3888, "MMMDCCCLXXXVIII"),
+            (3999, "MMMCMXCIX"),
+        ] {
+            assert_eq!(Roman::to_roman(number), roman);
+            assert_eq!(Roman::from_roman(roman), Some(number));
+        }
+        for number in MIN_NUMBER..=MAX_NUMBER {
+            assert_eq!(Roman::from_roman(&Roman::to_roman(number)), Some(number));
+        }
+        for roman in [
+            "", "IIII", "VV", "IM", "IC", "XM", "MCMC", "IVI", "xiv", "ABC",
+        ] {
+            assert_eq!(Roman::from_roman(roman), None, "{roman}");
+        }
+    }
+
+    #[test]
+    fn questions() {
+        let question = Roman::question(1994, false);
+        assert_eq!(question.prompt(), "What is 1994 in Roman numerals?");
+        assert_eq!(question.correct_answer(), "MCMXCIV");
+        assert!(question.is_answer_correct("mcmxciv"));
+        assert!(question.is_answer_correct("McMxCiV"));
+        assert!(!question.is_answer_correct("MDCCCCLXXXXIIII"));
+        assert!(!question.is_answer_correct("MXMIV"));
+        assert_eq!(question.key(), Some(&"roman:1994".to_string()));
+        assert_eq!(
+            question.categories(),
+            [Category::new("direction", "to-roman", 0)]
+        );
+        assert_eq!(
+            question.explanation(),
+            Some(&"1994 = 1000 + 900 + 90 + 4 = M + CM + XC + IV = MCMXCIV".to_string())
+        );
+
+        let question = Roman::question(1994, true);
+        assert_eq!(question.prompt(), "What is MCMXCIV?");
+        assert_eq!(question.correct_answer(), "1994");
+        assert!(!question.is_answer_correct("1995"));
+        assert_eq!(question.key(), Some(&"roman:MCMXCIV".to_string()));
+    }
+
+    #[test]
+    fn question_from_key() {
+        let roman = Roman::build(&to_args(&["-u", "2000"])).expect("Should build correctly");
+        assert!(roman.question_from_key("roman:1994").is_some());
+        assert!(roman.question_from_key("roman:MCMXCIV").is_some());
+        assert!(roman.question_from_key("roman:2026").is_none());
+        assert!(roman.question_from_key("roman:MMXXVI").is_none());
+        assert!(roman.question_from_key("roman:IIII").is_none());
+
+        let roman = Roman::build(&to_args(&["-m", "from-roman"])).expect("Should build correctly");
+        assert!(roman.question_from_key("roman:1994").is_none());
+        for question in roman.generate_questions(10, &mut rand::thread_rng()) {
+            assert!(question.correct_answer().parse::<u32>().is_ok());
+            assert!(roman.question_from_key(question.key().unwrap()).is_some());
+        }
+    }
+
+    #[test]
+    fn help_text() {
+        let roman = Roman::build(&to_args(&["-h"])).expect("Should build correctly");
+        assert!(roman.wants_to_print_help());
+        let help_text = roman.get_help_text();
+        assert!(help_text.contains("Roman options"));
+        assert!(help_text.contains("Usage"));
+        assert!(help_text.contains("-m, --mode"));
+        assert!(help_text.contains("-l, --lower-boundary"));
+        assert!(help_text.contains("-u, --upper-boundary"));
+    }
+}
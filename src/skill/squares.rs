@@ -0,0 +1,511 @@
+use std::iter;
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use super::{question_id, question_key, Adjustment, Base, Level};
+use crate::application::APP_NAME;
+use crate::args::prelude::*;
+use crate::question::{Category, Question};
+
+pub const CMD: &str = "squares";
+
+const ARG_ID_HELP: &str = "help";
+const ARG_ID_EXPONENT: &str = "exponent";
+const ARG_ID_MODE: &str = "mode";
+const ARG_ID_LOWER_BOUNDARY: &str = "lower_boundary";
+const ARG_ID_UPPER_BOUNDARY: &str = "upper_boundary";
+
+const MODE_FORWARD: &str = "forward";
+const MODE_INVERSE: &str = "inverse";
+const MODE_MIXED: &str = "mixed";
+
+const DEFAULT_EXPONENT: u32 = 2;
+const DEFAULT_LOWER_BOUNDARY: u32 = 1;
+const DEFAULT_UPPER_BOUNDARY_SQUARES: u32 = 99;
+const DEFAULT_UPPER_BOUNDARY_CUBES: u32 = 30;
+const DEFAULT_UPPER_BOUNDARY: u32 = 10;
+
+const CATEGORY_BASE: &str = "base";
+
+/// Number of bases added to the lower boundary in the initial range of adaptive mode
+const ADAPTIVE_INITIAL_SPAN: u32 = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// Raise a number to the power, e.g. "47^2"
+    Forward,
+    /// Find the root of a power, e.g. "√2209"
+    Inverse,
+    /// Either of them, chosen randomly
+    Mixed,
+}
+
+impl Mode {
+    fn from_string(value: &str) -> Mode {
+        match value {
+            MODE_FORWARD => Mode::Forward,
+            MODE_INVERSE => Mode::Inverse,
+            MODE_MIXED => Mode::Mixed,
+            _ => panic!("incorrect value for Mode"),
+        }
+    }
+
+    fn allows(self, inverse: bool) -> bool {
+        match self {
+            Mode::Forward => !inverse,
+            Mode::Inverse => inverse,
+            Mode::Mixed => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Squares {
+    arg_definitions: Vec<Arg>,
+    show_help: bool,
+
+    exponent: u32,
+    mode: Mode,
+    lower_boundary: u32,
+    upper_boundary: u32,
+
+    level: Level,
+}
+
+impl Squares {
+    pub fn build(args: &[String]) -> Result<Self, String> {
+        let arg_definitions = Self::build_arg_definitions();
+        let parsed_args = parser::parse_and_validate_arg_list(args, &arg_definitions)
+            .map_err(|err| Self::build_err_message(Some(err)))?;
+
+        let show_help =
+            bool::set_value_from_arg_or_default(ARG_ID_HELP, &parsed_args, &arg_definitions);
+        let exponent =
+            u32::set_value_from_arg_or_default(ARG_ID_EXPONENT, &parsed_args, &arg_definitions);
+        let mode =
+            String::set_value_from_arg_or_default(ARG_ID_MODE, &parsed_args, &arg_definitions);
+        let lower_boundary = u32::set_value_from_arg_or_default(
+            ARG_ID_LOWER_BOUNDARY,
+            &parsed_args,
+            &arg_definitions,
+        );
+        // The default depends on the exponent
+        let upper_boundary = if parser::is_arg_given(ARG_ID_UPPER_BOUNDARY, &parsed_args) {
+            u32::set_value_from_arg_or_default(
+                ARG_ID_UPPER_BOUNDARY,
+                &parsed_args,
+                &arg_definitions,
+            )
+        } else {
+            Self::default_upper_boundary(exponent)
+        };
+
+        if exponent < 2 {
+            return Err(Self::build_err_message(Some(
+                "exponent must be at least 2".to_string(),
+            )));
+        }
+        if lower_boundary > upper_boundary {
+            return Err(Self::build_err_message(Some(
+                "lower boundary must be less than or equal to upper boundary".to_string(),
+            )));
+        }
+        if u64::from(upper_boundary).checked_pow(exponent).is_none() {
+            return Err(Self::build_err_message(Some(format!(
+                "{upper_boundary}^{exponent} exceeds maximum allowed value"
+            ))));
+        }
+
+        let level = Level::new(
+            upper_boundary.saturating_sub(lower_boundary.saturating_add(ADAPTIVE_INITIAL_SPAN)),
+        );
+
+        Ok(Self {
+            arg_definitions,
+            show_help,
+            exponent,
+            mode: Mode::from_string(&mode),
+            lower_boundary,
+            upper_boundary,
+            level,
+        })
+    }
+
+    fn default_upper_boundary(exponent: u32) -> u32 {
+        match exponent {
+            2 => DEFAULT_UPPER_BOUNDARY_SQUARES,
+            3 => DEFAULT_UPPER_BOUNDARY_CUBES,
+            _ => DEFAULT_UPPER_BOUNDARY,
+        }
+    }
+
+    fn build_arg_definitions() -> Vec<Arg> {
+        vec![
+            Arg::builder()
+                .id(ARG_ID_HELP)
+                .short_name('h')
+                .long_name("help")
+                .description(vec!["Display help for squares command.".to_string()])
+                .kind(ArgKind::Flag)
+                .stop_parsing(true)
+                .default_value(ArgValue::Bool(false))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_EXPONENT)
+                .short_name('e')
+                .long_name("exponent")
+                .description(vec![
+                    "Set the exponent, e.g. 3 for cubes".to_string(),
+                    format!("(default: {DEFAULT_EXPONENT})."),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_EXPONENT))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_MODE)
+                .short_name('m')
+                .long_name("mode")
+                .description(vec![
+                    "Set the kind of questions: forward (powers),".to_string(),
+                    "inverse (roots) or mixed (either of them,".to_string(),
+                    "chosen randomly) (default: forward).".to_string(),
+                ])
+                .kind(ArgKind::Value(ValueKind::OneOfStr(vec![
+                    MODE_FORWARD.to_string(),
+                    MODE_INVERSE.to_string(),
+                    MODE_MIXED.to_string(),
+                ])))
+                .stop_parsing(false)
+                .default_value(ArgValue::Str(MODE_FORWARD.to_string()))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_LOWER_BOUNDARY)
+                .short_name('l')
+                .long_name("lower-boundary")
+                .description(vec![format!(
+                    "Set the minimum base (default: {DEFAULT_LOWER_BOUNDARY})."
+                )])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(DEFAULT_LOWER_BOUNDARY))
+                .build(),
+            Arg::builder()
+                .id(ARG_ID_UPPER_BOUNDARY)
+                .short_name('u')
+                .long_name("upper-boundary")
+                .description(vec![
+                    format!("Set the maximum base (default: {DEFAULT_UPPER_BOUNDARY_SQUARES}"),
+                    format!(
+                        "for squares, {DEFAULT_UPPER_BOUNDARY_CUBES} for cubes, {DEFAULT_UPPER_BOUNDARY} otherwise)."
+                    ),
+                ])
+                .kind(ArgKind::Value(ValueKind::UnsignedInt))
+                .stop_parsing(false)
+                .default_value(ArgValue::UnsignedInt(0))
+                .build(),
+        ]
+    }
+
+    fn usage() -> String {
+        format!("Usage: {APP_NAME} [option]... squares [squares_option]...")
+    }
+
+    fn help_prompt() -> String {
+        format!("Try '{APP_NAME} squares --help' for more information.")
+    }
+
+    fn additional_info() -> String {
+        format!("Practise squares, cubes or higher powers of a range of numbers, and their roots.\nUnlike powers, the exponent is fixed and the base varies.\nBy default, squares of numbers from {DEFAULT_LOWER_BOUNDARY} to {DEFAULT_UPPER_BOUNDARY_SQUARES} are asked.")
+    }
+
+    fn build_err_message(msg: Option<String>) -> String {
+        if let Some(msg) = msg {
+            format!(
+                "{}: {}: {}\n{}\n{}",
+                APP_NAME,
+                CMD,
+                msg,
+                Self::usage(),
+                Self::help_prompt()
+            )
+        } else {
+            format!("{}\n{}", Self::usage(), Self::help_prompt())
+        }
+    }
+
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let base = rng.gen_range(self.lower_boundary..=self.adaptive_upper_boundary());
+        let inverse = match self.mode {
+            Mode::Forward => false,
+            Mode::Inverse => true,
+            Mode::Mixed => *[false, true].choose(rng).expect("not empty"),
+        };
+        self.question(base, inverse)
+    }
+
+    /// Maximum base at the current difficulty level
+    fn adaptive_upper_boundary(&self) -> u32 {
+        self.upper_boundary - (self.level.max - self.level.get())
+    }
+
+    /// Root symbol for the exponent, e.g. "√" for squares or "⁵√" for the fifth root
+    fn root_symbol(exponent: u32) -> String {
+        match exponent {
+            2 => "√".to_string(),
+            3 => "∛".to_string(),
+            4 => "∜".to_string(),
+            _ => {
+                const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+                let index: String = exponent
+                    .to_string()
+                    .chars()
+                    .map(|digit| SUPERSCRIPTS[digit.to_digit(10).expect("decimal digit") as usize])
+                    .collect();
+                format!("{index}√")
+            }
+        }
+    }
+
+    fn question(&self, base: u32, inverse: bool) -> Question {
+        // Won't overflow, checked during Squares construction
+        let power = u64::from(base).pow(self.exponent);
+        let (prompt, answer, id) = if inverse {
+            (
+                format!("{}{power}", Self::root_symbol(self.exponent)),
+                base.to_string(),
+                format!("{power}^1/{}", self.exponent),
+            )
+        } else {
+            let id = format!("{base}^{}", self.exponent);
+            (id.clone(), power.to_string(), id)
+        };
+        Question::builder()
+            .question(&prompt)
+            .answer(&answer)
+            .key(&question_key(CMD, &id))
+            .categories(&[Category::new(CATEGORY_BASE, &base.to_string(), base.into())])
+            .build()
+    }
+
+    /// Returns the base whose `exponent`-th power is `power`, if there is one
+    fn exact_root(power: u64, exponent: u32) -> Option<u32> {
+        // Floating point estimate is off by at most one
+        let estimate = (power as f64).powf(1.0 / f64::from(exponent)).round() as u64;
+        (estimate.saturating_sub(1)..=estimate + 1)
+            .find(|base| base.checked_pow(exponent) == Some(power))
+            .and_then(|base| u32::try_from(base).ok())
+    }
+}
+
+impl Base for Squares {
+    fn generate_questions(&self, count: u32, rng: &mut dyn RngCore) -> Vec<Question> {
+        iter::repeat_with(|| self.generate_question(rng))
+            .take(count as usize)
+            .collect()
+    }
+
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let (number, exponent) = question_id(CMD, key)?.split_once('^')?;
+        let (exponent, inverse) = match exponent.strip_prefix("1/") {
+            Some(exponent) => (exponent, true),
+            None => (exponent, false),
+        };
+        if exponent.parse::<u32>().ok()? != self.exponent || !self.mode.allows(inverse) {
+            return None;
+        }
+        let base = if inverse {
+            Self::exact_root(number.parse().ok()?, self.exponent)?
+        } else {
+            number.parse().ok()?
+        };
+        if (self.lower_boundary..=self.upper_boundary).contains(&base) {
+            Some(self.question(base, inverse))
+        } else {
+            None
+        }
+    }
+
+    fn adapt(&self, adjustment: Adjustment) -> bool {
+        self.level.adjust(adjustment)
+    }
+
+    fn current_range(&self) -> Option<String> {
+        Some(format!(
+            "bases {}-{}",
+            self.lower_boundary,
+            self.adaptive_upper_boundary()
+        ))
+    }
+
+    fn wants_to_print_help(&self) -> bool {
+        self.show_help
+    }
+
+    fn get_help_text(&self) -> String {
+        let definitions = &self.arg_definitions;
+        let options = help::Options::new("Squares options", definitions);
+        help::build(
+            &Self::usage(),
+            Some(&Self::additional_info()),
+            &options,
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::{assert_err_message, to_args};
+
+    #[test]
+    fn build_squares_defaults() {
+        let squares = Squares::build(&to_args(&[])).expect("Should build correctly with no args");
+        assert!(!squares.show_help);
+        assert_eq!(squares.exponent, 2);
+        assert_eq!(squares.mode, Mode::Forward);
+        assert_eq!(squares.lower_boundary, 1);
+        assert_eq!(squares.upper_boundary, 99);
+
+        let squares =
+            Squares::build(&to_args(&["-e", "3"])).expect("Should build correctly with args");
+        assert_eq!(squares.upper_boundary, 30);
+        let squares =
+            Squares::build(&to_args(&["-e", "5"])).expect("Should build correctly with args");
+        assert_eq!(squares.upper_boundary, 10);
+        let squares = Squares::build(&to_args(&["-e", "3", "-u", "12", "-m", "mixed"]))
+            .expect("Should build correctly");
+        assert_eq!(squares.upper_boundary, 12);
+        assert_eq!(squares.mode, Mode::Mixed);
+        let squares =
+            Squares::build(&to_args(&["-l", "0", "-u", "0"])).expect("Should build correctly");
+        assert_eq!(squares.lower_boundary, 0);
+        assert_eq!(squares.upper_boundary, 0);
+    }
+
+    #[test]
+    fn build_squares_incorrect_args() {
+        let err = Squares::build(&to_args(&["-e", "1"])).unwrap_err();
+        assert!(err.contains("exponent must be at least 2"));
+        let err = Squares::build(&to_args(&["-l", "5", "-u", "4"])).unwrap_err();
+        assert!(err.contains("lower boundary must be less than or equal to upper boundary"));
+        let err = Squares::build(&to_args(&["-u", "0"])).unwrap_err();
+        assert!(err.contains("lower boundary must be less than or equal to upper boundary"));
+        let err = Squares::build(&to_args(&["-e", "3", "-u", "3000000"])).unwrap_err();
+        assert!(err.contains("3000000^3 exceeds maximum allowed value"));
+        let err = Squares::build(&to_args(&["--mode=backward"])).unwrap_err();
+        assert!(err.contains("invalid option argument"));
+    }
+
+    #[test]
+    fn error_message() {
+        let message = Squares::build_err_message(Some("something wrong".to_string()));
+        assert_err_message(&message, CMD, "something wrong");
+    }
+
+    #[test]
+    fn forward_questions() {
+        let squares = Squares::build(&to_args(&[])).expect("Should build correctly");
+        let question = squares.question(47, false);
+        assert_eq!(question.prompt(), "47^2");
+        assert_eq!(question.correct_answer(), "2209");
+        assert_eq!(question.key(), Some(&"squares:47^2".to_string()));
+        assert_eq!(question.categories(), [Category::new("base", "47", 47)]);
+    }
+
+    #[test]
+    fn inverse_questions() {
+        let squares = Squares::build(&to_args(&["-m", "inverse"])).expect("Should build correctly");
+        let question = squares.question(47, true);
+        assert_eq!(question.prompt(), "√2209");
+        assert_eq!(question.correct_answer(), "47");
+        assert_eq!(question.key(), Some(&"squares:2209^1/2".to_string()));
+
+        let cubes = Squares::build(&to_args(&["-e", "3", "-m", "inverse"]))
+            .expect("Should build correctly");
+        assert_eq!(cubes.question(27, true).prompt(), "∛19683");
+        let fifth = Squares::build(&to_args(&["-e", "5"])).expect("Should build correctly");
+        assert_eq!(fifth.question(2, true).prompt(), "⁵√32");
+        let twelfth =
+            Squares::build(&to_args(&["-e", "12", "-u", "3"])).expect("Should build correctly");
+        assert_eq!(twelfth.question(2, true).prompt(), "¹²√4096");
+    }
+
+    #[test]
+    fn mixed_questions() {
+        let squares =
+            Squares::build(&to_args(&["-m", "mixed", "-u", "9"])).expect("Should build correctly");
+        let prompts: Vec<_> = squares
+            .generate_questions(100, &mut rand::thread_rng())
+            .iter()
+            .map(|question| question.prompt().clone())
+            .collect();
+        assert!(prompts.iter().any(|prompt| prompt.starts_with('√')));
+        assert!(prompts.iter().any(|prompt| prompt.ends_with("^2")));
+    }
+
+    #[test]
+    fn exact_roots() {
+        assert_eq!(Squares::exact_root(2209, 2), Some(47));
+        assert_eq!(Squares::exact_root(2210, 2), None);
+        assert_eq!(Squares::exact_root(0, 3), Some(0));
+        assert_eq!(Squares::exact_root(19683, 3), Some(27));
+        assert_eq!(
+            Squares::exact_root(u64::from(u32::MAX).pow(2), 2),
+            Some(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn question_from_key() {
+        let squares =
+            Squares::build(&to_args(&["-m", "mixed", "-l", "10"])).expect("Should build correctly");
+        let question = squares
+            .question_from_key("squares:2209^1/2")
+            .expect("Key should be recognised");
+        assert_eq!(question.correct_answer(), "47");
+        let question = squares
+            .question_from_key("squares:47^2")
+            .expect("Key should be recognised");
+        assert_eq!(question.correct_answer(), "2209");
+
+        assert!(squares.question_from_key("squares:2210^1/2").is_none());
+        assert!(squares.question_from_key("squares:81^1/2").is_none());
+        assert!(squares.question_from_key("squares:100^2").is_none());
+        assert!(squares.question_from_key("squares:47^3").is_none());
+        assert!(squares.question_from_key("powers:2^10").is_none());
+
+        let squares = Squares::build(&to_args(&[])).expect("Should build correctly");
+        assert!(squares.question_from_key("squares:2209^1/2").is_none());
+    }
+
+    #[test]
+    fn adaptive_range() {
+        let squares = Squares::build(&to_args(&["-l", "11"])).expect("Should build correctly");
+        assert_eq!(squares.current_range(), Some("bases 11-99".to_string()));
+
+        assert!(squares.adapt(Adjustment::Start));
+        assert_eq!(squares.current_range(), Some("bases 11-20".to_string()));
+        assert!(squares
+            .generate_questions(20, &mut rand::thread_rng())
+            .iter()
+            .all(|question| question.correct_answer().parse::<u32>().unwrap() <= 400));
+        assert!(squares.adapt(Adjustment::Harder));
+        assert_eq!(squares.current_range(), Some("bases 11-21".to_string()));
+    }
+
+    #[test]
+    fn help_text() {
+        let squares = Squares::build(&to_args(&["-h"])).expect("Should build correctly");
+        assert!(squares.wants_to_print_help());
+        let help_text = squares.get_help_text();
+        assert!(help_text.contains("Squares options"));
+        assert!(help_text.contains("Usage"));
+        assert!(help_text.contains("-e, --exponent"));
+        assert!(help_text.contains("-m, --mode"));
+        assert!(help_text.contains("-l, --lower-boundary"));
+        assert!(help_text.contains("-u, --upper-boundary"));
+    }
+}
@@ -1,11 +1,12 @@
+use std::cmp;
 use std::iter;
 
-use rand::Rng;
+use rand::{Rng, RngCore};
 
-use super::Base;
+use super::{question_id, question_key, Adjustment, Base, Level};
 use crate::application::APP_NAME;
 use crate::args::prelude::*;
-use crate::question::Question;
+use crate::question::{Category, Question};
 
 pub const CMD: &str = "times_table";
 
@@ -17,6 +18,10 @@ const ARG_ID_UPPER_BOUNDARY_2: &str = "upper_boundary_2";
 
 const DEFAULT_LOWER_BOUNDARY: u32 = 1;
 const DEFAULT_UPPER_BOUNDARY: u32 = 10;
+const CATEGORY_FACTOR: &str = "factor";
+
+/// Number of values added to the lower boundaries in the initial range of adaptive mode
+const ADAPTIVE_INITIAL_SPAN: u32 = 4;
 
 #[derive(Debug)]
 pub struct TimesTable {
@@ -27,6 +32,8 @@ pub struct TimesTable {
     upper_boundary_1: u32,
     lower_boundary_2: u32,
     upper_boundary_2: u32,
+
+    level: Level,
 }
 
 impl TimesTable {
@@ -65,6 +72,11 @@ impl TimesTable {
             )));
         }
 
+        let level = Level::new(cmp::max(
+            Self::adaptive_max_level(lower_boundary_1, upper_boundary_1),
+            Self::adaptive_max_level(lower_boundary_2, upper_boundary_2),
+        ));
+
         Ok(Self {
             arg_definitions,
             show_help,
@@ -72,6 +84,7 @@ impl TimesTable {
             upper_boundary_1,
             lower_boundary_2,
             upper_boundary_2,
+            level,
         })
     }
 
@@ -172,30 +185,104 @@ impl TimesTable {
         }
     }
 
-    fn generate_question(&self) -> Question {
-        let mut rng = rand::thread_rng();
-        let mut first = rng.gen_range(self.lower_boundary_1..=self.upper_boundary_1);
-        let mut second = rng.gen_range(self.lower_boundary_2..=self.upper_boundary_2);
+    fn generate_question(&self, rng: &mut dyn RngCore) -> Question {
+        let (upper_boundary_1, upper_boundary_2) = self.adaptive_upper_boundaries();
+        let first = rng.gen_range(self.lower_boundary_1..=upper_boundary_1);
+        let second = rng.gen_range(self.lower_boundary_2..=upper_boundary_2);
+        Self::question(first, second, rng.gen_bool(0.5))
+    }
+
+    fn adaptive_max_level(lower_boundary: u32, upper_boundary: u32) -> u32 {
+        upper_boundary.saturating_sub(lower_boundary.saturating_add(ADAPTIVE_INITIAL_SPAN))
+    }
+
+    /// Maximum values of both factors at the current difficulty level
+    fn adaptive_upper_boundaries(&self) -> (u32, u32) {
+        let level = self.level.get();
+        let upper_boundary = |lower_boundary: u32, upper_boundary: u32| {
+            lower_boundary
+                .saturating_add(ADAPTIVE_INITIAL_SPAN)
+                .saturating_add(level)
+                .min(upper_boundary)
+        };
+        (
+            upper_boundary(self.lower_boundary_1, self.upper_boundary_1),
+            upper_boundary(self.lower_boundary_2, self.upper_boundary_2),
+        )
+    }
+
+    /// Factors are presented in reversed order if `swap` is set, the key is the same
+    /// regardless of the order.
+    fn question(mut first: u32, mut second: u32, swap: bool) -> Question {
         let result = u64::from(first) * u64::from(second); // u32::MAX ^ 2 < u64::MAX
+        let id = format!("{}*{}", first.min(second), first.max(second));
+        let mut categories = vec![Category::new(
+            CATEGORY_FACTOR,
+            &first.to_string(),
+            first.into(),
+        )];
+        if first != second {
+            categories.push(Category::new(
+                CATEGORY_FACTOR,
+                &second.to_string(),
+                second.into(),
+            ));
+        }
 
-        if rng.gen_bool(0.5) {
+        if swap {
             std::mem::swap(&mut first, &mut second);
         }
 
         Question::builder()
             .question(&format!("{first}*{second}"))
             .answer(&result.to_string())
+            .key(&question_key(CMD, &id))
+            .categories(&categories)
             .build()
     }
+
+    fn in_range(&self, first: u32, second: u32) -> bool {
+        (self.lower_boundary_1..=self.upper_boundary_1).contains(&first)
+            && (self.lower_boundary_2..=self.upper_boundary_2).contains(&second)
+    }
 }
 
 impl Base for TimesTable {
-    fn generate_questions(&self, count: u32) -> Vec<crate::question::Question> {
-        iter::repeat_with(|| self.generate_question())
+    fn generate_questions(
+        &self,
+        count: u32,
+        rng: &mut dyn RngCore,
+    ) -> Vec<crate::question::Question> {
+        iter::repeat_with(|| self.generate_question(rng))
             .take(count as usize)
             .collect()
     }
 
+    fn question_from_key(&self, key: &str) -> Option<Question> {
+        let (first, second) = question_id(CMD, key)?.split_once('*')?;
+        let first = first.parse::<u32>().ok()?;
+        let second = second.parse::<u32>().ok()?;
+        if self.in_range(first, second) || self.in_range(second, first) {
+            // Factors are shown in the order stored in the key, so that no randomness
+            // is needed and seeded runs stay reproducible
+            Some(Self::question(first, second, false))
+        } else {
+            None
+        }
+    }
+
+    fn adapt(&self, adjustment: Adjustment) -> bool {
+        self.level.adjust(adjustment)
+    }
+
+    fn current_range(&self) -> Option<String> {
+        let (upper_boundary_1, upper_boundary_2) = self.adaptive_upper_boundaries();
+        Some(format!(
+            "factors {}-{} and {}-{}",
+            self.lower_boundary_1, upper_boundary_1, self.lower_boundary_2, upper_boundary_2
+        ))
+    }
+
     fn wants_to_print_help(&self) -> bool {
         self.show_help
     }
@@ -304,10 +391,88 @@ mod tests {
             "--upper-boundary-2=1".to_string(),
         ];
         let times_table = TimesTable::build(&args).expect("Should build correctly");
-        let question = times_table.generate_question();
+        let question = times_table.generate_question(&mut rand::thread_rng());
         assert_eq!(question.prompt(), "1*1");
         assert_eq!(question.correct_answer(), "1");
         assert!(question.is_answer_correct("1"));
+        assert_eq!(question.key(), Some(&"times_table:1*1".to_string()));
+    }
+
+    #[test]
+    fn question_from_key() {
+        let args = [
+            "--lower-boundary-1=2".to_string(),
+            "--upper-boundary-1=5".to_string(),
+            "--lower-boundary-2=7".to_string(),
+            "--upper-boundary-2=9".to_string(),
+        ];
+        let times_table = TimesTable::build(&args).expect("Should build correctly");
+
+        let question = times_table
+            .question_from_key("times_table:3*8")
+            .expect("Key should be recognised");
+        assert_eq!(question.prompt(), "3*8");
+        assert_eq!(question.correct_answer(), "24");
+        assert_eq!(question.key(), Some(&"times_table:3*8".to_string()));
+
+        assert!(times_table.question_from_key("times_table:8*3").is_some());
+        assert!(times_table.question_from_key("times_table:6*8").is_none());
+        assert!(times_table.question_from_key("times_table:3*3").is_none());
+        assert!(times_table.question_from_key("times_table:3x8").is_none());
+        assert!(times_table.question_from_key("powers:2^3").is_none());
+    }
+
+    #[test]
+    fn question_categories() {
+        let question = TimesTable::question(7, 8, false);
+        assert_eq!(
+            question.categories(),
+            [
+                Category::new("factor", "7", 7),
+                Category::new("factor", "8", 8)
+            ]
+        );
+
+        let question = TimesTable::question(7, 7, false);
+        assert_eq!(question.categories(), [Category::new("factor", "7", 7)]);
+    }
+
+    #[test]
+    fn adaptive_range() {
+        let args = [
+            "--upper-boundary-1=12".to_string(),
+            "--lower-boundary-2=3".to_string(),
+            "--upper-boundary-2=8".to_string(),
+        ];
+        let times_table = TimesTable::build(&args).expect("Should build correctly");
+        assert_eq!(
+            times_table.current_range(),
+            Some("factors 1-12 and 3-8".to_string())
+        );
+
+        assert!(times_table.adapt(Adjustment::Start));
+        assert_eq!(
+            times_table.current_range(),
+            Some("factors 1-5 and 3-7".to_string())
+        );
+        assert!(times_table
+            .generate_questions(20, &mut rand::thread_rng())
+            .iter()
+            .all(|question| question.correct_answer().parse::<u32>().unwrap() <= 35));
+
+        assert!(times_table.adapt(Adjustment::Harder));
+        assert_eq!(
+            times_table.current_range(),
+            Some("factors 1-6 and 3-8".to_string())
+        );
+        for _ in 0..6 {
+            assert!(times_table.adapt(Adjustment::Harder));
+        }
+        assert!(!times_table.adapt(Adjustment::Harder));
+        assert_eq!(
+            times_table.current_range(),
+            Some("factors 1-12 and 3-8".to_string())
+        );
     }
 
     #[test]
@@ -319,7 +484,7 @@ mod tests {
             "--upper-boundary-2=3".to_string(),
         ];
         let times_table = TimesTable::build(&args).expect("Should build correctly");
-        let questions = times_table.generate_questions(10);
+        let questions = times_table.generate_questions(10, &mut rand::thread_rng());
         assert_eq!(questions.len(), 10);
         assert!(questions
             .iter()
@@ -1,7 +1,9 @@
+use std::collections::BTreeMap;
 use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::time::{Duration, Instant};
 
 use crate::config::NumberOfQuestions;
+use crate::question::{Category, Question};
 
 const DURATION_ZERO: Duration = Duration::new(0, 0);
 
@@ -16,10 +18,13 @@ impl Lock {
                 number_of_questions: NumberOfQuestions::Infinite,
                 number_of_answered_questions: 0,
                 number_of_correct_answers: 0,
+                number_of_timed_out_questions: 0,
                 start_time: Instant::now(),
                 current_question_start_time: Instant::now(),
                 current_question_answered: false,
-                time_per_question: vec![],
+                current_question: None,
+                records: vec![],
+                ranges: vec![],
             }),
         }
     }
@@ -28,12 +33,44 @@ impl Lock {
         self.write().start(number_of_questions);
     }
 
-    pub fn start_new_question(&self) {
-        self.write().start_new_question();
+    pub fn start_new_question(&self, question: &Question) {
+        self.write().start_new_question(question);
     }
 
-    pub fn answer_question(&self, correct: bool) {
-        self.write().answer_question(correct);
+    pub fn answer_question(&self, answer: &str, correct: bool) {
+        self.write().answer_question(answer, correct);
+    }
+
+    /// Marks the current question as wrong, because time ran out before it was answered.
+    pub fn time_out_question(&self) {
+        self.write().time_out_question();
+    }
+
+    /// Sets the range the following questions are generated from (adaptive mode).
+    pub fn set_current_range(&self, range: &str) {
+        self.write().set_current_range(range);
+    }
+
+    /// Returns ranges used for questions answered so far, e.g. "questions 1-5: exponents 1-4".
+    pub fn get_ranges(&self) -> Vec<String> {
+        self.read().get_ranges()
+    }
+
+    /// Returns accuracy and average time per category value, grouped by category name.
+    /// Returns an empty string if questions have no categories.
+    pub fn get_category_breakdown(&self) -> String {
+        self.read().get_category_breakdown()
+    }
+
+    /// Returns summary numbers of the game. Preferably call this method first to stop
+    /// the timer as early as possible.
+    pub fn get_totals(&self) -> Totals {
+        self.read().get_totals()
+    }
+
+    /// Returns records of all questions answered so far, in the order they were asked.
+    pub fn get_records(&self) -> Vec<Record> {
+        self.read().records.clone()
     }
 
     pub fn get_summary(&self) -> String {
@@ -59,6 +96,15 @@ impl Lock {
         self.read().get_current_accuracy()
     }
 
+    pub fn get_number_of_timed_out_questions(&self) -> u32 {
+        self.read().get_number_of_timed_out_questions()
+    }
+
+    /// Calculates number of questions answered per minute (time-limited mode).
+    pub fn get_questions_per_minute(&self) -> String {
+        self.read().get_questions_per_minute()
+    }
+
     /// Preferably call this method first to stop the timer as early as possible
     pub fn get_total_time(&self) -> String {
         self.read().get_total_time()
@@ -68,6 +114,10 @@ impl Lock {
         self.read().get_last_question_time()
     }
 
+    pub fn get_last_question_duration(&self) -> Duration {
+        self.read().get_last_question_duration()
+    }
+
     pub fn get_min_question_time(&self) -> String {
         self.read().get_min_question_time()
     }
@@ -80,23 +130,78 @@ impl Lock {
         self.read().get_avg_question_time()
     }
 
-    fn write(&self) -> RwLockWriteGuard<Stats> {
+    fn write(&self) -> RwLockWriteGuard<'_, Stats> {
         self.stats.write().expect("Stats are blocked")
     }
 
-    fn read(&self) -> RwLockReadGuard<Stats> {
+    fn read(&self) -> RwLockReadGuard<'_, Stats> {
         self.stats.read().expect("Stats are blocked")
     }
 }
 
+/// Single answered question. `correct` refers to the first attempt only, `timed_out` is set
+/// if time ran out before the question was answered correctly.
+/// Categories are used for the breakdown at the end of the game only, they are not saved
+/// in history. Timeouts are saved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub prompt: String,
+    pub correct_answer: String,
+    pub given_answers: Vec<String>,
+    pub correct: bool,
+    pub duration: Duration,
+    pub timed_out: bool,
+    pub categories: Vec<Category>,
+}
+
+/// Summary numbers of the game, for machine-readable export
+#[derive(Debug, Clone, PartialEq)]
+pub struct Totals {
+    pub number_of_questions: NumberOfQuestions,
+    pub answered: u32,
+    pub skipped: u32,
+    pub correct: u32,
+    pub total_time: Duration,
+}
+
+impl Totals {
+    /// Returns the accuracy of answered questions, in percent.
+    pub fn accuracy(&self) -> f64 {
+        accuracy(self.correct, self.answered)
+    }
+
+    /// Returns the accuracy of all questions (including skipped ones), in percent, or `None`
+    /// if the number of questions is not limited.
+    pub fn total_accuracy(&self) -> Option<f64> {
+        match self.number_of_questions {
+            NumberOfQuestions::Limited(num) => Some(accuracy(self.correct, num)),
+            NumberOfQuestions::Infinite | NumberOfQuestions::TimeLimited(_) => None,
+        }
+    }
+}
+
+/// Stats of category values, keyed by (order, value)
+type CategoryValues<'a> = BTreeMap<(i64, &'a str), CategoryStats>;
+
+#[derive(Default)]
+struct CategoryStats {
+    answered: u32,
+    correct: u32,
+    total_time: Duration,
+}
+
 struct Stats {
     number_of_questions: NumberOfQuestions,
     number_of_answered_questions: u32,
     number_of_correct_answers: u32,
+    number_of_timed_out_questions: u32,
     start_time: Instant,
     current_question_start_time: Instant,
     current_question_answered: bool,
-    time_per_question: Vec<Duration>,
+    current_question: Option<Question>,
+    records: Vec<Record>,
+    /// Ranges used in adaptive mode, with the index of the first record they apply to
+    ranges: Vec<(usize, String)>,
 }
 
 impl Stats {
@@ -105,23 +210,37 @@ impl Stats {
         self.start_time = Instant::now();
     }
 
-    pub fn start_new_question(&mut self) {
+    pub fn start_new_question(&mut self, question: &Question) {
         self.current_question_start_time = Instant::now();
         self.current_question_answered = false;
+        self.current_question = Some(question.clone());
     }
 
-    pub fn answer_question(&mut self, correct: bool) {
+    pub fn answer_question(&mut self, answer: &str, correct: bool) {
+        let duration = self.current_question_start_time.elapsed();
         if self.current_question_answered {
             // Question has already been answered, so it's a second attempt
             // Update total time, but don't mark as correct even if current answer matches
-            *self
-                .time_per_question
+            let record = self
+                .records
                 .last_mut()
-                .expect("answer_question incorrectly called") =
-                self.current_question_start_time.elapsed();
+                .expect("answer_question incorrectly called");
+            record.given_answers.push(answer.to_string());
+            record.duration = duration;
         } else {
-            self.time_per_question
-                .push(self.current_question_start_time.elapsed());
+            let question = self
+                .current_question
+                .as_ref()
+                .expect("answer_question called before start_new_question");
+            self.records.push(Record {
+                prompt: question.prompt().clone(),
+                correct_answer: question.correct_answer().clone(),
+                given_answers: vec![answer.to_string()],
+                correct,
+                duration,
+                timed_out: false,
+                categories: question.categories().to_vec(),
+            });
             self.number_of_answered_questions += 1;
             if correct {
                 self.number_of_correct_answers += 1;
@@ -130,9 +249,126 @@ impl Stats {
         }
     }
 
+    pub fn time_out_question(&mut self) {
+        let duration = self.current_question_start_time.elapsed();
+        if self.current_question_answered {
+            // Wrong answers have already been given (and recorded)
+            let record = self
+                .records
+                .last_mut()
+                .expect("time_out_question incorrectly called");
+            record.duration = duration;
+            record.timed_out = true;
+        } else {
+            let question = self
+                .current_question
+                .as_ref()
+                .expect("time_out_question called before start_new_question");
+            self.records.push(Record {
+                prompt: question.prompt().clone(),
+                correct_answer: question.correct_answer().clone(),
+                given_answers: vec![],
+                correct: false,
+                duration,
+                timed_out: true,
+                categories: question.categories().to_vec(),
+            });
+            self.number_of_answered_questions += 1;
+            self.current_question_answered = true;
+        }
+        self.number_of_timed_out_questions += 1;
+    }
+
+    pub fn set_current_range(&mut self, range: &str) {
+        let first_record = self.records.len();
+        if let Some((first, current)) = self.ranges.last_mut() {
+            if *first == first_record {
+                // No question has been answered with the previous range
+                *current = range.to_string();
+                self.ranges.dedup_by(|a, b| a.1 == b.1);
+                return;
+            }
+            if current == range {
+                return;
+            }
+        }
+        self.ranges.push((first_record, range.to_string()));
+    }
+
+    pub fn get_ranges(&self) -> Vec<String> {
+        let mut ranges = vec![];
+        for (i, (first, range)) in self.ranges.iter().enumerate() {
+            let end = self
+                .ranges
+                .get(i + 1)
+                .map_or(self.records.len(), |(next, _)| *next);
+            let questions = match end - first {
+                0 => continue,
+                1 => format!("question {}", first + 1),
+                _ => format!("questions {}-{end}", first + 1),
+            };
+            ranges.push(format!("{questions}: {range}"));
+        }
+        ranges
+    }
+
+    pub fn get_category_breakdown(&self) -> String {
+        // Category names in order of appearance, values sorted by their order
+        let mut breakdown: Vec<(&str, CategoryValues)> = vec![];
+        for record in &self.records {
+            for category in &record.categories {
+                let index = breakdown
+                    .iter()
+                    .position(|(name, _)| name == category.name())
+                    .unwrap_or_else(|| {
+                        breakdown.push((category.name(), BTreeMap::new()));
+                        breakdown.len() - 1
+                    });
+                let stats = breakdown[index]
+                    .1
+                    .entry((category.order(), category.value()))
+                    .or_default();
+                stats.answered += 1;
+                stats.correct += u32::from(record.correct);
+                stats.total_time += record.duration;
+            }
+        }
+
+        let mut text = String::new();
+        for (name, values) in breakdown {
+            let width = values
+                .keys()
+                .map(|(_, value)| value.chars().count())
+                .max()
+                .unwrap_or(0);
+            text.push_str(&format!("Per {name}:\n"));
+            for ((_, value), stats) in values {
+                text.push_str(&format!(
+                    "  {value:<width$}  {}/{} ({:.2}%), avg time: {}\n",
+                    stats.correct,
+                    stats.answered,
+                    f64::from(stats.correct) / f64::from(stats.answered) * 100.0,
+                    format_duration(&(stats.total_time / stats.answered))
+                ));
+            }
+        }
+        text.pop(); // Trailing new line
+        text
+    }
+
+    pub fn get_totals(&self) -> Totals {
+        Totals {
+            number_of_questions: self.number_of_questions,
+            answered: self.number_of_answered_questions,
+            skipped: self.get_number_of_remaining_questions(),
+            correct: self.number_of_correct_answers,
+            total_time: self.start_time.elapsed(),
+        }
+    }
+
     pub fn get_summary(&self) -> String {
         match self.number_of_questions {
-            NumberOfQuestions::Infinite => {
+            NumberOfQuestions::Infinite | NumberOfQuestions::TimeLimited(_) => {
                 format!("Questions total: {}", self.number_of_answered_questions)
             }
             NumberOfQuestions::Limited(total) => format!(
@@ -153,100 +389,136 @@ impl Stats {
 
     pub fn get_number_of_remaining_questions(&self) -> u32 {
         match self.number_of_questions {
-            NumberOfQuestions::Infinite => 0,
+            NumberOfQuestions::Infinite | NumberOfQuestions::TimeLimited(_) => 0,
             NumberOfQuestions::Limited(total) => total - self.number_of_answered_questions,
         }
     }
 
     pub fn get_total_accuracy(&self) -> String {
         let divisor = match self.number_of_questions {
-            NumberOfQuestions::Infinite => 0,
+            NumberOfQuestions::Infinite | NumberOfQuestions::TimeLimited(_) => 0,
             NumberOfQuestions::Limited(num) => num,
         };
         self.get_accuracy(divisor)
     }
 
+    pub fn get_questions_per_minute(&self) -> String {
+        let elapsed = match self.number_of_questions {
+            NumberOfQuestions::TimeLimited(limit) => self.start_time.elapsed().min(limit),
+            _ => self.start_time.elapsed(),
+        };
+        let per_minute = if elapsed.is_zero() {
+            0.0
+        } else {
+            f64::from(self.number_of_answered_questions) / elapsed.as_secs_f64() * 60.0
+        };
+        format!("{per_minute:.2}")
+    }
+
+    pub fn get_number_of_timed_out_questions(&self) -> u32 {
+        self.number_of_timed_out_questions
+    }
+
     pub fn get_current_accuracy(&self) -> String {
         let divisor = self.number_of_answered_questions;
         self.get_accuracy(divisor)
     }
 
     fn get_accuracy(&self, divisor: u32) -> String {
-        let acc = if divisor == 0 {
-            0.0
-        } else {
-            f64::from(self.number_of_correct_answers) / f64::from(divisor)
-        } * 100.0;
+        let acc = accuracy(self.number_of_correct_answers, divisor);
         format!("{acc:.2}%")
     }
 
     pub fn get_total_time(&self) -> String {
         let total_time = self.start_time.elapsed();
-        Self::format_duration(&total_time)
+        format_duration(&total_time)
     }
 
     pub fn get_last_question_time(&self) -> String {
-        let time = self
-            .time_per_question
-            .last()
-            .expect("No questions answered so far");
-        Self::format_duration(time)
+        format_duration(&self.get_last_question_duration())
+    }
+
+    pub fn get_last_question_duration(&self) -> Duration {
+        let record = self.records.last().expect("No questions answered so far");
+        record.duration
     }
 
     pub fn get_min_question_time(&self) -> String {
-        let min_time = self
-            .time_per_question
-            .iter()
-            .min()
-            .unwrap_or(&DURATION_ZERO);
-        Self::format_duration(min_time)
+        format_duration(&min_question_time(&self.records))
     }
 
     pub fn get_max_question_time(&self) -> String {
-        let max_time = self
-            .time_per_question
-            .iter()
-            .max()
-            .unwrap_or(&DURATION_ZERO);
-        Self::format_duration(max_time)
+        format_duration(&max_question_time(&self.records))
     }
 
     pub fn get_avg_question_time(&self) -> String {
-        let total_time = self.time_per_question.iter().sum::<Duration>();
-        let answered_questions = u32::try_from(self.time_per_question.len())
-            .expect("Time per question vector len > u32::MAX");
-        if answered_questions == 0 {
-            Self::format_duration(&DURATION_ZERO)
-        } else {
-            Self::format_duration(&(total_time / answered_questions))
-        }
+        format_duration(&avg_question_time(&self.records))
     }
+}
 
-    fn format_duration(duration: &Duration) -> String {
-        let hours = (duration.as_secs() / 60) / 60;
-        let minutes = (duration.as_secs() / 60) % 60;
-        let seconds = duration.as_secs() % 60;
-        let milliseconds = Self::truncate_trailing_zeros(duration.subsec_millis());
+/// Returns the percentage of `correct` answers out of `divisor`, or 0 if `divisor` is 0.
+pub fn accuracy(correct: u32, divisor: u32) -> f64 {
+    if divisor == 0 {
+        0.0
+    } else {
+        f64::from(correct) / f64::from(divisor) * 100.0
+    }
+}
 
-        let mut time = String::new();
-        if hours > 0 {
-            time.push_str(&format!("{hours}h "));
-            time.push_str(&format!("{minutes}m "));
-        } else if minutes > 0 {
-            time.push_str(&format!("{minutes}m "));
-        }
-        time.push_str(&format!("{seconds}.{milliseconds}s"));
+pub fn min_question_time(records: &[Record]) -> Duration {
+    records
+        .iter()
+        .map(|record| record.duration)
+        .min()
+        .unwrap_or(DURATION_ZERO)
+}
+
+pub fn max_question_time(records: &[Record]) -> Duration {
+    records
+        .iter()
+        .map(|record| record.duration)
+        .max()
+        .unwrap_or(DURATION_ZERO)
+}
 
-        time
+pub fn avg_question_time(records: &[Record]) -> Duration {
+    let total_time = records
+        .iter()
+        .map(|record| record.duration)
+        .sum::<Duration>();
+    let answered_questions = u32::try_from(records.len()).expect("Records vector len > u32::MAX");
+    if answered_questions == 0 {
+        DURATION_ZERO
+    } else {
+        total_time / answered_questions
     }
+}
 
-    fn truncate_trailing_zeros(number: u32) -> String {
-        let mut number = format!("{number:0>3}");
-        while number.ends_with('0') && number.len() > 1 {
-            number.pop();
-        }
-        number
+/// Formats duration as e.g. "1h 2m 3.45s", "2m 3.45s" or "3.45s".
+pub fn format_duration(duration: &Duration) -> String {
+    let hours = (duration.as_secs() / 60) / 60;
+    let minutes = (duration.as_secs() / 60) % 60;
+    let seconds = duration.as_secs() % 60;
+    let milliseconds = truncate_trailing_zeros(duration.subsec_millis());
+
+    let mut time = String::new();
+    if hours > 0 {
+        time.push_str(&format!("{hours}h "));
+        time.push_str(&format!("{minutes}m "));
+    } else if minutes > 0 {
+        time.push_str(&format!("{minutes}m "));
+    }
+    time.push_str(&format!("{seconds}.{milliseconds}s"));
+
+    time
+}
+
+fn truncate_trailing_zeros(number: u32) -> String {
+    let mut number = format!("{number:0>3}");
+    while number.ends_with('0') && number.len() > 1 {
+        number.pop();
     }
+    number
 }
 
 #[cfg(test)]
@@ -255,6 +527,104 @@ mod tests {
 
     use super::*;
 
+    fn question() -> Question {
+        Question::builder()
+            .question("Question")
+            .answer("Answer")
+            .build()
+    }
+
+    #[test]
+    fn time_limited_questions() {
+        let stats = Lock::new();
+        stats.start(NumberOfQuestions::TimeLimited(Duration::from_millis(1)));
+        for _ in 0..3 {
+            stats.start_new_question(&question());
+            stats.answer_question("Answer", true);
+        }
+        sleep(Duration::from_millis(5));
+
+        assert_eq!(stats.get_number_of_remaining_questions(), 0);
+        assert_eq!(stats.get_total_accuracy(), "0.00%");
+        assert_eq!(stats.get_current_accuracy(), "100.00%");
+        assert_eq!(stats.get_summary(), "Questions total: 3");
+        // 3 questions in 1ms (time after the limit is not counted)
+        assert_eq!(stats.get_questions_per_minute(), "180000.00");
+    }
+
+    #[test]
+    fn category_breakdown() {
+        let stats = Lock::new();
+        stats.start(NumberOfQuestions::Infinite);
+        stats.start_new_question(&question());
+        stats.answer_question("Answer", true);
+        assert_eq!(stats.get_category_breakdown(), "");
+
+        let answers = [
+            (("Tuesday", 2), ("1900s", 19), true),
+            (("Monday", 1), ("2000s", 20), true),
+            (("Tuesday", 2), ("2000s", 20), false),
+            (("Monday", 1), ("2000s", 20), true),
+        ];
+        for ((weekday, weekday_order), (century, century_order), correct) in answers {
+            let question = Question::builder()
+                .question("Question")
+                .answer("Answer")
+                .categories(&[
+                    Category::new("weekday", weekday, weekday_order),
+                    Category::new("century", century, century_order),
+                ])
+                .build();
+            stats.start_new_question(&question);
+            stats.answer_question("Answer", correct);
+        }
+
+        let breakdown = stats.get_category_breakdown();
+        let lines: Vec<_> = breakdown.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "Per weekday:");
+        assert!(lines[1].starts_with("  Monday   2/2 (100.00%), avg time: "));
+        assert!(lines[2].starts_with("  Tuesday  1/2 (50.00%), avg time: "));
+        assert_eq!(lines[3], "Per century:");
+        assert!(lines[4].starts_with("  1900s  1/1 (100.00%)"));
+        assert!(lines[5].starts_with("  2000s  2/3 (66.67%)"));
+    }
+
+    #[test]
+    fn ranges() {
+        let stats = Lock::new();
+        stats.start(NumberOfQuestions::Infinite);
+        assert!(stats.get_ranges().is_empty());
+
+        stats.set_current_range("1-4");
+        for _ in 0..3 {
+            stats.set_current_range("1-4");
+            stats.start_new_question(&question());
+            stats.answer_question("Answer", true);
+        }
+        stats.set_current_range("1-5");
+        stats.start_new_question(&question());
+        stats.answer_question("Answer", true);
+        // Changed twice before the next answer, back to the previous range
+        stats.set_current_range("1-6");
+        stats.set_current_range("1-5");
+        stats.start_new_question(&question());
+        stats.answer_question("Answer", true);
+        stats.set_current_range("1-4");
+        stats.start_new_question(&question());
+        stats.answer_question("Answer", true);
+        stats.set_current_range("1-3");
+
+        assert_eq!(
+            stats.get_ranges(),
+            vec![
+                "questions 1-3: 1-4",
+                "questions 4-5: 1-5",
+                "question 6: 1-4"
+            ]
+        );
+    }
+
     #[test]
     fn build_and_verify_stats_limited_questions() {
         let stats = Lock::new();
@@ -286,19 +656,19 @@ mod tests {
 
         // 3 correct answers
         for _ in 0..3 {
-            stats.start_new_question();
-            stats.answer_question(true);
+            stats.start_new_question(&question());
+            stats.answer_question("Answer", true);
         }
 
-        assert_eq!(stats.read().time_per_question.len(), 3);
+        assert_eq!(stats.read().records.len(), 3);
 
         // 3 incorrect answers
         for _ in 0..3 {
-            stats.start_new_question();
-            stats.answer_question(false);
+            stats.start_new_question(&question());
+            stats.answer_question("Wrong", false);
         }
 
-        assert_eq!(stats.read().time_per_question.len(), 6);
+        assert_eq!(stats.read().records.len(), 6);
 
         assert_eq!(stats.get_number_of_correct_answers(), "3/6");
         assert_eq!(stats.get_number_of_remaining_questions(), 4);
@@ -309,6 +679,12 @@ mod tests {
         assert!(summary.contains("Questions total: 10"));
         assert!(summary.contains("answers: 6"));
         assert!(summary.contains("skipped: 4"));
+
+        let totals = stats.get_totals();
+        assert_eq!(totals.number_of_questions, NumberOfQuestions::Limited(10));
+        assert_eq!(totals.answered, 6);
+        assert_eq!(totals.skipped, 4);
+        assert_eq!(totals.correct, 3);
     }
 
     #[test]
@@ -318,19 +694,19 @@ mod tests {
 
         // 3 correct answers
         for _ in 0..3 {
-            stats.start_new_question();
-            stats.answer_question(true);
+            stats.start_new_question(&question());
+            stats.answer_question("Answer", true);
         }
 
-        assert_eq!(stats.read().time_per_question.len(), 3);
+        assert_eq!(stats.read().records.len(), 3);
 
         // 3 incorrect answers
         for _ in 0..3 {
-            stats.start_new_question();
-            stats.answer_question(false);
+            stats.start_new_question(&question());
+            stats.answer_question("Wrong", false);
         }
 
-        assert_eq!(stats.read().time_per_question.len(), 6);
+        assert_eq!(stats.read().records.len(), 6);
 
         assert_eq!(stats.get_number_of_correct_answers(), "3/6");
         assert_eq!(
@@ -356,22 +732,22 @@ mod tests {
 
         // 3 correct answers
         for _ in 0..3 {
-            stats.start_new_question();
-            stats.answer_question(true);
+            stats.start_new_question(&question());
+            stats.answer_question("Answer", true);
         }
 
-        assert_eq!(stats.read().time_per_question.len(), 3);
+        assert_eq!(stats.read().records.len(), 3);
 
         // Answering incorrectly first (Repeat mode)
-        stats.start_new_question();
-        stats.answer_question(false);
-        assert_eq!(stats.read().time_per_question.len(), 4);
-        stats.answer_question(false);
-        assert_eq!(stats.read().time_per_question.len(), 4);
-        stats.answer_question(false);
-        assert_eq!(stats.read().time_per_question.len(), 4);
-        stats.answer_question(true);
-        assert_eq!(stats.read().time_per_question.len(), 4);
+        stats.start_new_question(&question());
+        stats.answer_question("Wrong", false);
+        assert_eq!(stats.read().records.len(), 4);
+        stats.answer_question("Wrong", false);
+        assert_eq!(stats.read().records.len(), 4);
+        stats.answer_question("Wrong", false);
+        assert_eq!(stats.read().records.len(), 4);
+        stats.answer_question("Answer", true);
+        assert_eq!(stats.read().records.len(), 4);
 
         assert_eq!(
             stats.get_number_of_correct_answers(),
@@ -395,22 +771,22 @@ mod tests {
 
         // 3 correct answers
         for _ in 0..3 {
-            stats.start_new_question();
-            stats.answer_question(true);
+            stats.start_new_question(&question());
+            stats.answer_question("Answer", true);
         }
 
-        assert_eq!(stats.read().time_per_question.len(), 3);
+        assert_eq!(stats.read().records.len(), 3);
 
         // Answering incorrectly first (Repeat mode)
-        stats.start_new_question();
-        stats.answer_question(false);
-        assert_eq!(stats.read().time_per_question.len(), 4);
-        stats.answer_question(false);
-        assert_eq!(stats.read().time_per_question.len(), 4);
-        stats.answer_question(false);
-        assert_eq!(stats.read().time_per_question.len(), 4);
-        stats.answer_question(true);
-        assert_eq!(stats.read().time_per_question.len(), 4);
+        stats.start_new_question(&question());
+        stats.answer_question("Wrong", false);
+        assert_eq!(stats.read().records.len(), 4);
+        stats.answer_question("Wrong", false);
+        assert_eq!(stats.read().records.len(), 4);
+        stats.answer_question("Wrong", false);
+        assert_eq!(stats.read().records.len(), 4);
+        stats.answer_question("Answer", true);
+        assert_eq!(stats.read().records.len(), 4);
 
         assert_eq!(
             stats.get_number_of_correct_answers(),
@@ -423,6 +799,66 @@ mod tests {
         assert!(summary.contains("Questions total: 4"));
     }
 
+    #[test]
+    fn records_keep_all_attempts() {
+        let stats = Lock::new();
+        stats.start(NumberOfQuestions::Limited(2));
+
+        stats.start_new_question(&question());
+        stats.answer_question("Answer", true);
+
+        stats.start_new_question(&question());
+        stats.answer_question("Wrong", false);
+        stats.answer_question("Still wrong", false);
+        stats.answer_question("Answer", true);
+
+        let records = stats.get_records();
+        assert_eq!(records.len(), 2);
+
+        assert_eq!(records[0].prompt, "Question");
+        assert_eq!(records[0].correct_answer, "Answer");
+        assert_eq!(records[0].given_answers, vec!["Answer".to_string()]);
+        assert!(records[0].correct);
+
+        assert_eq!(
+            records[1].given_answers,
+            vec![
+                "Wrong".to_string(),
+                "Still wrong".to_string(),
+                "Answer".to_string()
+            ]
+        );
+        assert!(!records[1].correct, "Only the first attempt counts");
+    }
+
+    #[test]
+    fn timed_out_questions() {
+        let stats = Lock::new();
+        stats.start(NumberOfQuestions::Limited(3));
+
+        stats.start_new_question(&question());
+        stats.time_out_question();
+
+        stats.start_new_question(&question());
+        stats.answer_question("Wrong", false);
+        stats.time_out_question();
+
+        stats.start_new_question(&question());
+        stats.answer_question("Answer", true);
+
+        assert_eq!(stats.get_number_of_timed_out_questions(), 2);
+        assert_eq!(stats.get_number_of_correct_answers(), "1/3");
+        assert_eq!(stats.get_number_of_remaining_questions(), 0);
+
+        let records = stats.get_records();
+        assert_eq!(records.len(), 3);
+        assert!(records[0].given_answers.is_empty());
+        assert!(!records[0].correct && records[0].timed_out);
+        assert_eq!(records[1].given_answers, vec!["Wrong".to_string()]);
+        assert!(!records[1].correct && records[1].timed_out);
+        assert!(records[2].correct && !records[2].timed_out);
+    }
+
     #[test]
     fn time_stats_no_questions() {
         let stats = Lock::new();
@@ -447,9 +883,9 @@ mod tests {
         let stats = Lock::new();
         stats.start(NumberOfQuestions::Infinite);
 
-        stats.start_new_question();
+        stats.start_new_question(&question());
         sleep(Duration::from_millis(200));
-        stats.answer_question(true);
+        stats.answer_question("Answer", true);
 
         let max_time = stats.get_max_question_time();
         let min_time = stats.get_min_question_time();
@@ -467,13 +903,13 @@ mod tests {
         let stats = Lock::new();
         stats.start(NumberOfQuestions::Infinite);
 
-        stats.start_new_question();
+        stats.start_new_question(&question());
         sleep(Duration::from_millis(50));
-        stats.answer_question(true);
+        stats.answer_question("Answer", true);
 
-        stats.start_new_question();
+        stats.start_new_question(&question());
         sleep(Duration::from_millis(400));
-        stats.answer_question(true);
+        stats.answer_question("Answer", true);
 
         let max_time = stats.get_max_question_time();
         let min_time = stats.get_min_question_time();
@@ -490,47 +926,47 @@ mod tests {
 
     #[test]
     fn duration_format() {
-        assert_eq!(Stats::format_duration(&Duration::from_millis(0)), "0.0s");
-        assert_eq!(Stats::format_duration(&Duration::from_millis(1)), "0.001s");
-        assert_eq!(Stats::format_duration(&Duration::from_millis(10)), "0.01s");
-        assert_eq!(Stats::format_duration(&Duration::from_millis(11)), "0.011s");
-        assert_eq!(Stats::format_duration(&Duration::from_millis(100)), "0.1s");
+        assert_eq!(format_duration(&Duration::from_millis(0)), "0.0s");
+        assert_eq!(format_duration(&Duration::from_millis(1)), "0.001s");
+        assert_eq!(format_duration(&Duration::from_millis(10)), "0.01s");
+        assert_eq!(format_duration(&Duration::from_millis(11)), "0.011s");
+        assert_eq!(format_duration(&Duration::from_millis(100)), "0.1s");
         assert_eq!(
-            Stats::format_duration(&Duration::from_millis(101)),
+            format_duration(&Duration::from_millis(101)),
             "0.101s"
         );
-        assert_eq!(Stats::format_duration(&Duration::from_millis(110)), "0.11s");
+        assert_eq!(format_duration(&Duration::from_millis(110)), "0.11s");
         assert_eq!(
-            Stats::format_duration(&Duration::from_millis(111)),
+            format_duration(&Duration::from_millis(111)),
             "0.111s"
         );
-        assert_eq!(Stats::format_duration(&Duration::from_millis(1000)), "1.0s");
+        assert_eq!(format_duration(&Duration::from_millis(1000)), "1.0s");
         assert_eq!(
-            Stats::format_duration(&Duration::from_millis(1001)),
+            format_duration(&Duration::from_millis(1001)),
             "1.001s"
         );
         assert_eq!(
-            Stats::format_duration(&Duration::from_millis(10 * 1000)),
+            format_duration(&Duration::from_millis(10 * 1000)),
             "10.0s"
         );
         assert_eq!(
-            Stats::format_duration(&Duration::from_millis(60 * 1000)),
+            format_duration(&Duration::from_millis(60 * 1000)),
             "1m 0.0s"
         );
         assert_eq!(
-            Stats::format_duration(&Duration::from_millis(10 * 60 * 1000)),
+            format_duration(&Duration::from_millis(10 * 60 * 1000)),
             "10m 0.0s"
         );
         assert_eq!(
-            Stats::format_duration(&Duration::from_millis(60 * 60 * 1000)),
+            format_duration(&Duration::from_millis(60 * 60 * 1000)),
             "1h 0m 0.0s"
         );
         assert_eq!(
-            Stats::format_duration(&Duration::from_millis(168 * 60 * 60 * 1000)),
+            format_duration(&Duration::from_millis(168 * 60 * 60 * 1000)),
             "168h 0m 0.0s"
         );
         assert_eq!(
-            Stats::format_duration(&Duration::from_millis(3 * 60 * 60 * 1000 + 5 * 60 * 1000 + 7 * 1000 + 93)),
+            format_duration(&Duration::from_millis(3 * 60 * 60 * 1000 + 5 * 60 * 1000 + 7 * 1000 + 93)),
             "3h 5m 7.093s"
         );
     }
@@ -0,0 +1,221 @@
+//! Printable worksheets. Questions are laid out in columns and their answers are written to
+//! a separate answer key next to the worksheet (`<name>-answers.<extension>`).
+//!
+//! The format depends on the extension of the file: Markdown (`.md`), HTML (`.html`, `.htm`)
+//! or plain text (anything else).
+
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::question::Question;
+
+/// Maximum number of columns
+const COLUMNS: usize = 3;
+const ANSWER_LINE: &str = "__________";
+/// Space between columns of the plain text format
+const COLUMN_GAP: usize = 4;
+/// Plain text uses fewer columns if they don't fit the page
+const PAGE_WIDTH: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Markdown,
+    Html,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Format {
+        let extension = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("md" | "markdown") => Format::Markdown,
+            Some("html" | "htm") => Format::Html,
+            _ => Format::Text,
+        }
+    }
+}
+
+/// Writes `questions` of `command` to `path` and their answers to the answer key.
+/// Returns the path of the answer key.
+pub fn write(path: &Path, command: &str, questions: &[Question]) -> io::Result<PathBuf> {
+    if let Some(dir) = path.parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+    let format = Format::from_path(path);
+
+    let prompts: Vec<_> = questions
+        .iter()
+        .map(|question| format!("{} {ANSWER_LINE}", question.prompt()))
+        .collect();
+    let answers: Vec<_> = questions
+        .iter()
+        .map(|question| question.correct_answer().clone())
+        .collect();
+
+    let answer_key_path = answer_key_path(path);
+    fs::write(
+        path,
+        render(format, &format!("Worksheet: {command}"), &prompts),
+    )?;
+    fs::write(
+        &answer_key_path,
+        render(format, &format!("Answer key: {command}"), &answers),
+    )?;
+    Ok(answer_key_path)
+}
+
+fn answer_key_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{stem}-answers.{}", extension.to_string_lossy()),
+        None => format!("{stem}-answers"),
+    };
+    path.with_file_name(name)
+}
+
+/// Lays numbered `items` out in rows of `COLUMNS` columns
+fn render(format: Format, title: &str, items: &[String]) -> String {
+    let items: Vec<_> = items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| format!("{}. {item}", i + 1))
+        .collect();
+    match format {
+        Format::Text => render_text(title, &items),
+        Format::Markdown => render_markdown(title, &items),
+        Format::Html => render_html(title, &items),
+    }
+}
+
+fn render_text(title: &str, items: &[String]) -> String {
+    let width = items
+        .iter()
+        .map(|item| item.chars().count())
+        .max()
+        .unwrap_or_default()
+        + COLUMN_GAP;
+    let columns = ((PAGE_WIDTH + COLUMN_GAP) / width).clamp(1, COLUMNS);
+    let mut text = format!("{title}\n{}\n\n", "=".repeat(title.chars().count()));
+    for row in items.chunks(columns) {
+        let row: String = row.iter().map(|item| format!("{item:<width$}")).collect();
+        text.push_str(row.trim_end());
+        text.push_str("\n\n");
+    }
+    text
+}
+
+fn render_markdown(title: &str, items: &[String]) -> String {
+    let mut text = format!("# {title}\n\n");
+    text.push_str(&format!("|{}\n", "   |".repeat(COLUMNS)));
+    text.push_str(&format!("|{}\n", "---|".repeat(COLUMNS)));
+    for row in items.chunks(COLUMNS) {
+        let mut cells: Vec<_> = row.iter().map(|item| markdown_escape(item)).collect();
+        cells.resize(COLUMNS, String::new());
+        text.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    text
+}
+
+fn render_html(title: &str, items: &[String]) -> String {
+    let title = html_escape(title);
+    let mut text = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>td {{ padding: 0.75em 2em 0.75em 0; }}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<table>\n"
+    );
+    for row in items.chunks(COLUMNS) {
+        let cells: String = row
+            .iter()
+            .map(|item| format!("<td>{}</td>", html_escape(item)))
+            .collect();
+        text.push_str(&format!("<tr>{cells}</tr>\n"));
+    }
+    text.push_str("</table>\n</body>\n</html>\n");
+    text
+}
+
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '|' | '*' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("{i}*2")).collect()
+    }
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(Format::from_path(Path::new("a/b.md")), Format::Markdown);
+        assert_eq!(Format::from_path(Path::new("b.HTML")), Format::Html);
+        assert_eq!(Format::from_path(Path::new("b.htm")), Format::Html);
+        assert_eq!(Format::from_path(Path::new("b.txt")), Format::Text);
+        assert_eq!(Format::from_path(Path::new("b")), Format::Text);
+    }
+
+    #[test]
+    fn answer_key_next_to_worksheet() {
+        assert_eq!(
+            answer_key_path(Path::new("dir/sheet.md")),
+            PathBuf::from("dir/sheet-answers.md")
+        );
+        assert_eq!(
+            answer_key_path(Path::new("sheet")),
+            PathBuf::from("sheet-answers")
+        );
+    }
+
+    #[test]
+    fn text_columns() {
+        let text = render(Format::Text, "Title", &items(4));
+        assert_eq!(
+            text,
+            "Title\n=====\n\n1. 1*2    2. 2*2    3. 3*2\n\n4. 4*2\n\n"
+        );
+
+        // Long items don't fit the page in 3 columns
+        let long_items = vec!["x".repeat(30); 3];
+        let text = render(Format::Text, "Title", &long_items);
+        assert_eq!(text.lines().filter(|line| line.starts_with('1')).count(), 1);
+        assert!(text.contains(&format!("\n3. {}\n", "x".repeat(30))));
+    }
+
+    #[test]
+    fn markdown_table() {
+        let text = render(Format::Markdown, "Title", &items(4));
+        assert_eq!(
+            text,
+            "# Title\n\n|   |   |   |\n|---|---|---|\n| 1. 1\\*2 | 2. 2\\*2 | 3. 3\\*2 |\n| 4. 4\\*2 |  |  |\n"
+        );
+    }
+
+    #[test]
+    fn html_table() {
+        let text = render(Format::Html, "A & B", &["1<2".to_string()]);
+        assert!(text.contains("<h1>A &amp; B</h1>"));
+        assert!(text.contains("<tr><td>1. 1&lt;2</td></tr>\n</table>"));
+    }
+}
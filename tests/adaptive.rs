@@ -0,0 +1,55 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn range_widens_after_fluent_answers() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=6",
+        "--no-history",
+        "--adaptive",
+        "powers",
+        "--base=1",
+        "--upper-boundary=10",
+    ]) // Every answer is 1
+    .write_stdin("1\n1\n1\n1\n1\n1\n")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Ranges used:\n  questions 1-5: exponents 1-4\n  question 6: exponents 1-5",
+    ));
+}
+
+#[test]
+fn range_narrows_after_misses() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "-n",
+        "9",
+        "--no-history",
+        "-a",
+        "powers",
+        "--base=1",
+        "--upper-boundary=10",
+    ])
+    .write_stdin("1\n1\n1\n1\n1\n0\n0\n1\n1\n")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "  questions 1-5: exponents 1-4\n  questions 6-8: exponents 1-5\n  question 9: exponents 1-4",
+    ));
+}
+
+#[test]
+fn no_ranges_without_adaptive_mode() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["-n", "1", "--no-history", "powers", "--base=1"])
+        .write_stdin("1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Ranges used").not());
+}
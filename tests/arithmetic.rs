@@ -0,0 +1,64 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn arithmetic_unrecognised_arg() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["arithmetic", "--incorrect"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Usage:").and(predicate::str::contains("unrecognised option")),
+        );
+}
+
+#[test]
+fn arithmetic_show_help() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let pred = predicate::str::contains("Usage:");
+    let pred = pred.and(predicate::str::contains("Arithmetic options"));
+    let pred = pred.and(predicate::str::contains(
+        "Display help for arithmetic command",
+    ));
+
+    cmd.arg("arithmetic")
+        .arg("-h")
+        .assert()
+        .success()
+        .stdout(pred);
+}
+
+#[test]
+fn arithmetic_one_question_correct_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "--seed=1",
+        "arithmetic",
+        "--digits=1",
+        "--operands=9",
+        "--no-carry",
+    ]) // Nine 1-digit numbers without carrying are all ones
+    .write_stdin("9")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1",
+    ))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn arithmetic_per_operation_breakdown() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["-n", "20", "--no-history", "arithmetic", "-o", "mixed"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Per operation:"))
+        .stdout(predicate::str::contains("Per carrying/borrowing:"));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn bases_unrecognised_arg() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["bases", "--incorrect"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Usage:").and(predicate::str::contains("unrecognised option")),
+        );
+}
+
+#[test]
+fn bases_show_help() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let pred = predicate::str::contains("Usage:");
+    let pred = pred.and(predicate::str::contains("Bases options"));
+    let pred = pred.and(predicate::str::contains("Display help for bases command"));
+
+    cmd.arg("bases").arg("-h").assert().success().stdout(pred);
+}
+
+#[test]
+fn bases_lenient_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "bases",
+        "--from=binary",
+        "--to=hex",
+        "-w",
+        "1",
+    ])
+    .write_stdin("0X_0")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("What is 0b"))
+    .stdout(predicate::str::contains(" in hex?"));
+}
+
+#[test]
+fn bases_same_source_and_target() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["bases", "-f", "hex", "-t", "hex"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "source and target bases must be different",
+        ));
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn calendar_unrecognised_arg() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["calendar", "--incorrect"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Usage:").and(predicate::str::contains("unrecognised option")),
+        );
+}
+
+#[test]
+fn calendar_show_help() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let pred = predicate::str::contains("Usage:");
+    let pred = pred.and(predicate::str::contains("Calendar options"));
+    let pred = pred.and(predicate::str::contains(
+        "Display help for calendar command",
+    ));
+
+    cmd.arg("calendar")
+        .arg("-h")
+        .assert()
+        .success()
+        .stdout(pred);
+}
+
+#[test]
+fn calendar_difference_correct_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "calendar",
+        "--mode=difference",
+        "--max-days=1",
+    ])
+    .write_stdin("1")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("How many days between"))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn calendar_year_too_low() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["calendar", "-l", "1500"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("year boundary too low"));
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn computus_unrecognised_arg() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["computus", "--incorrect"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Usage:").and(predicate::str::contains("unrecognised option")),
+        );
+}
+
+#[test]
+fn computus_show_help() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let pred = predicate::str::contains("Usage:");
+    let pred = pred.and(predicate::str::contains("Computus options"));
+    let pred = pred.and(predicate::str::contains(
+        "Display help for computus command",
+    ));
+
+    cmd.arg("computus")
+        .arg("-h")
+        .assert()
+        .success()
+        .stdout(pred);
+}
+
+#[test]
+fn computus_correct_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "computus",
+        "-l",
+        "2026",
+        "-u",
+        "2026",
+    ])
+    .write_stdin("05.04")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("When is Easter in 2026?"))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn computus_orthodox_before_reform() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "computus",
+        "--easter=orthodox",
+        "-l",
+        "1500",
+        "-u",
+        "1500",
+    ])
+    .write_stdin("April 19")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "When is Orthodox Easter in 1500 (Julian calendar)?",
+    ))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn computus_year_too_low() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["computus", "-l", "1500"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("year boundary too low"));
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn division_unrecognised_arg() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["division", "--incorrect"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Usage:").and(predicate::str::contains("unrecognised option")),
+        );
+}
+
+#[test]
+fn division_show_help() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let pred = predicate::str::contains("Usage:");
+    let pred = pred.and(predicate::str::contains("Division options"));
+    let pred = pred.and(predicate::str::contains(
+        "Display help for division command",
+    ));
+
+    cmd.arg("division")
+        .arg("-h")
+        .assert()
+        .success()
+        .stdout(pred);
+}
+
+#[test]
+fn division_one_question_correct_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "division",
+        "--lower-boundary-1=8",
+        "--upper-boundary-1=8",
+        "--lower-boundary-2=7",
+        "--upper-boundary-2=7",
+    ])
+    .write_stdin("8")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("56/7"))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn division_remainder_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "division",
+        "--mode=remainder",
+        "--lower-boundary-1=8",
+        "--upper-boundary-1=8",
+        "--lower-boundary-2=1",
+        "--upper-boundary-2=1",
+    ]) // Dividing by 1 never leaves a remainder
+    .write_stdin("8r0")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("8/1 (with remainder)"))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn division_invalid_divisor() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["division", "--lower-boundary-2=0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("divisor must be at least 1"));
+}
@@ -45,7 +45,7 @@ fn doomsday_show_help() {
 #[test]
 fn doomsday_one_question_incorrect_answer() {
     let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
-    cmd.args(["--number-of-questions=1", "doomsday"])
+    cmd.args(["--number-of-questions=1", "--no-history", "doomsday"])
         .write_stdin("hehe") // "hehe" is not a weekday
         .assert()
         .success()
@@ -54,3 +54,176 @@ fn doomsday_one_question_incorrect_answer() {
                 .and(predicate::str::contains("Correct answer:")),
         );
 }
+
+#[test]
+fn doomsday_year_mode() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "doomsday",
+        "--mode=year",
+        "-l",
+        "2024",
+        "-u",
+        "2024",
+    ])
+    .write_stdin("thu")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("What is the doomsday of 2024?"))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn doomsday_month_mode() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["-n", "5", "--no-history", "doomsday", "-m", "month"])
+        .write_stdin("\n\n\n\n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Which day of "))
+        .stdout(predicate::str::contains("Per month:"));
+}
+
+#[test]
+fn doomsday_explanation() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "--explain",
+        "doomsday",
+        "--mode=year",
+        "-l",
+        "2024",
+        "-u",
+        "2024",
+    ])
+    .write_stdin("mon")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Anchor day of the 2000s: Tuesday"))
+    .stdout(predicate::str::contains(
+        "Doomsday of 2024: Tuesday + 2 = Thursday",
+    ));
+}
+
+#[test]
+fn doomsday_explanation_with_any_behaviour_on_error() {
+    for behaviour in ["continue", "repeat"] {
+        let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+        cmd.args([
+            "--number-of-questions=1",
+            "--no-history",
+            "--explain",
+            &format!("--behavior-on-error={behaviour}"),
+            "doomsday",
+            "--mode=year",
+            "-l",
+            "2024",
+            "-u",
+            "2024",
+        ])
+        .write_stdin("mon\nthu\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Doomsday of 2024: Tuesday + 2 = Thursday",
+        ));
+    }
+}
+
+#[test]
+fn doomsday_no_explanation_by_default() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "doomsday",
+        "--mode=year",
+        "-l",
+        "2024",
+        "-u",
+        "2024",
+    ])
+    .write_stdin("mon")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Anchor day").not());
+}
+
+#[test]
+fn doomsday_julian_calendar() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "doomsday",
+        "--calendar=julian",
+        "--mode=year",
+        "-l",
+        "1582",
+        "-u",
+        "1582",
+    ])
+    .write_stdin("wed")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "What is the doomsday of 1582 (Julian)?",
+    ))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn doomsday_invalid_adoption_date() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["doomsday", "-c", "historical", "--adoption-date=1500-01-01"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "cannot be before the Gregorian reform",
+        ));
+}
+
+#[test]
+fn doomsday_date_format() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "doomsday",
+        "--date-format=long",
+        "-l",
+        "2024",
+        "-u",
+        "2024",
+    ])
+    .write_stdin("\n")
+    .assert()
+    .success()
+    .stdout(
+        predicate::str::is_match("What is the weekday of [0-9]{1,2} [A-Z][a-z]+ 2024\\?").unwrap(),
+    );
+}
+
+#[test]
+fn doomsday_localised_answers() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "doomsday",
+        "--mode=year",
+        "--language=fr",
+        "-l",
+        "2024",
+        "-u",
+        "2024",
+    ])
+    .write_stdin("Jeudi")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use assert_cmd::Command;
+
+use common::CMD;
+
+fn export(name: &str, format_args: &[&str]) -> PathBuf {
+    let path = env::temp_dir().join(format!(
+        "practicestuff_export_{}_{name}",
+        std::process::id()
+    ));
+    let export_arg = format!("--export={}", path.display());
+
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["--number-of-questions=2", "--no-history", &export_arg])
+        .args(format_args)
+        .args(["powers", "-b", "7", "-l", "2", "-u", "2"]) // Essentially guarantees question 7^2 (= 49)
+        .write_stdin("49\n50\n")
+        .assert()
+        .success();
+
+    path
+}
+
+fn read_and_remove(path: &Path) -> String {
+    let text = fs::read_to_string(path).expect("session not exported");
+    fs::remove_file(path).expect("could not remove exported file");
+    text
+}
+
+#[test]
+fn export_json() {
+    let json = read_and_remove(&export("json", &[]));
+    assert!(json.contains("\"command\": \"powers\""));
+    assert!(json.contains("\"completed\": true"));
+    assert!(json.contains("\"correct\": 1,"));
+    assert!(json.contains("\"accuracy\": 50.00,"));
+    assert!(json.contains(
+        "{\"prompt\": \"7^2\", \"expected_answer\": \"49\", \"given_answers\": [\"50\"], \"correct\": false, \"timed_out\": false, \"attempts\": 1, "
+    ));
+}
+
+#[test]
+fn export_csv() {
+    let path = export("session.csv", &["--format=csv"]);
+    let csv = read_and_remove(&path);
+    let lines: Vec<_> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[0],
+        "prompt,expected_answer,given_answers,correct,timed_out,attempts,duration_ms"
+    );
+    assert!(lines[1].starts_with("7^2,49,49,true,false,1,"));
+    assert!(lines[2].starts_with("7^2,49,50,false,false,1,"));
+
+    let summary = read_and_remove(&path.with_file_name(format!(
+        "practicestuff_export_{}_session-summary.csv",
+        std::process::id()
+    )));
+    let lines: Vec<_> = summary.lines().collect();
+    assert_eq!(lines[0], "metric,value");
+    assert!(lines.contains(&"answered,2"));
+    assert!(lines.contains(&"skipped,0"));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn gcd_unrecognised_arg() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["gcd", "--incorrect"]).assert().failure().stderr(
+        predicate::str::contains("Usage:").and(predicate::str::contains("unrecognised option")),
+    );
+}
+
+#[test]
+fn gcd_show_help() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let pred = predicate::str::contains("Usage:");
+    let pred = pred.and(predicate::str::contains("Gcd options"));
+    let pred = pred.and(predicate::str::contains("Display help for gcd command"));
+
+    cmd.arg("gcd").arg("-h").assert().success().stdout(pred);
+}
+
+#[test]
+fn gcd_euclidean_steps_on_wrong_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "--explain",
+        "gcd",
+        "--mode=gcd",
+        "--lower-boundary-1=252",
+        "--upper-boundary-1=252",
+        "--lower-boundary-2=105",
+        "--upper-boundary-2=105",
+    ])
+    .write_stdin("7")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("GCD(252, 105)"))
+    .stdout(predicate::str::contains("252 = 2 * 105 + 42"))
+    .stdout(predicate::str::contains("Correct answers: 0/1"));
+}
+
+#[test]
+fn gcd_lower_boundary_too_low() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["gcd", "--lower-boundary-1=0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "lower boundary must be at least 1",
+        ));
+}
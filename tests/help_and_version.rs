@@ -81,6 +81,7 @@ fn powers_one_question() {
     let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
     cmd.args([
         "--number-of-questions=1",
+        "--no-history",
         "powers",
         "-b",
         "7",
@@ -0,0 +1,121 @@
+mod common;
+
+use std::{env, fs};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn session_appended_to_history_file() {
+    let path = env::temp_dir().join(format!("practicestuff_history_{}", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let history_file = format!("--history-file={}", path.display());
+
+    for _ in 0..2 {
+        let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+        cmd.args([
+            "--number-of-questions=1",
+            history_file.as_str(),
+            "powers",
+            "-b",
+            "7",
+            "-l",
+            "2",
+            "-u",
+            "2",
+        ]) // Essentially guarantees question 7^2 (= 49)
+        .write_stdin("49")
+        .assert()
+        .success();
+    }
+
+    let history = fs::read_to_string(&path).expect("history file not created");
+    fs::remove_file(&path).expect("could not remove history file");
+
+    let lines: Vec<_> = history.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("session\t"));
+    assert!(lines[0].contains("\tpowers\t--number-of-questions=1,--history-file="));
+    assert!(lines[0].ends_with("\t-b,7,-l,2,-u,2\tcompleted"));
+    assert!(lines[1].starts_with("question\t7^2\t49\t49\t1\t"));
+    assert!(lines[2].starts_with("session\t"));
+    assert!(lines[3].starts_with("question\t7^2\t49\t49\t1\t"));
+}
+
+#[test]
+fn history_command_summarises_sessions() {
+    let path = env::temp_dir().join(format!(
+        "practicestuff_history_command_{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+    let history_file = format!("--history-file={}", path.display());
+
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=2",
+        history_file.as_str(),
+        "times_table",
+        "--lower-boundary-1=7",
+        "--upper-boundary-1=7",
+        "--lower-boundary-2=7",
+        "--upper-boundary-2=7",
+    ]) // Essentially guarantees question 7*7 (=49)
+    .write_stdin("49\n48\n")
+    .assert()
+    .success();
+
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([history_file.as_str(), "history"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Sessions:")
+                .and(predicate::str::contains("times_table"))
+                .and(predicate::str::contains("1/2 (50.00%)"))
+                .and(predicate::str::contains(
+                    "times_table: 1 session(s), 2 question(s)",
+                )),
+        );
+
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([history_file.as_str(), "history", "--skill=doomsday"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No sessions found."));
+
+    fs::remove_file(&path).expect("could not remove history file");
+}
+
+#[test]
+fn history_show_help() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let pred = predicate::str::contains("Usage:");
+    let pred = pred.and(predicate::str::contains("History options"));
+    let pred = pred.and(predicate::str::contains("Display help for history command"));
+
+    cmd.arg("history").arg("-h").assert().success().stdout(pred);
+}
+
+#[test]
+fn history_invalid_date() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["history", "--since=yesterday"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Usage:").and(predicate::str::contains("invalid date")));
+}
+
+#[test]
+fn conflicting_history_options() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["--history-file=history", "--no-history", "powers"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Usage:")
+                .and(predicate::str::contains("cannot be used together")),
+        );
+}
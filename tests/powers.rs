@@ -47,6 +47,7 @@ fn powers_one_question_correct_answer() {
     let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
     cmd.args([
         "--number-of-questions=1",
+        "--no-history",
         "powers",
         "-b",
         "7",
@@ -64,7 +65,7 @@ fn powers_one_question_correct_answer() {
 #[test]
 fn powers_one_question_incorrect_answer() {
     let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
-    cmd.args(["--number-of-questions=1", "powers"])
+    cmd.args(["--number-of-questions=1", "--no-history", "powers"])
         .write_stdin("hehe") // "hehe" is probably not a power of 2
         .assert()
         .success()
@@ -73,3 +74,45 @@ fn powers_one_question_incorrect_answer() {
                 .and(predicate::str::contains("Correct answer:")),
         );
 }
+
+#[test]
+fn powers_breakdown_per_exponent() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=2",
+        "--no-history",
+        "powers",
+        "-b",
+        "7",
+        "-l",
+        "2",
+        "-u",
+        "2",
+    ])
+    .write_stdin("49\n48\n")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Per exponent:\n  2  1/2 (50.00%), avg time: ",
+    ));
+}
+
+#[test]
+fn powers_inverse_correct_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "powers",
+        "--mode=inverse",
+        "-l",
+        "13",
+        "-u",
+        "13",
+    ])
+    .write_stdin("13")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("2^? = 8192"))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn primes_unrecognised_arg() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["primes", "--incorrect"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Usage:").and(predicate::str::contains("unrecognised option")),
+        );
+}
+
+#[test]
+fn primes_show_help() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let pred = predicate::str::contains("Usage:");
+    let pred = pred.and(predicate::str::contains("Primes options"));
+    let pred = pred.and(predicate::str::contains("Display help for primes command"));
+
+    cmd.arg("primes").arg("-h").assert().success().stdout(pred);
+}
+
+#[test]
+fn primes_factorisation_any_order() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "primes",
+        "--mode=factorisation",
+        "--lower-boundary=360",
+        "--upper-boundary=360",
+    ])
+    .write_stdin("5 * 3^2 * 2*2*2")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Factorise 360"))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn primes_lower_boundary_too_low() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["primes", "--lower-boundary=1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "lower boundary must be at least 2",
+        ));
+}
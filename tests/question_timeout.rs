@@ -0,0 +1,45 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn unanswered_question_times_out() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "-n",
+        "2",
+        "--question-timeout=1",
+        "--no-history",
+        "--no-color",
+        "powers",
+        "--base=1",
+    ]) // Only the first question is answered
+    .write_stdin("1\n")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Out of time! Correct answer: 1"))
+    .stdout(predicate::str::contains(
+        "Correct answers: 1/2\nTimed out: 1\n",
+    ));
+}
+
+#[test]
+fn correct_answer_not_shown_on_timeout_with_continue() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "-n",
+        "1",
+        "--question-timeout=1",
+        "--behavior-on-error=continue",
+        "--no-history",
+        "--no-color",
+        "powers",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Out of time!\n"))
+    .stdout(predicate::str::contains("Correct answer:").not());
+}
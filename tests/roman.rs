@@ -0,0 +1,76 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn roman_unrecognised_arg() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["roman", "--incorrect"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Usage:").and(predicate::str::contains("unrecognised option")),
+        );
+}
+
+#[test]
+fn roman_show_help() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let pred = predicate::str::contains("Usage:");
+    let pred = pred.and(predicate::str::contains("Roman options"));
+    let pred = pred.and(predicate::str::contains("Display help for roman command"));
+
+    cmd.arg("roman").arg("-h").assert().success().stdout(pred);
+}
+
+#[test]
+fn roman_lowercase_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "roman",
+        "--mode=to-roman",
+        "-l",
+        "1994",
+        "-u",
+        "1994",
+    ])
+    .write_stdin("mcmxciv")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("What is 1994 in Roman numerals?"))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn roman_non_canonical_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "roman",
+        "-m",
+        "to-roman",
+        "-l",
+        "4",
+        "-u",
+        "4",
+    ])
+    .write_stdin("IIII")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Correct answers: 0/1"));
+}
+
+#[test]
+fn roman_number_too_high() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["roman", "-u", "4000"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("from 1 to 3999"));
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use assert_cmd::Command;
+
+use common::CMD;
+
+fn prompts(seed: &str, command: &str) -> Vec<String> {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let output = cmd
+        .args(["-n", "10", "--no-history", "--no-color", seed, command])
+        .output()
+        .expect("should run");
+    assert!(output.status.success());
+    String::from_utf8(output.stdout)
+        .expect("output should be UTF-8")
+        .lines()
+        .filter(|line| line.starts_with("Q: "))
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn same_seed_same_questions() {
+    for command in ["powers", "times_table", "doomsday"] {
+        let questions = prompts("--seed=42", command);
+        assert_eq!(questions.len(), 10);
+        assert_eq!(questions, prompts("--seed=42", command));
+        assert_ne!(questions, prompts("--seed=43", command));
+    }
+}
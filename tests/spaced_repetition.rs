@@ -0,0 +1,56 @@
+mod common;
+
+use std::{env, fs};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn failed_question_is_asked_first_next_time() {
+    let data_dir = env::temp_dir().join(format!(
+        "practicestuff_spaced_repetition_{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&data_dir);
+
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.env("XDG_DATA_HOME", &data_dir)
+        .env("APPDATA", &data_dir)
+        .args([
+            "--number-of-questions=1",
+            "--no-history",
+            "--spaced-repetition",
+            "times_table",
+            "--lower-boundary-1=7",
+            "--upper-boundary-1=7",
+            "--lower-boundary-2=8",
+            "--upper-boundary-2=8",
+        ]) // Essentially guarantees question 7*8 (=56)
+        .write_stdin("54")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Correct answers: 0/1"));
+
+    let progress = fs::read_to_string(data_dir.join(CMD).join("repetition"))
+        .expect("spaced repetition progress not saved");
+    assert!(progress.starts_with("times_table:7*8\t"));
+
+    // Failed question is served first, even though the range is wider
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.env("XDG_DATA_HOME", &data_dir)
+        .env("APPDATA", &data_dir)
+        .args([
+            "--number-of-questions=1",
+            "--no-history",
+            "--spaced-repetition",
+            "times_table",
+        ])
+        .write_stdin("56")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Correct answers: 1/1"));
+
+    fs::remove_dir_all(&data_dir).expect("could not remove data directory");
+}
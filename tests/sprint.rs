@@ -0,0 +1,42 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn questions_are_asked_until_time_is_up() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["--time-limit=1s", "--no-history", "powers", "--base=1"]) // Every answer is 1
+        .write_stdin("1\n1\n1\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Time limit: 1.0s"))
+        .stdout(predicate::str::contains("time left: "))
+        .stdout(predicate::str::contains("Time is up!"))
+        .stdout(predicate::str::contains(
+            "Questions total: 3\nCorrect answers: 3/3\nAccuracy: 100.00%\nQuestions per minute: ",
+        ));
+}
+
+#[test]
+fn time_limit_with_number_of_questions() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["-n", "3", "-t", "2", "--no-history", "powers"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Usage:")
+                .and(predicate::str::contains("cannot be used together")),
+        );
+}
+
+#[test]
+fn invalid_time_limit() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["--time-limit=1x", "powers"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid time limit: '1x'"));
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn squares_unrecognised_arg() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["squares", "--incorrect"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Usage:").and(predicate::str::contains("unrecognised option")),
+        );
+}
+
+#[test]
+fn squares_show_help() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    let pred = predicate::str::contains("Usage:");
+    let pred = pred.and(predicate::str::contains("Squares options"));
+    let pred = pred.and(predicate::str::contains("Display help for squares command"));
+
+    cmd.arg("squares").arg("-h").assert().success().stdout(pred);
+}
+
+#[test]
+fn squares_one_question_correct_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "squares",
+        "-l",
+        "47",
+        "-u",
+        "47",
+    ])
+    .write_stdin("2209")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("47^2"))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
+
+#[test]
+fn cube_root_correct_answer() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args([
+        "--number-of-questions=1",
+        "--no-history",
+        "squares",
+        "--exponent=3",
+        "--mode=inverse",
+        "-l",
+        "27",
+        "-u",
+        "27",
+    ])
+    .write_stdin("27")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("∛19683"))
+    .stdout(predicate::str::contains("Correct answers: 1/1"));
+}
@@ -39,6 +39,7 @@ fn times_table_one_question_correct_answer() {
     let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
     cmd.args([
         "--number-of-questions=1",
+        "--no-history",
         "times_table",
         "--lower-boundary-1=7",
         "--upper-boundary-1=7",
@@ -54,7 +55,7 @@ fn times_table_one_question_correct_answer() {
 #[test]
 fn times_table_one_question_incorrect_answer() {
     let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
-    cmd.args(["--number-of-questions=1", "times_table"])
+    cmd.args(["--number-of-questions=1", "--no-history", "times_table"])
         .write_stdin("hehe") // "hehe" is probably not an answer to any times
                                                   // table question
         .assert()
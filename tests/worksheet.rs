@@ -0,0 +1,45 @@
+mod common;
+
+use std::{env, fs};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use common::CMD;
+
+#[test]
+fn worksheet_with_answer_key() {
+    let dir = env::temp_dir().join(format!("practicestuff_worksheet_{}", std::process::id()));
+    let path = dir.join("times_table.md");
+    let worksheet_arg = format!("--worksheet={}", path.display());
+
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["-n", "4", &worksheet_arg, "times_table"])
+        .args(["--lower-boundary-1=7", "--upper-boundary-1=7"])
+        .args(["--lower-boundary-2=8", "--upper-boundary-2=8"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Worksheet written to"))
+        .stdout(predicate::str::contains("Q: ").not());
+
+    let worksheet = fs::read_to_string(&path).expect("worksheet not written");
+    let answer_key =
+        fs::read_to_string(dir.join("times_table-answers.md")).expect("answer key not written");
+    fs::remove_dir_all(&dir).expect("could not remove worksheet directory");
+
+    assert!(worksheet.starts_with("# Worksheet: times_table\n"));
+    assert_eq!(worksheet.matches(r"\*").count(), 4); // 7*8 or 8*7
+    assert!(worksheet.contains("| 4. "));
+    assert!(answer_key.contains("| 1. 56 | 2. 56 | 3. 56 |\n| 4. 56 |  |  |\n"));
+}
+
+#[test]
+fn worksheet_needs_number_of_questions() {
+    let mut cmd = Command::cargo_bin(CMD).expect("crate not found");
+    cmd.args(["-n", "0", "--worksheet=sheet.txt", "powers"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "worksheet needs a limited number of questions",
+        ));
+}